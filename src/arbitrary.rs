@@ -0,0 +1,206 @@
+use crate::{
+    test_util::{
+        CommentBuilder,
+        LinkBuilder,
+    },
+    types::{
+        Comment,
+        Link,
+        Listing,
+        Thing,
+        ThingData,
+    },
+};
+use arbitrary::{
+    Arbitrary,
+    Result,
+    Unstructured,
+};
+
+/// Subreddits that generated things are posted in.
+const SUBREDDITS: &[&str] = &["test", "rust", "aww", "dankmemes", "AskReddit"];
+
+/// Accounts that generated things are posted by.
+const AUTHORS: &[&str] = &["test_user", "spez", "AutoModerator", "[deleted]"];
+
+/// Words that generated titles and bodies are made of.
+const WORDS: &[&str] = &[
+    "the",
+    "a",
+    "cat",
+    "rust",
+    "reddit",
+    "today",
+    "I",
+    "found",
+    "this",
+    "**bold**",
+    "&amp;",
+    "[link](https://example.com)",
+    "\n\n",
+];
+
+/// Domains that generated link posts point to.
+const DOMAINS: &[&str] = &["i.redd.it", "v.redd.it", "imgur.com", "www.youtube.com"];
+
+/// The deepest reply chain a generated comment may have.
+const MAX_REPLY_DEPTH: u32 = 3;
+
+/// The most children a generated listing or comment may have.
+const MAX_CHILDREN: u32 = 8;
+
+/// Generate a base36 id, like "2zxg4y".
+fn id(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(5..=7)?;
+    (0..len)
+        .map(|_| {
+            let digit = u.int_in_range(0..=35)?;
+            Ok(char::from_digit(digit, 36).unwrap())
+        })
+        .collect()
+}
+
+/// Generate a run of words, between `min` and `max` long.
+fn text(u: &mut Unstructured<'_>, min: u32, max: u32) -> Result<String> {
+    let len = u.int_in_range(min..=max)?;
+    let mut text = String::new();
+    for i in 0..len {
+        if i != 0 {
+            text.push(' ');
+        }
+        text.push_str(u.choose(WORDS)?);
+    }
+    Ok(text)
+}
+
+/// Generate a time of creation in UTC epoch-second format, between reddit's launch and now.
+fn created_utc(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(f64::from(
+        u.int_in_range(1_119_484_800..=1_790_000_000_u32)?,
+    ))
+}
+
+/// Generate a comment in the given link, replying to the given comment id if there is one.
+fn comment(
+    u: &mut Unstructured<'_>,
+    link_id: &str,
+    parent_id: Option<&str>,
+    depth: u32,
+) -> Result<Comment> {
+    let id = id(u)?;
+    let mut builder = CommentBuilder::new(&id)
+        .link_id(link_id)
+        .author(u.choose(AUTHORS)?)
+        .subreddit(u.choose(SUBREDDITS)?)
+        .body(&text(u, 1, 40)?)
+        .score(u.int_in_range(-50..=50_000)?)
+        .created_utc(created_utc(u)?);
+    if let Some(parent_id) = parent_id {
+        builder = builder.parent_comment(parent_id);
+    }
+
+    if depth < MAX_REPLY_DEPTH && u.ratio(1, 3)? {
+        let len = u.int_in_range(1..=MAX_CHILDREN)?;
+        let replies = (0..len)
+            .map(|_| comment(u, link_id, Some(&id), depth + 1))
+            .collect::<Result<Vec<_>>>()?;
+        builder = builder.replies(replies);
+    }
+
+    Ok(builder.build())
+}
+
+impl<'a> Arbitrary<'a> for Link {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut builder = LinkBuilder::new(&id(u)?)
+            .title(&text(u, 1, 20)?)
+            .author(u.choose(AUTHORS)?)
+            .subreddit(u.choose(SUBREDDITS)?)
+            .score(u.int_in_range(-50..=100_000)?)
+            .num_comments(u.int_in_range(0..=20_000)?)
+            .created_utc(created_utc(u)?)
+            .over_18(u.ratio(1, 10)?)
+            .spoiler(u.ratio(1, 20)?)
+            .stickied(u.ratio(1, 20)?);
+
+        if u.arbitrary()? {
+            builder = builder.selftext(&text(u, 0, 100)?);
+        } else {
+            let url = format!("https://{}/{}", u.choose(DOMAINS)?, id(u)?);
+            builder = builder.url(&url);
+        }
+        if u.ratio(1, 4)? {
+            builder = builder.flair(&text(u, 1, 3)?);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Comment {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let link_id = id(u)?;
+        comment(u, &link_id, None, 0)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Listing {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.int_in_range(0..=MAX_CHILDREN)?;
+        let links = u.arbitrary()?;
+        let link_id = id(u)?;
+        let children = (0..len)
+            .map(|_| {
+                let data = if links {
+                    ThingData::Link(Box::new(Link::arbitrary(u)?))
+                } else {
+                    ThingData::Comment(Box::new(comment(u, &link_id, None, 0)?))
+                };
+                Ok(Thing {
+                    id: None,
+                    name: None,
+                    data,
+                })
+            })
+            .collect::<Result<Vec<Thing>>>()?;
+
+        let after = match children.last() {
+            Some(last) if u.arbitrary()? => last.fullname().map(Into::into),
+            _ => None,
+        };
+
+        Ok(Listing {
+            before: None,
+            after,
+            modhash: "".into(),
+            children,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::parse_thing_strict;
+
+    #[test]
+    fn generated_things_parse() {
+        let data: Vec<u8> = (0..4096_u32).map(|i| (i * 31 % 251) as u8).collect();
+        let mut u = Unstructured::new(&data);
+
+        let listing = Listing::arbitrary(&mut u).unwrap();
+        let thing = Thing {
+            id: None,
+            name: None,
+            data: ThingData::Listing(Box::new(listing)),
+        };
+        let text = serde_json::to_string(&thing).unwrap();
+        parse_thing_strict(&text).unwrap();
+
+        let comment = Comment::arbitrary(&mut u).unwrap();
+        assert!(comment.link_id.starts_with("t3_"));
+        for (reply, _) in comment.walk_replies() {
+            assert!(reply.parent_id.starts_with("t1_"));
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use crate::{
+    error::Error,
+    types::{
+        Comment,
+        Link,
+        Thing,
+    },
+};
+use futures_util::{
+    Stream,
+    StreamExt,
+};
+use std::{
+    collections::HashSet,
+    fs::{
+        File,
+        OpenOptions,
+    },
+    io::{
+        BufRead,
+        BufReader,
+        BufWriter,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+/// The size a file may grow to before a new one is started, if no other size is set.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// The file extension of archive files.
+const EXTENSION: &str = "ndjson";
+
+/// An item that can be archived.
+pub trait Archivable: serde::Serialize {
+    /// Get the fullname of this item, used for deduplication.
+    fn fullname(&self) -> Option<&str>;
+}
+
+impl Archivable for Link {
+    fn fullname(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+}
+
+impl Archivable for Comment {
+    fn fullname(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+}
+
+impl Archivable for Thing {
+    fn fullname(&self) -> Option<&str> {
+        Thing::fullname(self)
+    }
+}
+
+impl<T> Archivable for Box<T>
+where
+    T: Archivable,
+{
+    fn fullname(&self) -> Option<&str> {
+        T::fullname(self)
+    }
+}
+
+/// A writer that appends items to newline-delimited json files in a directory.
+///
+/// Files are named like `{prefix}.00000.ndjson`, and a new file is started when one grows past the max file size.
+/// Items are deduplicated by fullname, including items written before the archive was reopened,
+/// so restarting a stream into the same archive will not write duplicates.
+#[derive(Debug)]
+pub struct ArchiveWriter {
+    dir: PathBuf,
+    prefix: Box<str>,
+    max_file_size: u64,
+
+    /// The fullnames of the items in the archive.
+    seen: HashSet<Box<str>>,
+
+    /// The index of the current file.
+    file_index: usize,
+
+    /// The current file, opened on the first write.
+    file: Option<BufWriter<File>>,
+
+    /// The size of the current file.
+    file_size: u64,
+}
+
+impl ArchiveWriter {
+    /// Open an archive in a directory, creating it if needed.
+    ///
+    /// Existing files with the prefix are read to resume the archive.
+    /// A partially written last line, left by a crash, is removed.
+    pub fn open(dir: impl AsRef<Path>, prefix: &str) -> std::io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+
+        let mut writer = Self {
+            dir,
+            prefix: prefix.into(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            seen: HashSet::new(),
+            file_index: 0,
+            file: None,
+            file_size: 0,
+        };
+
+        let mut file_index = 0;
+        while writer.file_path(file_index).exists() {
+            writer.load_file(file_index)?;
+            file_index += 1;
+        }
+        writer.file_index = file_index.saturating_sub(1);
+
+        Ok(writer)
+    }
+
+    /// Set the size a file may grow to before a new one is started.
+    ///
+    /// Files may be larger than this if a single item is larger.
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Get the number of unique items in the archive.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Returns `true` if the archive has no items.
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Returns `true` if an item with the given fullname is in the archive.
+    pub fn contains(&self, fullname: &str) -> bool {
+        self.seen.contains(fullname)
+    }
+
+    /// Get the path of the file with the given index.
+    fn file_path(&self, index: usize) -> PathBuf {
+        self.dir
+            .join(format!("{}.{index:05}.{EXTENSION}", self.prefix))
+    }
+
+    /// Read the fullnames of an existing file, and remove a partial last line.
+    fn load_file(&mut self, index: usize) -> std::io::Result<()> {
+        #[derive(serde::Deserialize)]
+        struct Record {
+            name: Option<Box<str>>,
+            data: Option<RecordData>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct RecordData {
+            name: Option<Box<str>>,
+        }
+
+        let path = self.file_path(index);
+        let mut reader = BufReader::new(File::open(&path)?);
+        let mut line = Vec::new();
+        let mut complete_size = 0;
+        loop {
+            line.clear();
+            let n = reader.read_until(b'\n', &mut line)?;
+            if n == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            complete_size += n as u64;
+
+            // Lines that are not records are skipped, as they cannot be deduplicated anyways.
+            if let Ok(record) = serde_json::from_slice::<Record>(&line) {
+                let name = record
+                    .name
+                    .or_else(|| record.data.and_then(|data| data.name));
+                if let Some(name) = name {
+                    self.seen.insert(name);
+                }
+            }
+        }
+
+        if complete_size != std::fs::metadata(&path)?.len() {
+            OpenOptions::new()
+                .write(true)
+                .open(&path)?
+                .set_len(complete_size)?;
+        }
+        self.file_size = complete_size;
+
+        Ok(())
+    }
+
+    /// Write an item to the archive.
+    ///
+    /// Returns `false` if the item was already in the archive, in which case nothing is written.
+    /// Items without a fullname are always written.
+    pub fn write<T>(&mut self, item: &T) -> std::io::Result<bool>
+    where
+        T: Archivable,
+    {
+        if let Some(fullname) = item.fullname() {
+            if self.seen.contains(fullname) {
+                return Ok(false);
+            }
+        }
+
+        let mut line = serde_json::to_vec(item)?;
+        line.push(b'\n');
+        let line_size = line.len() as u64;
+
+        if self.file_size > 0 && self.file_size + line_size > self.max_file_size {
+            self.rotate()?;
+        }
+
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.file_path(self.file_index))?;
+                self.file.insert(BufWriter::new(file))
+            }
+        };
+        file.write_all(&line)?;
+        self.file_size += line_size;
+
+        if let Some(fullname) = item.fullname() {
+            self.seen.insert(fullname.into());
+        }
+
+        Ok(true)
+    }
+
+    /// Finish the current file and start a new one.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        self.file_index += 1;
+        self.file_size = 0;
+
+        Ok(())
+    }
+
+    /// Flush buffered items to the current file.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for ArchiveWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Write the items of a stream to an archive until the stream ends, returning the number of new items written.
+///
+/// Each item is flushed as it is written, so the archive is complete up to the last item if the process stops.
+/// Retryable errors, like ones yielded by [`Client::stream_new_posts`](crate::Client::stream_new_posts), are skipped.
+/// Other errors stop archiving and are returned.
+pub async fn archive_stream<S, T>(stream: S, writer: &mut ArchiveWriter) -> Result<u64, Error>
+where
+    S: Stream<Item = Result<T, Error>>,
+    T: Archivable,
+{
+    let mut stream = std::pin::pin!(stream);
+    let mut num_written = 0;
+    while let Some(result) = stream.next().await {
+        let item = match result {
+            Ok(item) => item,
+            Err(error) if error.is_retryable() => continue,
+            Err(error) => return Err(error),
+        };
+
+        if writer.write(&item)? {
+            writer.flush()?;
+            num_written += 1;
+        }
+    }
+
+    Ok(num_written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Get an empty directory for a test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("reddit-archive-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn link(id: &str) -> Link {
+        Link {
+            id: id.into(),
+            name: format!("t3_{id}").into(),
+            ..Link::default()
+        }
+    }
+
+    #[test]
+    fn dedup_and_resume() {
+        let dir = test_dir("resume");
+
+        let mut writer = ArchiveWriter::open(&dir, "links").unwrap();
+        assert!(writer.write(&link("a")).unwrap());
+        assert!(writer.write(&link("b")).unwrap());
+        assert!(!writer.write(&link("a")).unwrap());
+        drop(writer);
+
+        // Simulate a crash in the middle of a write.
+        let path = dir.join("links.00000.ndjson");
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"name\":\"t3_c\",").unwrap();
+        drop(file);
+
+        let mut writer = ArchiveWriter::open(&dir, "links").unwrap();
+        assert_eq!(writer.len(), 2);
+        assert!(!writer.write(&link("b")).unwrap());
+        assert!(writer.write(&link("c")).unwrap());
+        drop(writer);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let links = text
+            .lines()
+            .map(|line| serde_json::from_str::<Link>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(links.len(), 3);
+        assert_eq!(&*links[2].name, "t3_c");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rotation() {
+        let dir = test_dir("rotation");
+
+        let mut writer = ArchiveWriter::open(&dir, "links").unwrap().max_file_size(1);
+        for id in ["a", "b", "c"] {
+            assert!(writer.write(&link(id)).unwrap());
+        }
+        drop(writer);
+
+        for index in 0..3 {
+            let path = dir.join(format!("links.{index:05}.ndjson"));
+            assert_eq!(std::fs::read_to_string(path).unwrap().lines().count(), 1);
+        }
+
+        let writer = ArchiveWriter::open(&dir, "links").unwrap();
+        assert_eq!(writer.len(), 3);
+        assert!(writer.contains("t3_b"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::{
+        Duration,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+/// The extension of cache entry files.
+const ENTRY_EXTENSION: &str = "cache";
+
+/// A cache of responses on disk, consulted by a [`Client`](crate::Client) before making requests.
+///
+/// Each response is stored in its own file in the cache directory, named by a hash of the request.
+/// Only successful json responses to anonymous GET requests are cached.
+/// Requests made with an access token are never cached, and neither are the polls of streams.
+///
+/// The cache is best-effort; entries that cannot be read or written are treated as missing.
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    /// the directory entries are stored in
+    dir: PathBuf,
+
+    /// how long entries are used for after being stored
+    ttl: Duration,
+}
+
+/// A response stored in a [`DiskCache`].
+#[derive(Debug)]
+pub(crate) struct CachedResponse {
+    /// the url of the response, after redirects
+    pub url: url::Url,
+
+    /// the status code of the response
+    pub status: u16,
+
+    /// the headers of the response, skipping those that are not valid utf8
+    pub headers: Vec<(String, String)>,
+
+    /// the response body
+    pub body: bytes::Bytes,
+}
+
+/// The first line of a cache entry file, which is followed by the response body.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct EntryHeader {
+    key: String,
+    expires_at: u64,
+    url: url::Url,
+    status: u16,
+    headers: Vec<(String, String)>,
+}
+
+impl DiskCache {
+    /// Create a cache in the given directory, using entries for ttl after they are stored.
+    ///
+    /// The directory is created when the first entry is stored.
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl,
+        }
+    }
+
+    /// Get the directory entries are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Get how long entries are used for after being stored.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Remove all entries of this cache.
+    pub async fn clear(&self) -> std::io::Result<()> {
+        self.remove_entries(|_| true).await
+    }
+
+    /// Remove the entries of this cache that have expired.
+    pub async fn remove_expired(&self) -> std::io::Result<()> {
+        let now = unix_time();
+        self.remove_entries(|header| header.is_none_or(|header| header.expires_at <= now))
+            .await
+    }
+
+    /// Remove the entries whose header matches the predicate.
+    ///
+    /// The header is `None` if it could not be read.
+    async fn remove_entries(
+        &self,
+        mut predicate: impl FnMut(Option<&EntryHeader>) -> bool,
+    ) -> std::io::Result<()> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some(ENTRY_EXTENSION) {
+                continue;
+            }
+
+            let header = tokio::fs::read(&path)
+                .await
+                .ok()
+                .and_then(|data| split_entry(&data).map(|(header, _)| header));
+            if predicate(header.as_ref()) {
+                match tokio::fs::remove_file(&path).await {
+                    Ok(()) => {}
+                    Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the response stored under a key, if it has not expired.
+    pub(crate) async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let data = tokio::fs::read(self.entry_path(key)).await.ok()?;
+        let (header, body) = split_entry(&data)?;
+        // Different keys may have the same hash.
+        if header.key != key || header.expires_at <= unix_time() {
+            return None;
+        }
+
+        Some(CachedResponse {
+            url: header.url,
+            status: header.status,
+            headers: header.headers,
+            body: bytes::Bytes::copy_from_slice(body),
+        })
+    }
+
+    /// Store a response under a key.
+    pub(crate) async fn put(&self, key: &str, response: &CachedResponse) -> std::io::Result<()> {
+        let header = EntryHeader {
+            key: key.into(),
+            expires_at: unix_time().saturating_add(self.ttl.as_secs()),
+            url: response.url.clone(),
+            status: response.status,
+            headers: response.headers.clone(),
+        };
+        let mut data = serde_json::to_vec(&header)?;
+        data.push(b'\n');
+        data.extend_from_slice(&response.body);
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        // Write to a temporary file first, so readers never see a partial entry.
+        let path = self.entry_path(key);
+        let temp_path = path.with_extension(format!("{ENTRY_EXTENSION}.tmp"));
+        tokio::fs::write(&temp_path, data).await?;
+        tokio::fs::rename(&temp_path, &path).await
+    }
+
+    /// Get the path of the entry file of a key.
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.{ENTRY_EXTENSION}", fnv1a(key.as_bytes())))
+    }
+}
+
+impl CachedResponse {
+    /// Read a response received from the network.
+    pub(crate) async fn from_response(response: reqwest::Response) -> Result<Self, reqwest::Error> {
+        let url = response.url().clone();
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response.bytes().await?;
+
+        Ok(Self {
+            url,
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Returns `true` if this response should be stored.
+    pub(crate) fn is_cacheable(response: &reqwest::Response) -> bool {
+        response.status() == reqwest::StatusCode::OK
+            && response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|content_type| content_type.contains("json"))
+    }
+
+    /// Convert this into a response, as if it had been received from the network.
+    pub(crate) fn into_response(self) -> reqwest::Response {
+        use reqwest::ResponseBuilderExt;
+
+        let status = http::StatusCode::from_u16(self.status).unwrap_or(http::StatusCode::OK);
+        let mut response = http::Response::builder()
+            .status(status)
+            .url(self.url)
+            .body(self.body)
+            .expect("valid response");
+        for (name, value) in self.headers {
+            let (Ok(name), Ok(value)) = (
+                http::HeaderName::from_bytes(name.as_bytes()),
+                http::HeaderValue::from_str(&value),
+            ) else {
+                continue;
+            };
+            response.headers_mut().append(name, value);
+        }
+
+        reqwest::Response::from(response)
+    }
+}
+
+/// Returns `true` if the response to a request may be read from and stored in the cache.
+///
+/// Only anonymous GET requests are cached, so responses for one account are never written to disk or shared.
+pub(crate) fn is_cacheable_request(request: &reqwest::Request) -> bool {
+    request.method() == reqwest::Method::GET
+        && !request
+            .headers()
+            .contains_key(reqwest::header::AUTHORIZATION)
+}
+
+/// Make the cache key of a request.
+pub(crate) fn request_key(request: &reqwest::Request) -> String {
+    format!("{} {}", request.method(), request.url())
+}
+
+/// Split a cache entry file into its header and body.
+fn split_entry(data: &[u8]) -> Option<(EntryHeader, &[u8])> {
+    let newline = data.iter().position(|&byte| byte == b'\n')?;
+    let header = serde_json::from_slice(&data[..newline]).ok()?;
+
+    Some((header, &data[newline + 1..]))
+}
+
+/// Get the current time, in epoch-seconds.
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Hash bytes with 64 bit FNV-1a, which is stable across runs and versions, unlike std's hasher.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample_response() -> CachedResponse {
+        CachedResponse {
+            url: "https://www.reddit.com/r/rust.json".parse().unwrap(),
+            status: 200,
+            headers: vec![("content-type".into(), "application/json".into())],
+            body: bytes::Bytes::from_static(b"{\"kind\": \"Listing\"}\n"),
+        }
+    }
+
+    #[tokio::test]
+    async fn disk_cache() {
+        let dir = std::env::temp_dir().join(format!("reddit-rs-cache-test-{}", std::process::id()));
+        let cache = DiskCache::new(&dir, Duration::from_secs(60));
+        let expired = DiskCache::new(&dir, Duration::ZERO);
+
+        assert!(cache.get("GET a").await.is_none());
+        cache.put("GET a", &sample_response()).await.unwrap();
+        expired.put("GET b", &sample_response()).await.unwrap();
+
+        let response = cache.get("GET a").await.unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(&*response.body, b"{\"kind\": \"Listing\"}\n");
+        assert!(cache.get("GET b").await.is_none());
+
+        let response = response.into_response();
+        assert_eq!(
+            response.url().as_str(),
+            "https://www.reddit.com/r/rust.json"
+        );
+        assert_eq!(
+            response.headers()[reqwest::header::CONTENT_TYPE],
+            "application/json"
+        );
+        assert_eq!(response.text().await.unwrap(), "{\"kind\": \"Listing\"}\n");
+
+        cache.remove_expired().await.unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        cache.clear().await.unwrap();
+        assert!(cache.get("GET a").await.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cacheable_requests() {
+        let client = reqwest::Client::new();
+        let anonymous = client
+            .get("https://www.reddit.com/r/rust.json")
+            .build()
+            .unwrap();
+        let authenticated = client
+            .get("https://oauth.reddit.com/r/rust")
+            .bearer_auth("token")
+            .build()
+            .unwrap();
+        let post = client
+            .post("https://www.reddit.com/api/read_message")
+            .build()
+            .unwrap();
+
+        assert!(is_cacheable_request(&anonymous));
+        assert!(!is_cacheable_request(&authenticated));
+        assert!(!is_cacheable_request(&post));
+        assert_eq!(
+            request_key(&anonymous),
+            "GET https://www.reddit.com/r/rust.json"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+/// The url captcha images are served from.
+const CAPTCHA_URL: &str = "https://www.reddit.com/captcha";
+
+/// A captcha to solve before using an endpoint that requires one.
+///
+/// Reddit asks new and low karma accounts to solve these, see [`Client::needs_captcha`](crate::Client::needs_captcha).
+/// See https://www.reddit.com/dev/api#section_captcha
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captcha {
+    iden: Box<str>,
+}
+
+impl Captcha {
+    /// Create a captcha from its identifier.
+    pub fn new(iden: &str) -> Self {
+        Self { iden: iden.into() }
+    }
+
+    /// Get the identifier of this captcha.
+    pub fn iden(&self) -> &str {
+        &self.iden
+    }
+
+    /// Get the url of the image of this captcha, a png.
+    pub fn image_url(&self) -> String {
+        format!("{CAPTCHA_URL}/{}.png", self.iden)
+    }
+
+    /// Attach the text read from the image of this captcha, to send with a request.
+    pub fn solve(&self, answer: &str) -> CaptchaSolution {
+        CaptchaSolution {
+            iden: self.iden.clone(),
+            answer: answer.into(),
+        }
+    }
+}
+
+/// A captcha and the text read from its image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptchaSolution {
+    iden: Box<str>,
+    answer: Box<str>,
+}
+
+impl CaptchaSolution {
+    /// Get the identifier of the captcha.
+    pub fn iden(&self) -> &str {
+        &self.iden
+    }
+
+    /// Get the text read from the image of the captcha.
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    /// Get the form parameters of this solution, which are sent with the request that required it.
+    pub(crate) fn form(&self) -> [(&'static str, &str); 2] {
+        [("iden", &self.iden), ("captcha", &self.answer)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn captcha_solution() {
+        let captcha = Captcha::new("xk5fgJ2IbCnH0TqkHNyk4TfTxaphGoWD");
+        assert_eq!(
+            captcha.image_url(),
+            "https://www.reddit.com/captcha/xk5fgJ2IbCnH0TqkHNyk4TfTxaphGoWD.png"
+        );
+
+        let solution = captcha.solve("ABCDEF");
+        assert_eq!(
+            solution.form(),
+            [
+                ("iden", "xk5fgJ2IbCnH0TqkHNyk4TfTxaphGoWD"),
+                ("captcha", "ABCDEF")
+            ]
+        );
+    }
+}
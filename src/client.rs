@@ -1,94 +1,2218 @@
+#[cfg(feature = "disk-cache")]
+use crate::cache::{
+    CachedResponse,
+    DiskCache,
+};
+#[cfg(feature = "opengraph")]
+use crate::opengraph::{
+    parse_opengraph,
+    OpenGraph,
+};
 use crate::{
+    captcha::Captcha,
+    comments::{
+        take_comment_with_ancestors,
+        CommentContext,
+        MAX_COMMENT_CONTEXT,
+    },
     error::Error,
-    types::Thing,
+    inbox::ReadAllStatus,
+    light::{
+        LightLink,
+        LightThing,
+    },
+    media::{
+        extension_from_mime_type,
+        unescape_url,
+    },
+    pagination::{
+        Paginator,
+        MAX_LIMIT,
+    },
+    parse::ParseMode,
+    rate_limit::RateLimit,
+    reddit_url::RedditUrl,
+    stream::{
+        SourcedItem,
+        StreamOptions,
+    },
+    submit::{
+        PostRequirements,
+        SubmissionDraft,
+    },
+    types::{
+        params::Sort,
+        Collection,
+        CollectionDisplayLayout,
+        Comment,
+        Emoji,
+        EmojiPermissions,
+        Link,
+        Listing,
+        Message,
+        ModeratedSubreddit,
+        PartialUser,
+        Preferences,
+        StructuredStyles,
+        Subreddit,
+        SubredditKarma,
+        SubredditRelationship,
+        SubredditSettings,
+        Thing,
+        ThingData,
+        VoteDirection,
+    },
+    upload::S3UploadLease,
+    user_agent::UserAgent,
+    widgets::Widgets,
+};
+use futures_util::{
+    Stream,
+    StreamExt,
+    TryStreamExt,
+};
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
 };
+use tokio::io::AsyncWriteExt;
+
+/// The base url for requests that do not need an access token.
+pub(crate) const BASE_URL: &str = "https://www.reddit.com";
+
+/// The base url for requests that need an access token.
+const OAUTH_BASE_URL: &str = "https://oauth.reddit.com";
+
+/// The cookie reddit's website sets when a user opts in to viewing quarantined subreddits.
+const QUARANTINE_OPT_IN_COOKIE: &str = "_options=%7B%22pref_quarantine_optin%22%3A%20true%7D";
+
+/// Reddit will redirect us here if a subreddit could not be found.
+const SEARCH_URL: &str = "https://www.reddit.com/subreddits/search.json?";
+
+/// A client to access reddit
+#[derive(Clone)]
+pub struct Client {
+    /// The inner http client.
+    ///
+    /// It probably shouldn't be used directly by you.
+    /// It also sets a strange user-agent as well in accordance with reddit's request.
+    pub client: reqwest::Client,
+
+    /// How responses are parsed.
+    parse_mode: ParseMode,
+
+    /// The OAuth access token, if authenticated.
+    access_token: Option<Box<str>>,
+
+    /// Whether `raw_json=1` is sent with requests.
+    raw_json: bool,
+
+    /// Whether the quarantine opt-in cookie is sent with requests.
+    quarantine_opt_in: bool,
+
+    /// The last rate limit state reported by reddit.
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+
+    /// The middleware client requests are sent through, if any.
+    #[cfg(feature = "middleware")]
+    middleware: Option<reqwest_middleware::ClientWithMiddleware>,
+
+    /// The user-agent, added to requests sent through middleware.
+    #[cfg(feature = "middleware")]
+    user_agent: reqwest::header::HeaderValue,
+
+    /// The cache responses are read from and stored in, if any.
+    #[cfg(feature = "disk-cache")]
+    disk_cache: Option<Arc<DiskCache>>,
+}
+
+impl Client {
+    /// Create a new [`Client`].
+    pub fn new() -> Self {
+        Self::builder()
+            .build()
+            .expect("failed to build reddit client")
+    }
+
+    /// Create a new [`Client`] with a user-agent.
+    ///
+    /// The components are not validated, and are formatted as given.
+    /// See https://github.com/reddit-archive/reddit/wiki/API#rules
+    #[deprecated = "use `Client::try_new_with_user_agent` or `ClientBuilder::user_agent` instead"]
+    pub fn new_with_user_agent(
+        platform: &str,
+        app_id: &str,
+        app_version: &str,
+        reddit_username: &str,
+    ) -> Self {
+        let user_agent =
+            UserAgent::from_parts_unchecked(platform, app_id, app_version, reddit_username);
+
+        Self::builder()
+            .user_agent(user_agent)
+            .build()
+            .expect("failed to build reddit client")
+    }
+
+    /// Create a new [`Client`] with a user-agent, validating it.
+    ///
+    /// This fails with [`Error::InvalidUserAgent`] if a component does not follow reddit's rules.
+    /// See [`UserAgentBuilder`](crate::user_agent::UserAgentBuilder) for how components are normalized.
+    /// See https://github.com/reddit-archive/reddit/wiki/API#rules
+    pub fn try_new_with_user_agent(
+        platform: &str,
+        app_id: &str,
+        app_version: &str,
+        reddit_username: &str,
+    ) -> Result<Self, Error> {
+        let user_agent = UserAgent::builder()
+            .platform(platform)
+            .app_id(app_id)
+            .version(app_version)
+            .username(reddit_username)
+            .build()?;
+
+        Self::builder().user_agent(user_agent).build()
+    }
+
+    /// Create a new [`Client`] configured from environment variables.
+    ///
+    /// `REDDIT_USER_AGENT` sets the user-agent, in the format parsed by [`UserAgent`].
+    /// If `REDDIT_CLIENT_ID`, `REDDIT_CLIENT_SECRET`, `REDDIT_USERNAME`, and `REDDIT_PASSWORD` are set,
+    /// an access token is requested for the user with the password grant of a script app.
+    /// If none of them are set, the client is not authenticated.
+    /// Setting only some of them fails with [`Error::MissingEnvVar`].
+    ///
+    /// See https://github.com/reddit-archive/reddit/wiki/OAuth2-Quick-Start-Example
+    pub async fn from_env() -> Result<Self, Error> {
+        let config = EnvConfig::from_vars(|name| std::env::var(name).ok())?;
+
+        let mut builder = Self::builder();
+        if let Some(user_agent) = config.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        let client = builder.build()?;
+
+        let Some(credentials) = config.credentials else {
+            return Ok(client);
+        };
+        let access_token = client.fetch_password_token(&credentials).await?;
+
+        Ok(Self {
+            access_token: Some(access_token),
+            ..client
+        })
+    }
+
+    /// Request an access token with the password grant of a script app.
+    async fn fetch_password_token(
+        &self,
+        credentials: &PasswordCredentials,
+    ) -> Result<Box<str>, Error> {
+        #[derive(serde::Deserialize)]
+        struct AccessTokenResponse {
+            access_token: Option<Box<str>>,
+            error: Option<Box<str>>,
+        }
+
+        let request = self
+            .client
+            .post(format!("{BASE_URL}/api/v1/access_token"))
+            .basic_auth(&credentials.client_id, Some(&credentials.client_secret))
+            .form(&[
+                ("grant_type", "password"),
+                ("username", &credentials.username),
+                ("password", &credentials.password),
+            ]);
+        let res = self.send(request).await?;
+        let response: AccessTokenResponse = self.parse_response(res).await?;
+
+        match (response.access_token, response.error) {
+            (Some(access_token), _) => Ok(access_token),
+            (None, error) => Err(Error::Api {
+                code: error.unwrap_or_else(|| "unknown_error".into()),
+                message: "failed to get an access token".into(),
+                field: None,
+            }),
+        }
+    }
+
+    /// Create a new [`ClientBuilder`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Get the [`ParseMode`] used by this client.
+    pub fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    /// Create a request to the given url.
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let mut request = self.client.request(method, url);
+        if self.raw_json {
+            request = request.query(&[("raw_json", "1")]);
+        }
+        if self.quarantine_opt_in {
+            request = request.header(reqwest::header::COOKIE, QUARANTINE_OPT_IN_COOKIE);
+        }
+        request
+    }
+
+    /// Create a request to the OAuth api, with the given path.
+    ///
+    /// This fails if the client does not have an access token.
+    fn oauth_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, Error> {
+        let access_token = self
+            .access_token
+            .as_deref()
+            .ok_or(Error::MissingAccessToken)?;
+        let url = format!("{OAUTH_BASE_URL}{path}");
+
+        Ok(self.request(method, &url).bearer_auth(access_token))
+    }
+
+    /// Create a request to the api, with the given path.
+    ///
+    /// This uses the OAuth api if the client has an access token.
+    pub(crate) fn api_request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> reqwest::RequestBuilder {
+        match self.access_token.as_deref() {
+            Some(access_token) => {
+                let url = format!("{OAUTH_BASE_URL}{path}");
+                self.request(method, &url).bearer_auth(access_token)
+            }
+            None => {
+                let url = format!("{BASE_URL}{path}.json");
+                self.request(method, &url)
+            }
+        }
+    }
+
+    /// Send a request, respecting and updating the rate limit.
+    ///
+    /// This fails if the response has an error status.
+    pub(crate) async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        self.send_inner(request, true).await
+    }
+
+    /// Send a request like [`Client::send`], but never read the response from or store it in the disk cache.
+    ///
+    /// This is used for requests that should always see the newest data, like the polls of streams.
+    pub(crate) async fn send_uncached(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        self.send_inner(request, false).await
+    }
+
+    /// Send a request, using the disk cache if use_cache is `true` and the request is cacheable.
+    #[cfg_attr(not(feature = "disk-cache"), allow(unused_variables))]
+    async fn send_inner(
+        &self,
+        request: reqwest::RequestBuilder,
+        use_cache: bool,
+    ) -> Result<reqwest::Response, Error> {
+        #[cfg(feature = "disk-cache")]
+        let cache_key = self
+            .disk_cache
+            .as_ref()
+            .filter(|_| use_cache)
+            .and_then(|_| request.try_clone()?.build().ok())
+            .filter(crate::cache::is_cacheable_request)
+            .map(|request| crate::cache::request_key(&request));
+        #[cfg(feature = "disk-cache")]
+        if let (Some(disk_cache), Some(cache_key)) =
+            (self.disk_cache.as_ref(), cache_key.as_deref())
+        {
+            if let Some(cached) = disk_cache.get(cache_key).await {
+                return Ok(cached.into_response());
+            }
+        }
+
+        loop {
+            let wait_duration = self
+                .rate_limit
+                .lock()
+                .unwrap_or_else(|error| error.into_inner())
+                .as_mut()
+                .map(|rate_limit| rate_limit.reserve())
+                .unwrap_or_default();
+            if wait_duration.is_zero() {
+                break;
+            }
+            tokio::time::sleep(wait_duration).await;
+        }
+
+        let response = self.execute(request).await?;
+
+        if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+            *self
+                .rate_limit
+                .lock()
+                .unwrap_or_else(|error| error.into_inner()) = Some(rate_limit);
+        }
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::RateLimited {
+                retry_after: crate::rate_limit::retry_after_from_headers(response.headers()),
+                url: Some(Box::new(response.url().clone())),
+                status: Some(response.status()),
+            });
+        }
+
+        // Reddit will redirect browsers here if the subreddit is quarantined.
+        if response.url().path().starts_with("/quarantine") {
+            return Err(Error::SubredditQuarantined {
+                url: Some(Box::new(response.url().clone())),
+                status: None,
+            });
+        }
+
+        if let Err(error) = response.error_for_status_ref() {
+            let status = response.status();
+            if status == reqwest::StatusCode::FORBIDDEN || status == reqwest::StatusCode::NOT_FOUND
+            {
+                let url = response.url().clone();
+                let text = response.text().await?;
+                if let Some(error) = Error::from_error_body(&text, status, &url) {
+                    return Err(error);
+                }
+            }
+
+            return Err(error.into());
+        }
+
+        #[cfg(feature = "disk-cache")]
+        if let (Some(disk_cache), Some(cache_key)) =
+            (self.disk_cache.as_ref(), cache_key.as_deref())
+        {
+            if CachedResponse::is_cacheable(&response) {
+                let cached = CachedResponse::from_response(response).await?;
+                // The cache is best-effort, so failing to store a response is not an error.
+                let _ = disk_cache.put(cache_key, &cached).await;
+                return Ok(cached.into_response());
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Send a request through the middleware client, if there is one.
+    #[cfg(feature = "middleware")]
+    async fn execute(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, Error> {
+        let Some(middleware) = self.middleware.as_ref() else {
+            return Ok(request.send().await?);
+        };
+
+        // The middleware client has its own inner client, which does not set our user-agent.
+        let mut request = request.build()?;
+        request
+            .headers_mut()
+            .entry(reqwest::header::USER_AGENT)
+            .or_insert_with(|| self.user_agent.clone());
+
+        Ok(middleware.execute(request).await?)
+    }
+
+    /// Send a request.
+    #[cfg(not(feature = "middleware"))]
+    async fn execute(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, Error> {
+        Ok(request.send().await?)
+    }
+
+    /// Send a request to a host other than reddit's api, like a media host or the site of a link post.
+    ///
+    /// This goes through the middleware, if there is one, but skips the rate limit and the disk cache, which are for the api.
+    /// This fails if the response has an error status.
+    async fn send_external(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        let response = self.execute(request).await?;
+        Ok(response.error_for_status()?)
+    }
+
+    /// Send a request to a write endpoint, returning the response body.
+    ///
+    /// This fails if reddit reports an api error.
+    async fn send_api(&self, request: reqwest::RequestBuilder) -> Result<String, Error> {
+        let text = self.send(request).await?.text().await?;
+        if let Some(error) = Error::from_api_errors(text.as_bytes()) {
+            return Err(error);
+        }
+
+        Ok(text)
+    }
+
+    /// Get the last rate limit state reported by reddit.
+    ///
+    /// This is shared between clones of this client.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self
+            .rate_limit
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Parse a json response body.
+    ///
+    /// The body is parsed from bytes, and is only copied into a string if parsing fails.
+    /// Parse errors include the body, url, status, and reddit request id of the response.
+    async fn parse_response<T>(&self, response: reqwest::Response) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let url = response.url().clone();
+        let status = response.status();
+        let request_id = request_id(&response);
+        let bytes = response.bytes().await?;
+
+        parse_body(self.parse_mode, &bytes, url, status, request_id)
+    }
+
+    /// Get the top posts of a subreddit where subreddit is the name and num_posts is the number of posts to retrieve.
+    ///
+    /// Reddit returns at most [`MAX_LIMIT`] posts per request,
+    /// so larger numbers of posts are fetched with multiple requests and joined into one listing.
+    /// Fewer posts are returned if the subreddit runs out of posts.
+    pub async fn get_subreddit(&self, subreddit: &str, num_posts: usize) -> Result<Thing, Error> {
+        let mut thing = self
+            .get_subreddit_page(subreddit, num_posts.min(MAX_LIMIT), None, 0)
+            .await?;
+        let ThingData::Listing(listing) = &mut thing.data else {
+            return Ok(thing);
+        };
+
+        fetch_more_pages(
+            &mut listing.children,
+            &mut listing.after,
+            num_posts,
+            |limit, after, count| async move {
+                let page = self
+                    .get_subreddit_page(subreddit, limit, Some(&after), count)
+                    .await?
+                    .data
+                    .into_listing()
+                    .ok_or(Error::ExpectedListing)?;
+                Ok((page.children, page.after))
+            },
+        )
+        .await?;
+
+        Ok(thing)
+    }
+
+    /// Get a page of the top posts of a subreddit, where count is the number of posts already fetched.
+    async fn get_subreddit_page(
+        &self,
+        subreddit: &str,
+        limit: usize,
+        after: Option<&str>,
+        count: usize,
+    ) -> Result<Thing, Error> {
+        let path = format!("/r/{subreddit}");
+        let mut request = self
+            .api_request(reqwest::Method::GET, &path)
+            .query(&[("limit", limit)]);
+        if let Some(after) = after {
+            request = request.query(&[("after", after), ("count", &count.to_string())]);
+        }
+        let res = self.send(request).await?;
+
+        if res.url().as_str().starts_with(SEARCH_URL) {
+            return Err(Error::SubredditNotFound);
+        }
+
+        self.parse_response(res).await
+    }
+
+    /// Get the about page of a subreddit, like its description, subscriber count, icon, and banner.
+    pub async fn get_subreddit_about(&self, subreddit: &str) -> Result<Subreddit, Error> {
+        let path = format!("/r/{subreddit}/about");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let res = self.send(request).await?;
+
+        if res.url().as_str().starts_with(SEARCH_URL) {
+            return Err(Error::SubredditNotFound);
+        }
+
+        let thing: Thing = self.parse_response(res).await?;
+        thing
+            .data
+            .into_subreddit()
+            .map(|subreddit| *subreddit)
+            .ok_or(Error::SubredditNotFound)
+    }
+
+    /// Get the subreddits whose names start with a prefix, for search-as-you-type pickers.
+    ///
+    /// At most 10 subreddits are returned, most relevant first. User profiles are not included.
+    /// See https://www.reddit.com/dev/api#GET_api_subreddit_autocomplete_v2
+    pub async fn autocomplete_subreddits(
+        &self,
+        prefix: &str,
+        include_over_18: bool,
+    ) -> Result<Vec<Subreddit>, Error> {
+        let request = self
+            .api_request(reqwest::Method::GET, "/api/subreddit_autocomplete_v2")
+            .query(&[
+                ("query", prefix),
+                ("include_over_18", bool_param(include_over_18)),
+                ("include_profiles", "false"),
+                ("limit", "10"),
+            ]);
+        let res = self.send(request).await?;
+        let thing: Thing = self.parse_response(res).await?;
+        let listing = thing.data.into_listing().ok_or(Error::ExpectedListing)?;
+
+        Ok(listing
+            .children
+            .into_iter()
+            .filter_map(|thing| thing.data.into_subreddit())
+            .map(|subreddit| *subreddit)
+            .collect())
+    }
+
+    /// Get the hot posts of a subreddit from its rss feed.
+    ///
+    /// Feeds have far less data than [`Client::get_subreddit`],
+    /// but are useful as a fallback when the json endpoints are rate limited or blocked.
+    #[cfg(feature = "rss")]
+    pub async fn get_subreddit_rss(
+        &self,
+        subreddit: &str,
+    ) -> Result<Vec<crate::rss::FeedLink>, Error> {
+        let url = format!("{BASE_URL}/r/{subreddit}.rss");
+        let res = self.send(self.request(reqwest::Method::GET, &url)).await?;
+
+        if res.url().path().starts_with("/subreddits/search") {
+            return Err(Error::SubredditNotFound);
+        }
+
+        let text = res.text().await?;
+        crate::rss::parse_feed(&text).map_err(|error| Error::Feed {
+            data: text.into(),
+            error,
+        })
+    }
+
+    /// Get the top posts of a subreddit in their light representation, where subreddit is the name and num_posts is the number of posts to retrieve.
+    ///
+    /// This is faster than [`Client::get_subreddit`] for bulk jobs that do not need every field.
+    pub async fn get_subreddit_light(
+        &self,
+        subreddit: &str,
+        num_posts: usize,
+    ) -> Result<Vec<LightLink>, Error> {
+        let path = format!("/r/{subreddit}");
+        let query = [("limit", num_posts.min(MAX_LIMIT).to_string())];
+        let (mut children, mut after) = self
+            .get_listing_page::<LightThing<LightLink>>(&path, &query, true)
+            .await?;
+
+        fetch_more_pages(
+            &mut children,
+            &mut after,
+            num_posts,
+            |limit, after, count| {
+                let path = &path;
+                async move {
+                    let query = [
+                        ("limit", limit.to_string()),
+                        ("after", after.into()),
+                        ("count", count.to_string()),
+                    ];
+                    self.get_listing_page(path, &query, true).await
+                }
+            },
+        )
+        .await?;
+
+        Ok(children.into_iter().map(|child| child.data).collect())
+    }
+
+    /// Get a page of a listing endpoint, where path is the path of the endpoint without an extension.
+    pub(crate) async fn get_listing(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<Listing, Error> {
+        let request = self.api_request(reqwest::Method::GET, path).query(query);
+        let res = self.send(request).await?;
+        let thing: Thing = self.parse_response(res).await?;
+
+        thing
+            .data
+            .into_listing()
+            .map(|listing| *listing)
+            .ok_or(Error::ExpectedListing)
+    }
+
+    /// Get the children of a page of a listing endpoint, parsed as the given type.
+    ///
+    /// This is useful for listings with children that are not things.
+    /// If use_cache is `false`, the disk cache is skipped, which streams use so that every poll sees new items.
+    pub(crate) async fn get_listing_children<C>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        use_cache: bool,
+    ) -> Result<Vec<C>, Error>
+    where
+        C: serde::de::DeserializeOwned,
+    {
+        let (children, _) = self.get_listing_page(path, query, use_cache).await?;
+        Ok(children)
+    }
+
+    /// Get the children of a page of a listing endpoint like [`Client::get_listing_children`], along with the cursor of the next page.
+    async fn get_listing_page<C>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+        use_cache: bool,
+    ) -> Result<(Vec<C>, Option<Box<str>>), Error>
+    where
+        C: serde::de::DeserializeOwned,
+    {
+        #[derive(serde::Deserialize)]
+        struct RawListing<C> {
+            data: RawListingData<C>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct RawListingData<C> {
+            after: Option<Box<str>>,
+            children: Vec<C>,
+        }
+
+        let request = self.api_request(reqwest::Method::GET, path).query(query);
+        let res = if use_cache {
+            self.send(request).await?
+        } else {
+            self.send_uncached(request).await?
+        };
+        let listing: RawListing<C> = self.parse_response(res).await?;
+
+        Ok((listing.data.children, listing.data.after))
+    }
+
+    /// Get the top posts of many subreddits, making at most concurrency requests at once.
+    ///
+    /// Results are returned in the same order as the subreddits.
+    /// Each request reserves a request of the rate limit before it is sent, so a high concurrency will not exceed it.
+    pub async fn get_subreddits_concurrent(
+        &self,
+        subreddits: &[&str],
+        num_posts: usize,
+        concurrency: usize,
+    ) -> Vec<Result<Thing, Error>> {
+        futures_util::stream::iter(subreddits)
+            .map(|subreddit| self.get_subreddit(subreddit, num_posts))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Get posts, comments, and subreddits by their fullnames, like "t3_10a1b2c".
+    ///
+    /// Reddit returns at most [`MAX_LIMIT`] things per request,
+    /// so larger numbers of fullnames are split into multiple requests, with up to concurrency sent at once.
+    /// The things are returned in the order of the fullnames.
+    /// Things reddit does not return, like ones that do not exist, are skipped.
+    ///
+    /// See https://www.reddit.com/dev/api#GET_api_info
+    pub async fn get_info(
+        &self,
+        fullnames: &[&str],
+        concurrency: usize,
+    ) -> Result<Vec<Thing>, Error> {
+        let pages: Vec<Box<Listing>> = futures_util::stream::iter(fullnames.chunks(MAX_LIMIT))
+            .map(|chunk| async move {
+                let request = self
+                    .api_request(reqwest::Method::GET, "/api/info")
+                    .query(&[("id", chunk.join(","))]);
+                let res = self.send(request).await?;
+                let thing: Thing = self.parse_response(res).await?;
+                thing.data.into_listing().ok_or(Error::ExpectedListing)
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let things = pages.into_iter().flat_map(|page| page.children);
+        Ok(order_by_fullnames(fullnames, things))
+    }
+
+    /// Get the names and avatars of accounts by their fullnames, like "t2_1w72".
+    ///
+    /// The accounts are keyed by fullname. Accounts reddit does not return, like deleted or suspended ones, are skipped.
+    /// Larger numbers of fullnames are split into requests of [`MAX_LIMIT`] fullnames.
+    pub async fn get_users_by_ids(
+        &self,
+        fullnames: &[&str],
+    ) -> Result<HashMap<Box<str>, PartialUser>, Error> {
+        let mut users = HashMap::with_capacity(fullnames.len());
+        for chunk in fullnames.chunks(MAX_LIMIT) {
+            let request = self
+                .api_request(reqwest::Method::GET, "/api/user_data_by_account_ids")
+                .query(&[("ids", chunk.join(","))]);
+            let res = self.send(request).await?;
+            let page: HashMap<Box<str>, PartialUser> = self.parse_response(res).await?;
+            users.extend(page);
+        }
+
+        Ok(users)
+    }
+
+    /// Get a [`Paginator`] over the hot posts of a subreddit.
+    pub fn subreddit_paginator(&self, subreddit: &str) -> Paginator<Box<Link>> {
+        Paginator::new(self.clone(), &format!("/r/{subreddit}"))
+    }
+
+    /// Get a [`Paginator`] over the posts of a subreddit in a given order.
+    ///
+    /// Use [`Paginator::time_range`] to pick the time range of top and controversial posts.
+    pub fn subreddit_sorted_paginator(&self, subreddit: &str, sort: Sort) -> Paginator<Box<Link>> {
+        Paginator::new(self.clone(), &format!("/r/{subreddit}/{sort}"))
+    }
+
+    /// Get a [`Paginator`] over the posts and comments a user has saved, newest first.
+    ///
+    /// If a category is given, only things filed under it are included.
+    /// Users can only see their own saved things, so this requires an access token of the user.
+    pub fn saved_paginator(&self, username: &str, category: Option<&str>) -> Paginator<Thing> {
+        let paginator = Paginator::new(self.clone(), &format!("/user/{username}/saved"));
+        match category {
+            Some(category) => paginator.param("category", category),
+            None => paginator,
+        }
+    }
+
+    /// Get a [`Paginator`] over the hot posts of r/popular.
+    ///
+    /// geo_filter is a country code like "US" or "GB", or "GLOBAL", and limits posts to ones popular in that region.
+    /// Reddit picks a region based on the location of the request if no filter is given.
+    pub fn popular_paginator(&self, geo_filter: Option<&str>) -> Paginator<Box<Link>> {
+        let paginator = Paginator::new(self.clone(), "/r/popular/hot");
+        match geo_filter {
+            Some(geo_filter) => paginator.param("g", &geo_filter.to_ascii_uppercase()),
+            None => paginator,
+        }
+    }
+
+    /// Get a [`Paginator`] over the results of a search of all posts.
+    ///
+    /// See https://www.reddit.com/dev/api#GET_search
+    pub fn search_paginator(&self, query: &str) -> Paginator<Box<Link>> {
+        Paginator::new(self.clone(), "/search").param("q", query)
+    }
+
+    /// Get a [`Paginator`] over the results of a search of the posts of a subreddit.
+    pub fn subreddit_search_paginator(&self, subreddit: &str, query: &str) -> Paginator<Box<Link>> {
+        Paginator::new(self.clone(), &format!("/r/{subreddit}/search"))
+            .param("q", query)
+            .param("restrict_sr", "on")
+    }
+
+    /// Get an infinite stream of new posts in a subreddit.
+    ///
+    /// This polls the subreddit's new posts, yielding posts that have not been seen yet, oldest first.
+    /// The polling interval adapts to how active the subreddit is.
+    /// Errors are yielded, but do not end the stream.
+    pub fn stream_new_posts(
+        &self,
+        subreddit: &str,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Link, Error>> {
+        crate::stream::poll_listing(self.clone(), &format!("/r/{subreddit}/new"), options)
+    }
+
+    /// Get an infinite stream of new comments in a subreddit.
+    ///
+    /// This polls the subreddit's new comments, yielding comments that have not been seen yet, oldest first.
+    /// The polling interval adapts to how active the subreddit is.
+    /// Errors are yielded, but do not end the stream.
+    pub fn stream_new_comments(
+        &self,
+        subreddit: &str,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Comment, Error>> {
+        crate::stream::poll_listing(self.clone(), &format!("/r/{subreddit}/comments"), options)
+    }
+
+    /// Get an infinite stream of new posts in many subreddits.
+    ///
+    /// This works like [`Client::stream_new_posts`],
+    /// but joins subreddits into as few requests as possible and polls them in turn,
+    /// so many subreddits can be watched without multiplying the number of requests.
+    /// Posts are tagged with the subreddit they came from.
+    pub fn stream_new_posts_multi(
+        &self,
+        subreddits: &[&str],
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<SourcedItem<Link>, Error>> {
+        crate::stream::poll_subreddits(self.clone(), subreddits, "/new", options)
+    }
+
+    /// Get an infinite stream of new comments in many subreddits.
+    ///
+    /// This works like [`Client::stream_new_comments`],
+    /// but joins subreddits into as few requests as possible and polls them in turn,
+    /// so many subreddits can be watched without multiplying the number of requests.
+    /// Comments are tagged with the subreddit they came from.
+    pub fn stream_new_comments_multi(
+        &self,
+        subreddits: &[&str],
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<SourcedItem<Comment>, Error>> {
+        crate::stream::poll_subreddits(self.clone(), subreddits, "/comments", options)
+    }
+
+    /// Get an infinite stream of new items in the modqueue of a subreddit.
+    ///
+    /// Items are links or comments waiting for moderator review, yielded oldest first and deduplicated by fullname.
+    /// Errors are yielded, but do not end the stream.
+    /// This requires an access token for a moderator of the subreddit.
+    pub fn stream_modqueue(
+        &self,
+        subreddit: &str,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Thing, Error>> {
+        crate::stream::poll_listing(
+            self.clone(),
+            &format!("/r/{subreddit}/about/modqueue"),
+            options,
+        )
+    }
+
+    /// Get an infinite stream of new unread messages in the inbox, including comment replies and mentions.
+    ///
+    /// If mark_read is `true`, each fetched batch of messages is marked as read on the next poll,
+    /// which happens once the consumer asks for a message after the batch.
+    /// If marking them fails, the error is yielded and marking is tried again on the following polls.
+    /// Errors are yielded, but do not end the stream.
+    /// This requires an access token.
+    pub fn stream_inbox(
+        &self,
+        mark_read: bool,
+        options: StreamOptions,
+    ) -> impl Stream<Item = Result<Message, Error>> {
+        crate::stream::poll_inbox(self.clone(), "/message/unread", mark_read, options)
+    }
+
+    /// Mark messages as read, where fullnames are the fullnames of the messages.
+    ///
+    /// This requires an access token.
+    pub async fn mark_read(&self, fullnames: &[&str]) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/read_message")?
+            .form(&[("id", fullnames.join(",")), ("api_type", "json".into())]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Collapse messages in the inbox, where fullnames are the fullnames of the messages.
+    ///
+    /// This requires an access token.
+    pub async fn collapse_messages(&self, fullnames: &[&str]) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/collapse_message")?
+            .form(&[("id", fullnames.join(","))]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Uncollapse messages in the inbox, where fullnames are the fullnames of the messages.
+    ///
+    /// This requires an access token.
+    pub async fn uncollapse_messages(&self, fullnames: &[&str]) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/uncollapse_message")?
+            .form(&[("id", fullnames.join(","))]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Mark all messages in the inbox as read.
+    ///
+    /// Reddit may process this in the background, see [`ReadAllStatus::Queued`].
+    /// This requires an access token.
+    pub async fn mark_all_read(&self) -> Result<ReadAllStatus, Error> {
+        let request = self.oauth_request(reqwest::Method::POST, "/api/read_all_messages")?;
+        let res = self.send(request).await?;
+        let status = res.status();
+        let text = res.text().await?;
+        if let Some(error) = Error::from_api_errors(text.as_bytes()) {
+            return Err(error);
+        }
+
+        if status == reqwest::StatusCode::ACCEPTED {
+            Ok(ReadAllStatus::Queued)
+        } else {
+            Ok(ReadAllStatus::Done)
+        }
+    }
+
+    /// Save a post or comment, where fullname is the fullname of the thing.
+    ///
+    /// Reddit premium users can file the saved thing under a category, which is created if it does not exist.
+    /// This requires an access token.
+    pub async fn save(&self, fullname: &str, category: Option<&str>) -> Result<(), Error> {
+        let mut form = vec![("id", fullname)];
+        if let Some(category) = category {
+            form.push(("category", category));
+        }
+
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/save")?
+            .form(&form);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Unsave a post or comment, where fullname is the fullname of the thing.
+    ///
+    /// This requires an access token.
+    pub async fn unsave(&self, fullname: &str) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/unsave")?
+            .form(&[("id", fullname)]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Vote on a post or comment, where fullname is the fullname of the thing.
+    ///
+    /// Voting [`VoteDirection::Neutral`] removes the logged in user's vote.
+    /// Votes must be made by a human, see https://www.reddit.com/dev/api#POST_api_vote
+    /// This requires an access token.
+    pub async fn vote(&self, fullname: &str, direction: VoteDirection) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/vote")?
+            .form(&[("id", fullname), ("dir", direction.as_str())]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Get the categories the logged in user has filed saved things under.
+    ///
+    /// This is empty for users without reddit premium.
+    /// This requires an access token.
+    pub async fn get_saved_categories(&self) -> Result<Vec<Box<str>>, Error> {
+        #[derive(serde::Deserialize)]
+        struct SavedCategories {
+            categories: Vec<SavedCategory>,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct SavedCategory {
+            category: Box<str>,
+        }
+
+        let request = self.oauth_request(reqwest::Method::GET, "/api/saved_categories")?;
+        let res = self.send(request).await?;
+        let categories: SavedCategories = self.parse_response(res).await?;
+
+        Ok(categories
+            .categories
+            .into_iter()
+            .map(|category| category.category)
+            .collect())
+    }
+
+    /// Get the rules a subreddit has for new posts.
+    ///
+    /// See [`SubmissionDraft::validate`].
+    pub async fn get_post_requirements(&self, subreddit: &str) -> Result<PostRequirements, Error> {
+        let path = format!("/api/v1/{subreddit}/post_requirements");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Submit a post to a subreddit, returning the fullname of the new post.
+    ///
+    /// Build the draft with [`SubmissionBuilder`](crate::submit::SubmissionBuilder).
+    /// The draft is not validated, see [`SubmissionDraft::validate`].
+    /// This requires an access token.
+    pub async fn submit(
+        &self,
+        subreddit: &str,
+        draft: &SubmissionDraft,
+    ) -> Result<Box<str>, Error> {
+        #[derive(serde::Deserialize)]
+        struct SubmitResponse {
+            json: SubmitJson,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct SubmitJson {
+            data: SubmitData,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct SubmitData {
+            name: Box<str>,
+        }
+
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/submit")?
+            .form(&draft.form(subreddit));
+        let res = self.send(request).await?;
+        let response: SubmitResponse = self.parse_response(res).await?;
+
+        Ok(response.json.data.name)
+    }
+
+    /// Check whether the logged in user must solve a captcha to use endpoints that may require one, like [`Client::submit`].
+    ///
+    /// See [`Client::new_captcha`].
+    pub async fn needs_captcha(&self) -> Result<bool, Error> {
+        let request = self.api_request(reqwest::Method::GET, "/api/needs_captcha");
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Create a new captcha to solve.
+    ///
+    /// Show the image at [`Captcha::image_url`], or fetch it with [`Client::get_captcha_image`],
+    /// then attach the solution to the request that requires it, like with [`SubmissionDraft::captcha`].
+    /// This requires an access token.
+    pub async fn new_captcha(&self) -> Result<Captcha, Error> {
+        #[derive(serde::Deserialize)]
+        struct CaptchaResponse {
+            json: CaptchaJson,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CaptchaJson {
+            data: CaptchaData,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct CaptchaData {
+            iden: Box<str>,
+        }
+
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/new_captcha")?
+            .form(&[("api_type", "json")]);
+        let res = self.send(request).await?;
+        let response: CaptchaResponse = self.parse_response(res).await?;
+
+        Ok(Captcha::new(&response.json.data.iden))
+    }
+
+    /// Get the image of a captcha, a png.
+    pub async fn get_captcha_image(&self, captcha: &Captcha) -> Result<bytes::Bytes, Error> {
+        let request = self.client.get(captcha.image_url());
+        let res = self.send(request).await?;
+
+        Ok(res.bytes().await?)
+    }
+
+    /// Opt in to viewing a quarantined subreddit.
+    ///
+    /// This requires an access token, and applies to requests made with it.
+    /// Clients without an access token should use [`ClientBuilder::quarantine_opt_in`] instead.
+    pub async fn opt_in_quarantine(&self, subreddit: &str) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/quarantine_optin")?
+            .form(&[("sr_name", subreddit), ("api_type", "json")]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Get the post data for a post from a given subreddit
+    pub async fn get_post(&self, subreddit: &str, post_id: &str) -> Result<Vec<Thing>, Error> {
+        let path = format!("/r/{subreddit}/comments/{post_id}");
+        self.get_post_at(self.api_request(reqwest::Method::GET, &path))
+            .await
+    }
+
+    /// Get the post data for a post from its id alone, without knowing its subreddit.
+    ///
+    /// post_id may be the id of the post, like "h966lq", or its fullname, like "t3_h966lq".
+    pub async fn get_post_by_id(&self, post_id: &str) -> Result<Vec<Thing>, Error> {
+        let post_id = post_id.strip_prefix("t3_").unwrap_or(post_id);
+        let path = format!("/comments/{post_id}");
+        self.get_post_at(self.api_request(reqwest::Method::GET, &path))
+            .await
+    }
+
+    /// Get a comment of a post, including its replies.
+    ///
+    /// This fails with [`Error::CommentNotFound`] if the post does not have the comment.
+    pub async fn get_comment(
+        &self,
+        subreddit: &str,
+        post_id: &str,
+        comment_id: &str,
+    ) -> Result<Box<Comment>, Error> {
+        let path = format!("/r/{subreddit}/comments/{post_id}/_/{comment_id}");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let mut things = self.get_post_at(request).await?;
+
+        things
+            .get_mut(1)
+            .and_then(|thing| thing.data.as_listing_mut())
+            .and_then(|comments| comments.take_comment(&format!("t1_{comment_id}")))
+            .ok_or(Error::CommentNotFound)
+    }
+
+    /// Get a comment of a post, including its replies, and up to context of the comments it replies to.
+    ///
+    /// Reddit returns at most 8 ancestors, so larger contexts are clamped.
+    /// This fails with [`Error::CommentNotFound`] if the post does not have the comment.
+    pub async fn get_comment_with_context(
+        &self,
+        subreddit: &str,
+        post_id: &str,
+        comment_id: &str,
+        context: u8,
+    ) -> Result<CommentContext, Error> {
+        let context = context.min(MAX_COMMENT_CONTEXT);
+        let path = format!("/r/{subreddit}/comments/{post_id}/_/{comment_id}");
+        let request = self
+            .api_request(reqwest::Method::GET, &path)
+            .query(&[("context", context)]);
+        let mut things = self.get_post_at(request).await?.into_iter();
+
+        let link = things
+            .next()
+            .and_then(|thing| thing.data.into_listing())
+            .and_then(|listing| listing.children.into_iter().next())
+            .and_then(|thing| thing.data.into_link())
+            .ok_or(Error::PostNotFound {
+                url: None,
+                status: None,
+            })?;
+        let mut comments = things
+            .next()
+            .and_then(|thing| thing.data.into_listing())
+            .and_then(|mut comments| {
+                take_comment_with_ancestors(&mut comments, &format!("t1_{comment_id}"))
+            })
+            .ok_or(Error::CommentNotFound)?;
+        let comment = comments.pop().ok_or(Error::CommentNotFound)?;
+
+        Ok(CommentContext {
+            link,
+            ancestors: comments,
+            comment,
+        })
+    }
+
+    /// Get the post data for a post from its url.
+    ///
+    /// This accepts any url that [`RedditUrl`] parses as a post or comment, including shortlinks and share links.
+    /// Comment urls return the post data of the post the comment is in.
+    /// This fails with [`Error::InvalidUrl`] if the url is not a reddit post url.
+    pub async fn get_post_by_url(&self, url: &str) -> Result<Vec<Thing>, Error> {
+        let mut reddit_url: RedditUrl = url.parse()?;
+        if let RedditUrl::Share { .. } = reddit_url {
+            reddit_url = self.resolve_share_link(url).await?;
+        }
+
+        let post_id = reddit_url.post_id().ok_or(Error::InvalidUrl)?;
+        match reddit_url.subreddit() {
+            Some(subreddit) => self.get_post(subreddit, post_id).await,
+            None => self.get_post_by_id(post_id).await,
+        }
+    }
+
+    /// Get the post data of a request for a post.
+    async fn get_post_at(&self, request: reqwest::RequestBuilder) -> Result<Vec<Thing>, Error> {
+        let res = match self.send(request).await {
+            Ok(res) => res,
+            Err(Error::Reqwest(error))
+                if error.status() == Some(reqwest::StatusCode::NOT_FOUND) =>
+            {
+                return Err(Error::PostNotFound {
+                    url: error.url().cloned().map(Box::new),
+                    status: error.status(),
+                });
+            }
+            Err(error) => return Err(error),
+        };
+
+        if res.url().as_str().starts_with(SEARCH_URL) {
+            return Err(Error::SubredditNotFound);
+        }
+
+        self.parse_response(res).await
+    }
+
+    /// Resolve a share link, like `https://www.reddit.com/r/aww/s/AbCdEfGhIj`, into the post or comment it links to.
+    ///
+    /// Share links cannot be parsed offline, so this follows their redirect with a HEAD request,
+    /// which does not download the page.
+    /// This fails with [`Error::InvalidUrl`] if the url is not a share link,
+    /// or if it does not redirect to a post or comment.
+    pub async fn resolve_share_link(&self, url: &str) -> Result<RedditUrl, Error> {
+        let url = match url.parse()? {
+            RedditUrl::Share {
+                subreddit,
+                share_id,
+            } => format!("{BASE_URL}/r/{subreddit}/s/{share_id}"),
+            _ => return Err(Error::InvalidUrl),
+        };
+
+        let res = self.send(self.client.head(&url)).await?;
+        match res.url().as_str().parse()? {
+            reddit_url @ (RedditUrl::Post { .. } | RedditUrl::Comment { .. }) => Ok(reddit_url),
+            _ => Err(Error::InvalidUrl),
+        }
+    }
+
+    /// Download a file, like an image or video of a post.
+    ///
+    /// Reddit html escapes urls unless raw_json is used, so urls from responses may need to be unescaped first.
+    /// Downloads do not count against or wait for the rate limit, which only applies to the api.
+    pub async fn download(&self, url: &str) -> Result<bytes::Bytes, Error> {
+        let res = self.send_external(self.client.get(url)).await?;
+        Ok(res.bytes().await?)
+    }
+
+    /// Download a file to a path, without buffering the whole file in memory.
+    ///
+    /// See [`Client::download`].
+    pub async fn download_to_file(&self, url: &str, path: &Path) -> Result<(), Error> {
+        let mut res = self.send_external(self.client.get(url)).await?;
+        let mut file = tokio::fs::File::create(path).await?;
+        while let Some(chunk) = res.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+
+        Ok(())
+    }
+
+    /// Download the best full size image of a post.
+    ///
+    /// See [`Link::image_url`].
+    pub async fn download_image(&self, link: &Link) -> Result<bytes::Bytes, Error> {
+        let url = link.image_url().ok_or(Error::MissingMedia)?;
+        self.download(&url).await
+    }
+
+    /// Download the best full size image of a post to a path.
+    ///
+    /// See [`Link::image_url`].
+    pub async fn download_image_to_file(&self, link: &Link, path: &Path) -> Result<(), Error> {
+        let url = link.image_url().ok_or(Error::MissingMedia)?;
+        self.download_to_file(&url, path).await
+    }
+
+    /// Download the thumbnail image of a post.
+    ///
+    /// See [`Link::thumbnail_url`].
+    pub async fn download_thumbnail(&self, link: &Link) -> Result<bytes::Bytes, Error> {
+        let url = link.thumbnail_url().ok_or(Error::MissingMedia)?;
+        self.download(&url).await
+    }
+
+    /// Download the full size images of the gallery of a post into a directory, returning their paths in order.
+    ///
+    /// Files are named after their position in the gallery and their media id, like `01_abc123.jpg`.
+    /// Up to `concurrency` images are downloaded at once.
+    /// This fails with [`Error::MissingMedia`] if the post is not a gallery,
+    /// and with [`Error::InvalidParam`] before downloading anything if a media id is not a safe file name.
+    ///
+    /// See [`Link::gallery_images`].
+    pub async fn download_gallery(
+        &self,
+        link: &Link,
+        dir: &Path,
+        concurrency: usize,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let images = link.gallery_images();
+        if images.is_empty() {
+            return Err(Error::MissingMedia);
+        }
+
+        // The media ids and extensions come from reddit, so make sure they cannot name a path outside of dir.
+        for image in images.iter() {
+            if !is_safe_file_name_part(&image.media_id) {
+                return Err(Error::InvalidParam {
+                    kind: "media id",
+                    value: image.media_id.clone(),
+                });
+            }
+            if !is_safe_file_name_part(image.extension) {
+                return Err(Error::InvalidParam {
+                    kind: "media extension",
+                    value: image.extension.into(),
+                });
+            }
+        }
+
+        let width = images.len().to_string().len().max(2);
+        futures_util::stream::iter(images.into_iter().enumerate())
+            .map(|(i, image)| async move {
+                let file_name = format!("{:0width$}_{}.{}", i + 1, image.media_id, image.extension);
+                let path = dir.join(file_name);
+                self.download_to_file(&image.url, &path).await?;
+                Ok(path)
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await
+    }
+
+    /// Download the v.redd.it video of a post to a path, and its audio track to another path.
+    ///
+    /// Reddit serves the video and audio as separate files, which must be muxed to get a video with sound.
+    /// Returns `false` if the video has no audio track, in which case nothing is written to the audio path.
+    ///
+    /// See [`Link::reddit_video`].
+    pub async fn download_video_to_file(
+        &self,
+        link: &Link,
+        video_path: &Path,
+        audio_path: &Path,
+    ) -> Result<bool, Error> {
+        let video = link.reddit_video().ok_or(Error::MissingMedia)?;
+        self.download_to_file(&unescape_url(&video.fallback_url), video_path)
+            .await?;
+
+        for url in video.audio_urls() {
+            match self.download_to_file(&url, audio_path).await {
+                Ok(()) => return Ok(true),
+                // A missing audio track is reported as either of these.
+                Err(error)
+                    if matches!(
+                        error.status(),
+                        Some(reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::NOT_FOUND)
+                    ) =>
+                {
+                    continue;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Fetch the page an external link post links to, and extract its OpenGraph metadata.
+    ///
+    /// This is useful when reddit did not generate a preview for the post.
+    /// Relative image urls are resolved against the url of the page.
+    /// This fails with [`Error::MissingMedia`] if the post is a self post.
+    #[cfg(feature = "opengraph")]
+    pub async fn get_opengraph(&self, link: &Link) -> Result<OpenGraph, Error> {
+        if link.is_self {
+            return Err(Error::MissingMedia);
+        }
+
+        // This is not a reddit request, so the rate limit does not apply.
+        let response = self
+            .send_external(self.client.get(unescape_url(&link.url)))
+            .await?;
+        let url = response.url().clone();
+        let text = response.text().await?;
+
+        let mut opengraph = parse_opengraph(&text);
+        if let Some(image) = opengraph.image.as_deref() {
+            if let Ok(image) = url.join(image) {
+                opengraph.image = Some(image.as_str().into());
+            }
+        }
+
+        Ok(opengraph)
+    }
+
+    /// Get the karma of the logged in user, broken down by subreddit.
+    ///
+    /// This requires an access token.
+    pub async fn get_my_karma(&self) -> Result<Vec<SubredditKarma>, Error> {
+        #[derive(serde::Deserialize)]
+        struct KarmaList {
+            data: Vec<SubredditKarma>,
+        }
+
+        let request = self.oauth_request(reqwest::Method::GET, "/api/v1/me/karma")?;
+        let res = self.send(request).await?;
+        let karma_list: KarmaList = self.parse_response(res).await?;
+
+        Ok(karma_list.data)
+    }
+
+    /// Get the subreddits a user moderates, where username is the account name of the user.
+    ///
+    /// This is empty if the user does not moderate any subreddits.
+    pub async fn get_moderated_subreddits(
+        &self,
+        username: &str,
+    ) -> Result<Vec<ModeratedSubreddit>, Error> {
+        #[derive(serde::Deserialize)]
+        struct ModeratedList {
+            #[serde(default)]
+            data: Vec<ModeratedSubreddit>,
+        }
+
+        let path = format!("/user/{username}/moderated_subreddits");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let res = self.send(request).await?;
+        let list: ModeratedList = self.parse_response(res).await?;
+
+        Ok(list.data)
+    }
+
+    /// Check whether a username is available to register.
+    ///
+    /// This fails with an api error if the name is not a valid username.
+    pub async fn is_username_available(&self, username: &str) -> Result<bool, Error> {
+        let request = self
+            .api_request(reqwest::Method::GET, "/api/username_available")
+            .query(&[("user", username)]);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Get the preferences of the logged in user.
+    ///
+    /// This requires an access token.
+    pub async fn get_prefs(&self) -> Result<Preferences, Error> {
+        let request = self.oauth_request(reqwest::Method::GET, "/api/v1/me/prefs")?;
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Update the preferences of the logged in user, returning the new preferences.
+    ///
+    /// Only fields that are `Some` in the patch are changed.
+    /// This requires an access token.
+    pub async fn update_prefs(&self, patch: &Preferences) -> Result<Preferences, Error> {
+        let request = self
+            .oauth_request(reqwest::Method::PATCH, "/api/v1/me/prefs")?
+            .json(patch);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Get a collection and its posts, where collection_id is the uuid of the collection.
+    pub async fn get_collection(&self, collection_id: &str) -> Result<Collection, Error> {
+        let request = self
+            .api_request(reqwest::Method::GET, "/api/v1/collections/collection")
+            .query(&[("collection_id", collection_id), ("include_links", "true")]);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Get the collections of a subreddit, where subreddit_id is the fullname of the subreddit, like "t5_2qh1o".
+    ///
+    /// The posts of the collections are not included, see [`Collection::link_ids`].
+    pub async fn get_subreddit_collections(
+        &self,
+        subreddit_id: &str,
+    ) -> Result<Vec<Collection>, Error> {
+        let request = self
+            .api_request(
+                reqwest::Method::GET,
+                "/api/v1/collections/subreddit_collections",
+            )
+            .query(&[("sr_fullname", subreddit_id)]);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Create a collection in a subreddit, where subreddit_id is the fullname of the subreddit.
+    ///
+    /// Reddit uses [`CollectionDisplayLayout::Timeline`] if no layout is given.
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn create_collection(
+        &self,
+        subreddit_id: &str,
+        title: &str,
+        description: &str,
+        display_layout: Option<CollectionDisplayLayout>,
+    ) -> Result<Collection, Error> {
+        let mut form = vec![
+            ("sr_fullname", subreddit_id),
+            ("title", title),
+            ("description", description),
+        ];
+        if let Some(display_layout) = display_layout {
+            form.push(("display_layout", display_layout.as_str()));
+        }
+
+        let request = self
+            .oauth_request(
+                reqwest::Method::POST,
+                "/api/v1/collections/create_collection",
+            )?
+            .form(&form);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Delete a collection. The posts in it are not deleted.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn delete_collection(&self, collection_id: &str) -> Result<(), Error> {
+        let request = self
+            .oauth_request(
+                reqwest::Method::POST,
+                "/api/v1/collections/delete_collection",
+            )?
+            .form(&[("collection_id", collection_id)]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Add a post to the end of a collection, where link_fullname is the fullname of the post.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn add_post_to_collection(
+        &self,
+        collection_id: &str,
+        link_fullname: &str,
+    ) -> Result<(), Error> {
+        let request = self
+            .oauth_request(
+                reqwest::Method::POST,
+                "/api/v1/collections/add_post_to_collection",
+            )?
+            .form(&[
+                ("collection_id", collection_id),
+                ("link_fullname", link_fullname),
+            ]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Remove a post from a collection, where link_fullname is the fullname of the post.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn remove_post_from_collection(
+        &self,
+        collection_id: &str,
+        link_fullname: &str,
+    ) -> Result<(), Error> {
+        let request = self
+            .oauth_request(
+                reqwest::Method::POST,
+                "/api/v1/collections/remove_post_in_collection",
+            )?
+            .form(&[
+                ("collection_id", collection_id),
+                ("link_fullname", link_fullname),
+            ]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Get the sidebar and topbar widgets of a subreddit.
+    ///
+    /// These are only shown on new reddit, and are not included in the old sidebar of a subreddit.
+    pub async fn get_widgets(&self, subreddit: &str) -> Result<Widgets, Error> {
+        let path = format!("/r/{subreddit}/api/widgets");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let res = self.send(request).await?;
+        self.parse_response(res).await
+    }
+
+    /// Get the new reddit theme of a subreddit, like its banner, icon, and colors.
+    pub async fn get_structured_styles(&self, subreddit: &str) -> Result<StructuredStyles, Error> {
+        #[derive(serde::Deserialize)]
+        struct StylesResponse {
+            data: StylesData,
+        }
+
+        #[derive(serde::Deserialize)]
+        struct StylesData {
+            style: StructuredStyles,
+        }
+
+        let path = format!("/api/v1/structured_styles/{subreddit}");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let res = self.send(request).await?;
+        Ok(self.parse_response::<StylesResponse>(res).await?.data.style)
+    }
+
+    /// Get the custom emojis of a subreddit, including reddit's snoomojis.
+    ///
+    /// The snoomojis come first, then the emojis of the subreddit, each sorted by name.
+    pub async fn get_emojis(&self, subreddit: &str) -> Result<Vec<Emoji>, Error> {
+        let path = format!("/api/v1/{subreddit}/emojis/all");
+        let request = self.api_request(reqwest::Method::GET, &path);
+        let res = self.send(request).await?;
+        let groups: BTreeMap<Box<str>, BTreeMap<Box<str>, Emoji>> =
+            self.parse_response(res).await?;
+
+        Ok(groups
+            .into_values()
+            .flatten()
+            .map(|(name, emoji)| Emoji { name, ..emoji })
+            .collect())
+    }
+
+    /// Upload a file to the S3 bucket of an upload lease.
+    ///
+    /// S3 is not reddit's api, so this skips the rate limit and the checks for reddit's error responses.
+    async fn upload_to_lease(
+        &self,
+        lease: &S3UploadLease,
+        file_name: &str,
+        mime_type: &str,
+        file: &[u8],
+    ) -> Result<(), Error> {
+        let (content_type, body) = lease.multipart_body(file_name, mime_type, file);
+        let upload = self
+            .client
+            .post(lease.url())
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body);
+        self.send_external(upload).await?;
+
+        Ok(())
+    }
+
+    /// Upload a custom emoji to a subreddit, where image is a png or jpeg image with the given mime type.
+    ///
+    /// Reddit resizes images to 128x128 pixels, and limits them to 64KB.
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn upload_emoji(
+        &self,
+        subreddit: &str,
+        name: &str,
+        image: &[u8],
+        mime_type: &str,
+        permissions: EmojiPermissions,
+    ) -> Result<(), Error> {
+        #[derive(serde::Deserialize)]
+        struct LeaseResponse {
+            #[serde(rename = "s3UploadLease")]
+            s3_upload_lease: S3UploadLease,
+        }
+
+        let extension = extension_from_mime_type(mime_type).ok_or_else(|| Error::InvalidParam {
+            kind: "mime type",
+            value: mime_type.into(),
+        })?;
+        let file_name = format!("{name}.{extension}");
+
+        let path = format!("/api/v1/{subreddit}/emoji_asset_upload_s3.json");
+        let request = self
+            .oauth_request(reqwest::Method::POST, &path)?
+            .form(&[("filepath", &*file_name), ("mimetype", mime_type)]);
+        let res = self.send(request).await?;
+        let lease = self
+            .parse_response::<LeaseResponse>(res)
+            .await?
+            .s3_upload_lease;
+        self.upload_to_lease(&lease, &file_name, mime_type, image)
+            .await?;
+
+        let path = format!("/api/v1/{subreddit}/emoji.json");
+        let request = self.oauth_request(reqwest::Method::POST, &path)?.form(&[
+            ("name", name),
+            ("s3_key", lease.key()),
+            (
+                "user_flair_allowed",
+                bool_param(permissions.user_flair_allowed),
+            ),
+            (
+                "post_flair_allowed",
+                bool_param(permissions.post_flair_allowed),
+            ),
+            ("mod_flair_only", bool_param(permissions.mod_flair_only)),
+        ]);
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Delete a custom emoji of a subreddit.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn delete_emoji(&self, subreddit: &str, name: &str) -> Result<(), Error> {
+        let path = format!("/api/v1/{subreddit}/emoji/{name}");
+        let request = self.oauth_request(reqwest::Method::DELETE, &path)?;
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Get the settings of a subreddit.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn get_subreddit_settings(
+        &self,
+        subreddit: &str,
+    ) -> Result<SubredditSettings, Error> {
+        #[derive(serde::Deserialize)]
+        struct SettingsThing {
+            data: SubredditSettings,
+        }
+
+        let path = format!("/r/{subreddit}/about/edit");
+        let request = self.oauth_request(reqwest::Method::GET, &path)?;
+        let res = self.send(request).await?;
+        Ok(self.parse_response::<SettingsThing>(res).await?.data)
+    }
+
+    /// Update the settings of a subreddit, usually after changing the settings from [`Client::get_subreddit_settings`].
+    ///
+    /// All settings are sent, as reddit resets missing settings to their defaults.
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn update_subreddit_settings(
+        &self,
+        settings: &SubredditSettings,
+    ) -> Result<(), Error> {
+        let request = self
+            .oauth_request(reqwest::Method::POST, "/api/site_admin")?
+            .form(&settings.site_admin_form());
+        self.send_api(request).await?;
+
+        Ok(())
+    }
+
+    /// Get the banned users of a subreddit.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn get_banned(&self, subreddit: &str) -> Result<Vec<SubredditRelationship>, Error> {
+        self.get_relationships(subreddit, "banned").await
+    }
+
+    /// Get the muted users of a subreddit.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn get_muted(&self, subreddit: &str) -> Result<Vec<SubredditRelationship>, Error> {
+        self.get_relationships(subreddit, "muted").await
+    }
 
-// Guesses for good defaults for the user agent.
+    /// Get the approved users of a subreddit.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn get_contributors(
+        &self,
+        subreddit: &str,
+    ) -> Result<Vec<SubredditRelationship>, Error> {
+        self.get_relationships(subreddit, "contributors").await
+    }
 
-// TODO: Extract from target
-const DEFAULT_PLATFORM: &str = "pc";
+    /// Get the users banned from the wiki of a subreddit.
+    ///
+    /// This requires an access token of a moderator of the subreddit.
+    pub async fn get_wikibanned(
+        &self,
+        subreddit: &str,
+    ) -> Result<Vec<SubredditRelationship>, Error> {
+        self.get_relationships(subreddit, "wikibanned").await
+    }
 
-const DEFAULT_APP_ID: &str = env!("CARGO_PKG_NAME");
-const DEFAULT_APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+    /// Get all pages of a `/r/{subreddit}/about/{where}` relationship listing.
+    async fn get_relationships(
+        &self,
+        subreddit: &str,
+        kind: &str,
+    ) -> Result<Vec<SubredditRelationship>, Error> {
+        let path = format!("/r/{subreddit}/about/{kind}");
+        let fetch_page = |after: Option<Box<str>>| {
+            let path = &path;
+            async move {
+                let mut request = self
+                    .oauth_request(reqwest::Method::GET, path)?
+                    .query(&[("limit", MAX_LIMIT.to_string())]);
+                if let Some(after) = after.as_deref() {
+                    request = request.query(&[("after", after)]);
+                }
+                let res = self.send(request).await?;
+                let page: RelationshipPage = self.parse_response(res).await?;
+                Ok((page.data.children, page.data.after))
+            }
+        };
 
-// TODO: Is there really a good default to choose here?
-const DEFAULT_REDDIT_USERNAME: &str = "deleted";
+        let (mut relationships, mut after) = fetch_page(None).await?;
+        fetch_more_pages(&mut relationships, &mut after, usize::MAX, |_, after, _| {
+            fetch_page(Some(after))
+        })
+        .await?;
 
-/// A client to access reddit
-#[derive(Clone)]
-pub struct Client {
-    /// The inner http client.
+        Ok(relationships)
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check that a part of a file name is not empty and only has ascii letters, digits, '_' and '-'.
+fn is_safe_file_name_part(part: &str) -> bool {
+    !part.is_empty()
+        && part
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// Fetch more pages of a listing until it has num_items children, it runs out, or a page repeats its cursor.
+///
+/// fetch_page is called with the number of children to request, which is at most [`MAX_LIMIT`],
+/// the cursor of the page, and the number of children fetched so far.
+/// It returns the children of the page and the cursor of the next page.
+async fn fetch_more_pages<C, F, Fut>(
+    children: &mut Vec<C>,
+    after: &mut Option<Box<str>>,
+    num_items: usize,
+    mut fetch_page: F,
+) -> Result<(), Error>
+where
+    F: FnMut(usize, Box<str>, usize) -> Fut,
+    Fut: std::future::Future<Output = Result<(Vec<C>, Option<Box<str>>), Error>>,
+{
+    while children.len() < num_items {
+        let Some(cursor) = after.take() else {
+            break;
+        };
+
+        let count = children.len();
+        let limit = (num_items - count).min(MAX_LIMIT);
+        let (page, page_after) = fetch_page(limit, cursor.clone(), count).await?;
+
+        // Reddit sometimes returns the same page again, which would repeat forever.
+        if page.is_empty() || page_after.as_deref() == Some(&*cursor) {
+            break;
+        }
+        *after = page_after;
+        children.extend(page);
+    }
+
+    Ok(())
+}
+
+/// Format a boolean form parameter.
+pub(crate) fn bool_param(value: bool) -> &'static str {
+    if value {
+        "true"
+    } else {
+        "false"
+    }
+}
+
+/// Sort things into the order of a list of fullnames, skipping fullnames without a thing.
+fn order_by_fullnames(fullnames: &[&str], things: impl Iterator<Item = Thing>) -> Vec<Thing> {
+    let mut things: HashMap<Box<str>, Thing> = things
+        .filter_map(|thing| Some((thing.fullname()?.into(), thing)))
+        .collect();
+
+    fullnames
+        .iter()
+        .filter_map(|fullname| things.remove(*fullname))
+        .collect()
+}
+
+/// A page of a subreddit relationship listing.
+///
+/// The children are not things, so this can not be parsed as a [`Listing`].
+#[derive(serde::Deserialize)]
+struct RelationshipPage {
+    data: RelationshipPageData,
+}
+
+#[derive(serde::Deserialize)]
+struct RelationshipPageData {
+    after: Option<Box<str>>,
+    children: Vec<SubredditRelationship>,
+}
+
+/// The credentials of a script app, used for the password grant.
+struct PasswordCredentials {
+    client_id: String,
+    client_secret: String,
+    username: String,
+    password: String,
+}
+
+/// Client configuration read from environment variables.
+struct EnvConfig {
+    user_agent: Option<UserAgent>,
+    credentials: Option<PasswordCredentials>,
+}
+
+impl EnvConfig {
+    /// Read the configuration, where var gets the value of a variable.
     ///
-    /// It probably shouldn't be used directly by you.
-    /// It also sets a strange user-agent as well in accordance with reddit's request.
-    pub client: reqwest::Client,
+    /// Empty variables are treated as unset.
+    fn from_vars<F>(var: F) -> Result<Self, Error>
+    where
+        F: Fn(&'static str) -> Option<String>,
+    {
+        let var = |name| var(name).filter(|value| !value.is_empty());
+
+        let user_agent = var("REDDIT_USER_AGENT")
+            .map(|user_agent| user_agent.parse())
+            .transpose()?;
+
+        let names = [
+            "REDDIT_CLIENT_ID",
+            "REDDIT_CLIENT_SECRET",
+            "REDDIT_USERNAME",
+            "REDDIT_PASSWORD",
+        ];
+        let credentials = match names.map(var) {
+            [None, None, None, None] => None,
+            [Some(client_id), Some(client_secret), Some(username), Some(password)] => {
+                Some(PasswordCredentials {
+                    client_id,
+                    client_secret,
+                    username,
+                    password,
+                })
+            }
+            values => {
+                let i = values.iter().position(Option::is_none).unwrap_or_default();
+                return Err(Error::MissingEnvVar { name: names[i] });
+            }
+        };
+
+        Ok(Self {
+            user_agent,
+            credentials,
+        })
+    }
 }
 
-impl Client {
-    /// Create a new [`Client`].
+/// Get the reddit request id of a response, which reddit asks for in bug reports.
+pub(crate) fn request_id(response: &reqwest::Response) -> Option<Box<str>> {
+    response
+        .headers()
+        .get("x-reddit-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(Box::from)
+}
+
+/// Parse a json response body, checking for api errors.
+///
+/// Parse errors include the body, url, status, and reddit request id of the response.
+pub(crate) fn parse_body<T>(
+    parse_mode: ParseMode,
+    bytes: &[u8],
+    url: url::Url,
+    status: reqwest::StatusCode,
+    request_id: Option<Box<str>>,
+) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    if let Some(error) = Error::from_api_errors(bytes) {
+        return Err(error);
+    }
+
+    parse_mode.parse_slice(bytes).map_err(|error| Error::Json {
+        data: String::from_utf8_lossy(bytes).into(),
+        error,
+        url: Some(Box::new(url)),
+        status: Some(status),
+        request_id,
+    })
+}
+
+/// A builder for a [`Client`].
+pub struct ClientBuilder {
+    user_agent: UserAgent,
+    parse_mode: ParseMode,
+    access_token: Option<Box<str>>,
+    raw_json: bool,
+    quarantine_opt_in: bool,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+    #[cfg(feature = "middleware")]
+    middleware: Option<reqwest_middleware::ClientWithMiddleware>,
+    #[cfg(feature = "disk-cache")]
+    disk_cache: Option<DiskCache>,
+}
+
+impl ClientBuilder {
+    /// Create a new [`ClientBuilder`].
     pub fn new() -> Self {
-        Self::new_with_user_agent(
-            DEFAULT_PLATFORM,
-            DEFAULT_APP_ID,
-            DEFAULT_APP_VERSION,
-            DEFAULT_REDDIT_USERNAME,
-        )
+        Self {
+            user_agent: UserAgent::default(),
+            parse_mode: ParseMode::default(),
+            access_token: None,
+            raw_json: true,
+            quarantine_opt_in: false,
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            tcp_keepalive: None,
+            #[cfg(feature = "middleware")]
+            middleware: None,
+            #[cfg(feature = "disk-cache")]
+            disk_cache: None,
+        }
     }
 
-    /// Create a new [`Client`] with a user-agent.
+    /// Set the user-agent.
     ///
+    /// This defaults to [`UserAgent::default`], which should be replaced with one that identifies your app.
     /// See https://github.com/reddit-archive/reddit/wiki/API#rules
-    pub fn new_with_user_agent(
-        platform: &str,
-        app_id: &str,
-        app_version: &str,
-        reddit_username: &str,
-    ) -> Self {
-        let user_agent = format!("{platform}:{app_id}:v{app_version} (by /u/{reddit_username})");
+    pub fn user_agent(mut self, user_agent: UserAgent) -> Self {
+        self.user_agent = user_agent;
+        self
+    }
 
-        let mut client_builder = reqwest::Client::builder();
-        client_builder = client_builder.user_agent(user_agent);
+    /// Set the [`ParseMode`] used for responses.
+    ///
+    /// This defaults to [`ParseMode::Strict`].
+    pub fn parse_mode(mut self, parse_mode: ParseMode) -> Self {
+        self.parse_mode = parse_mode;
+        self
+    }
 
-        let client = client_builder
-            .build()
-            .expect("failed to build reddit client");
+    /// Set the OAuth access token, used for endpoints that need a logged in user.
+    ///
+    /// See https://github.com/reddit-archive/reddit/wiki/OAuth2
+    pub fn access_token(mut self, access_token: &str) -> Self {
+        self.access_token = Some(access_token.into());
+        self
+    }
 
-        Self { client }
+    /// Set whether `raw_json=1` is sent with requests.
+    ///
+    /// When enabled, reddit will not escape `&`, `<`, and `>` in response text, like bodies and urls.
+    /// This defaults to `true`.
+    pub fn raw_json(mut self, raw_json: bool) -> Self {
+        self.raw_json = raw_json;
+        self
     }
 
-    /// Get the top posts of a subreddit where subreddit is the name and num_posts is the number of posts to retrieve.
-    pub async fn get_subreddit(&self, subreddit: &str, num_posts: usize) -> Result<Thing, Error> {
-        let url = format!("https://www.reddit.com/r/{subreddit}.json?limit={num_posts}");
-        let res = self.client.get(&url).send().await?.error_for_status()?;
+    /// Set whether requests opt in to viewing quarantined subreddits.
+    ///
+    /// This sends the cookie reddit's website uses to remember the opt-in,
+    /// so listings of quarantined subreddits can be fetched without an access token
+    /// instead of failing with [`Error::SubredditQuarantined`].
+    /// Only enable this if the user has consented to viewing quarantined content.
+    /// This defaults to `false`.
+    pub fn quarantine_opt_in(mut self, quarantine_opt_in: bool) -> Self {
+        self.quarantine_opt_in = quarantine_opt_in;
+        self
+    }
 
-        // Reddit will redirect us here if the subreddit could not be found.
-        const SEARCH_URL: &str = "https://www.reddit.com/subreddits/search.json?";
-        if res.url().as_str().starts_with(SEARCH_URL) {
-            return Err(Error::SubredditNotFound);
-        }
+    /// Set the maximum number of idle connections to keep per host.
+    ///
+    /// This defaults to reqwest's default, which is unlimited.
+    pub fn pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+        self
+    }
 
-        let text = res.text().await?;
-        serde_json::from_str(&text).map_err(|error| Error::Json {
-            data: text.into(),
-            error,
-        })
+    /// Set how long idle connections are kept before being closed.
+    ///
+    /// Long-running streams should set this above their poll interval, so connections stay warm between polls.
+    /// This defaults to reqwest's default, which is 90 seconds.
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
     }
 
-    /// Get the post data for a post from a given subreddit
-    pub async fn get_post(&self, subreddit: &str, post_id: &str) -> Result<Vec<Thing>, Error> {
-        let url = format!("https://www.reddit.com/r/{subreddit}/comments/{post_id}.json");
-        Ok(self
-            .client
-            .get(&url)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?)
+    /// Set the interval of TCP keepalive probes on connections.
+    ///
+    /// This defaults to reqwest's default.
+    pub fn tcp_keepalive(mut self, tcp_keepalive: Duration) -> Self {
+        self.tcp_keepalive = Some(tcp_keepalive);
+        self
+    }
+
+    /// Send requests through a middleware client, like one with tracing, caching, or retry middleware.
+    ///
+    /// The rate limit, error handling, and user-agent of this client still apply.
+    /// Connection pool options of this builder do not apply, as the middleware client has its own inner client.
+    #[cfg(feature = "middleware")]
+    pub fn middleware(mut self, middleware: reqwest_middleware::ClientWithMiddleware) -> Self {
+        self.middleware = Some(middleware);
+        self
+    }
+
+    /// Read responses from and store responses in a cache on disk.
+    ///
+    /// Cached responses are returned without making a request, so they do not count against the rate limit.
+    /// Only anonymous requests are cached, and streams always make new requests.
+    #[cfg(feature = "disk-cache")]
+    pub fn disk_cache(mut self, disk_cache: DiskCache) -> Self {
+        self.disk_cache = Some(disk_cache);
+        self
+    }
+
+    /// Build the [`Client`].
+    pub fn build(self) -> Result<Client, Error> {
+        #[cfg(feature = "middleware")]
+        let user_agent = self.user_agent.to_string();
+
+        let mut client_builder = reqwest::Client::builder();
+        client_builder = client_builder.user_agent(self.user_agent.to_string());
+        if let Some(pool_max_idle_per_host) = self.pool_max_idle_per_host {
+            client_builder = client_builder.pool_max_idle_per_host(pool_max_idle_per_host);
+        }
+        if let Some(pool_idle_timeout) = self.pool_idle_timeout {
+            client_builder = client_builder.pool_idle_timeout(pool_idle_timeout);
+        }
+        if let Some(tcp_keepalive) = self.tcp_keepalive {
+            client_builder = client_builder.tcp_keepalive(tcp_keepalive);
+        }
+
+        let client = client_builder.build()?;
+
+        // The client would have failed to build if this were invalid.
+        #[cfg(feature = "middleware")]
+        let user_agent =
+            reqwest::header::HeaderValue::from_str(&user_agent).expect("invalid user-agent");
+
+        Ok(Client {
+            client,
+            parse_mode: self.parse_mode,
+            access_token: self.access_token,
+            raw_json: self.raw_json,
+            quarantine_opt_in: self.quarantine_opt_in,
+            rate_limit: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "middleware")]
+            middleware: self.middleware,
+            #[cfg(feature = "middleware")]
+            user_agent,
+            #[cfg(feature = "disk-cache")]
+            disk_cache: self.disk_cache.map(Arc::new),
+        })
     }
 }
 
-impl Default for Client {
+impl Default for ClientBuilder {
     fn default() -> Self {
         Self::new()
     }
@@ -128,6 +2252,23 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn get_post_by_id_works() {
+        let client = Client::new();
+        for post_id in ["h966lq", "t3_h966lq"] {
+            let post = client.get_post_by_id(post_id).await.unwrap();
+            let link = post[0].data.as_listing().unwrap().children[0]
+                .data
+                .as_link()
+                .unwrap();
+            assert_eq!(&*link.subreddit, "dankmemes");
+        }
+
+        let error = client.get_post_by_id("zzzzzzzzzz").await.unwrap_err();
+        assert!(error.is_post_not_found(), "error = {error:#?}");
+    }
+
     #[tokio::test]
     #[ignore]
     async fn get_subreddit_works() {
@@ -146,7 +2287,7 @@ mod test {
         for subreddit in subreddits.iter() {
             match get_subreddit(subreddit).await {
                 Ok(()) => {}
-                Err(Error::Json { data, error }) => {
+                Err(Error::Json { data, error, .. }) => {
                     let line = error.line();
                     let column = error.column();
 
@@ -172,6 +2313,29 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    #[ignore]
+    async fn autocomplete_subreddits_works() {
+        let client = Client::new();
+        let subreddits = client
+            .autocomplete_subreddits("dankm", false)
+            .await
+            .unwrap();
+        assert!(!subreddits.is_empty());
+        assert!(subreddits.len() <= 10);
+        assert!(subreddits
+            .iter()
+            .any(|subreddit| subreddit.display_name.eq_ignore_ascii_case("dankmemes")));
+        assert!(subreddits.iter().all(|subreddit| !subreddit.over18));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn is_username_available_works() {
+        let client = Client::new();
+        assert!(!client.is_username_available("spez").await.unwrap());
+    }
+
     #[tokio::test]
     #[ignore]
     async fn invalid_subreddit() {
@@ -179,4 +2343,434 @@ mod test {
         let error = client.get_subreddit("gfdghfj", 25).await.unwrap_err();
         assert!(error.is_subreddit_not_found(), "error = {error:#?}");
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn get_subreddits_concurrent_works() {
+        let client = Client::new();
+        let subreddits = ["aww", "cats", "gfdghfj"];
+        let results = client.get_subreddits_concurrent(&subreddits, 10, 2).await;
+        assert_eq!(results.len(), subreddits.len());
+        results[0].as_ref().unwrap();
+        results[1].as_ref().unwrap();
+        assert!(results[2].as_ref().unwrap_err().is_subreddit_not_found());
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn get_subreddit_over_max_limit() {
+        let client = Client::new();
+        let thing = client.get_subreddit("aww", 150).await.unwrap();
+        let listing = thing.data.into_listing().unwrap();
+        assert_eq!(listing.children.len(), 150);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn invalid_post() {
+        let client = Client::new();
+        let error = client.get_post("dankmemes", "zzzzzzz").await.unwrap_err();
+        assert!(error.is_post_not_found(), "error = {error:#?}");
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn get_comment_works() {
+        let client = Client::new();
+        let comment = client
+            .get_comment("dankvideos", "h8p0py", "fut2zpi")
+            .await
+            .unwrap();
+        assert_eq!(&*comment.name, "t1_fut2zpi");
+
+        let context = client
+            .get_comment_with_context("dankvideos", "h8p0py", "fut2zpi", 2)
+            .await
+            .unwrap();
+        assert_eq!(context.ancestors.len(), 2);
+        assert_eq!(context.ancestors[1].name, context.comment.parent_id);
+    }
+
+    #[cfg(feature = "middleware")]
+    #[tokio::test]
+    #[ignore]
+    async fn get_subreddit_middleware_works() {
+        let middleware = reqwest_middleware::ClientBuilder::new(reqwest::Client::new()).build();
+        let client = Client::builder().middleware(middleware).build().unwrap();
+        client.get_subreddit("aww", 5).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolve_share_link_rejects_other_urls() {
+        let error = Client::new()
+            .resolve_share_link("https://www.reddit.com/r/aww/comments/10a1b2c/")
+            .await
+            .unwrap_err();
+        assert!(error.is_invalid_url());
+    }
+
+    #[tokio::test]
+    async fn fetch_more_pages_clamps_and_stops() {
+        let mut limits = Vec::new();
+        let mut children: Vec<usize> = (0..MAX_LIMIT).collect();
+        let mut after = Some("t3_0".into());
+        fetch_more_pages(&mut children, &mut after, 250, |limit, _, count| {
+            limits.push(limit);
+            let after = format!("t3_{}", count + limit).into();
+            async move { Ok(((count..count + limit).collect(), Some(after))) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(limits, [MAX_LIMIT, 50]);
+        assert_eq!(children.len(), 250);
+
+        // A page that repeats its cursor ends the listing.
+        let mut children: Vec<usize> = vec![0];
+        let mut after = Some("t3_0".into());
+        fetch_more_pages(&mut children, &mut after, 250, |_, cursor, _| async move {
+            Ok((vec![1], Some(cursor)))
+        })
+        .await
+        .unwrap();
+        assert_eq!(children, [0]);
+        assert!(after.is_none());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn new_with_user_agent() {
+        // The deprecated constructor formats components as given, like it always has.
+        let _client = Client::new_with_user_agent("pc", "my app", "1.0", "a");
+
+        let result = Client::try_new_with_user_agent("pc", "my app", "1.0", "a");
+        assert!(result.is_err_and(|error| error.is_invalid_user_agent()));
+        Client::try_new_with_user_agent("linux", "my-app", "v1.0.0", "/u/spez").unwrap();
+    }
+
+    #[test]
+    fn env_config() {
+        let vars = |vars: &'static [(&'static str, &'static str)]| {
+            move |name| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        let config = EnvConfig::from_vars(vars(&[])).unwrap();
+        assert!(config.user_agent.is_none());
+        assert!(config.credentials.is_none());
+
+        let config = EnvConfig::from_vars(vars(&[
+            ("REDDIT_USER_AGENT", "linux:my-app:v1.0.0 (by /u/spez)"),
+            ("REDDIT_CLIENT_ID", "id"),
+            ("REDDIT_CLIENT_SECRET", "secret"),
+            ("REDDIT_USERNAME", "spez"),
+            ("REDDIT_PASSWORD", "hunter2"),
+        ]))
+        .unwrap();
+        assert_eq!(config.user_agent.unwrap().app_id(), "my-app");
+        assert_eq!(config.credentials.unwrap().username, "spez");
+
+        let error = EnvConfig::from_vars(vars(&[
+            ("REDDIT_CLIENT_ID", "id"),
+            ("REDDIT_CLIENT_SECRET", ""),
+        ]))
+        .err()
+        .unwrap();
+        assert!(
+            matches!(
+                error,
+                Error::MissingEnvVar {
+                    name: "REDDIT_CLIENT_SECRET"
+                }
+            ),
+            "error = {error:#?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn parse_error_keeps_response() {
+        let response = http::Response::builder()
+            .status(200)
+            .header("x-reddit-request-id", "abc123")
+            .body("[{\"kind\": \"Listing\"")
+            .unwrap();
+        let error = Client::new()
+            .parse_response::<Vec<Thing>>(reqwest::Response::from(response))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.status(), Some(reqwest::StatusCode::OK));
+        match error {
+            Error::Json {
+                data, request_id, ..
+            } => {
+                assert_eq!(&*data, "[{\"kind\": \"Listing\"");
+                assert_eq!(request_id.as_deref(), Some("abc123"));
+            }
+            error => panic!("unexpected error {error:?}"),
+        }
+    }
+
+    /// Serve one http response on a local port, once the request read so far ends with request_end.
+    fn serve_once(
+        request_end: &'static [u8],
+        response: &'static [u8],
+    ) -> (std::net::SocketAddr, std::thread::JoinHandle<()>) {
+        use std::io::{
+            Read,
+            Write,
+        };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(request_end) {
+                let n = stream.read(&mut buffer).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..n]);
+            }
+            stream.write_all(response).unwrap();
+        });
+
+        (addr, server)
+    }
+
+    #[tokio::test]
+    async fn upload_to_lease_skips_rate_limit() {
+        // S3 does not send rate limit headers, but if it did they must not be mistaken for reddit's.
+        let (addr, server) = serve_once(
+            b"--\r\n",
+            b"HTTP/1.1 201 Created\r\n\
+            x-ratelimit-remaining: 0\r\n\
+            x-ratelimit-used: 100\r\n\
+            x-ratelimit-reset: 600\r\n\
+            content-length: 0\r\n\r\n",
+        );
+
+        let lease: S3UploadLease = serde_json::from_str(&format!(
+            r#"{{"action": "http://{addr}", "fields": [{{"name": "key", "value": "t5_abc/emoji.png"}}]}}"#
+        ))
+        .unwrap();
+        let client = Client::new();
+        client
+            .upload_to_lease(&lease, "emoji.png", "image/png", b"png data")
+            .await
+            .unwrap();
+        server.join().unwrap();
+
+        assert!(client.rate_limit().is_none());
+    }
+
+    #[tokio::test]
+    async fn post_not_found_keeps_response() {
+        let (addr, server) = serve_once(
+            b"\r\n\r\n",
+            b"HTTP/1.1 404 Not Found\r\ncontent-length: 2\r\n\r\n{}",
+        );
+
+        let client = Client::new();
+        let url = format!("http://{addr}/comments/zzzzzzz.json");
+        let error = client
+            .get_post_at(client.request(reqwest::Method::GET, &url))
+            .await
+            .unwrap_err();
+        server.join().unwrap();
+
+        assert!(error.is_post_not_found(), "error = {error:#?}");
+        assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+        assert_eq!(
+            error.url().map(|url| url.path()),
+            Some("/comments/zzzzzzz.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn download_gallery_rejects_unsafe_media_ids() {
+        let thing: Thing =
+            serde_json::from_str(include_str!("../test_data/subreddit_cromch.json")).unwrap();
+        let mut link = *thing
+            .data
+            .into_listing()
+            .unwrap()
+            .children
+            .into_iter()
+            .filter_map(|child| child.data.into_link())
+            .find(|link| link.gallery_data.is_some())
+            .unwrap();
+
+        let media_metadata = link.media_metadata.as_mut().unwrap();
+        let items = &mut link.gallery_data.as_mut().unwrap().items;
+        for (i, item) in items.iter_mut().enumerate() {
+            let metadata = media_metadata.remove(&item.media_id).unwrap();
+            item.media_id = format!("../../evil{i}").into();
+            media_metadata.insert(item.media_id.clone(), metadata);
+        }
+
+        let dir = std::env::temp_dir().join("reddit-rs-download-gallery-test");
+        let error = Client::new()
+            .download_gallery(&link, &dir, 4)
+            .await
+            .unwrap_err();
+        assert!(error.is_invalid_param(), "error = {error:#?}");
+        assert!(!dir.exists());
+    }
+
+    #[tokio::test]
+    async fn get_my_karma_requires_access_token() {
+        let client = Client::new();
+        let error = client.get_my_karma().await.unwrap_err();
+        assert!(
+            matches!(error, Error::MissingAccessToken),
+            "error = {error:#?}"
+        );
+    }
+
+    #[test]
+    fn raw_json_query() {
+        let url = "https://www.reddit.com/r/aww.json?limit=25";
+
+        let client = Client::new();
+        let request = client.request(reqwest::Method::GET, url).build().unwrap();
+        assert_eq!(request.url().query(), Some("limit=25&raw_json=1"));
+
+        let client = Client::builder().raw_json(false).build().unwrap();
+        let request = client.request(reqwest::Method::GET, url).build().unwrap();
+        assert_eq!(request.url().query(), Some("limit=25"));
+    }
+
+    #[test]
+    fn quarantine_opt_in_cookie() {
+        let url = "https://www.reddit.com/r/aww.json";
+
+        let client = Client::new();
+        let request = client.request(reqwest::Method::GET, url).build().unwrap();
+        assert!(request.headers().get(reqwest::header::COOKIE).is_none());
+
+        let client = Client::builder().quarantine_opt_in(true).build().unwrap();
+        let request = client.request(reqwest::Method::GET, url).build().unwrap();
+        assert_eq!(
+            request.headers()[reqwest::header::COOKIE],
+            QUARANTINE_OPT_IN_COOKIE
+        );
+    }
+
+    #[test]
+    fn api_request_host() {
+        let client = Client::new();
+        let request = client
+            .api_request(reqwest::Method::GET, "/r/aww")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://www.reddit.com/r/aww.json?raw_json=1"
+        );
+        assert!(request
+            .headers()
+            .get(reqwest::header::AUTHORIZATION)
+            .is_none());
+
+        // Clients with an access token use it, so its quarantine opt-ins apply.
+        let client = Client::builder().access_token("token").build().unwrap();
+        let request = client
+            .api_request(reqwest::Method::GET, "/r/aww")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://oauth.reddit.com/r/aww?raw_json=1"
+        );
+        assert_eq!(
+            request.headers()[reqwest::header::AUTHORIZATION],
+            "Bearer token"
+        );
+    }
+
+    #[test]
+    fn order_things_by_fullnames() {
+        let thing: Thing =
+            serde_json::from_str(include_str!("../test_data/subreddit_dankmemes.json")).unwrap();
+        let listing = thing.data.into_listing().unwrap();
+        let names: Vec<String> = listing
+            .children
+            .iter()
+            .map(|thing| thing.fullname().unwrap().to_string())
+            .collect();
+
+        let fullnames = [&*names[2], "t3_missing", &*names[0], &*names[1]];
+        let things = order_by_fullnames(&fullnames, listing.children.into_iter());
+        let ordered: Vec<_> = things
+            .iter()
+            .map(|thing| thing.fullname().unwrap())
+            .collect();
+        assert_eq!(ordered, [&*names[2], &*names[0], &*names[1]]);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn get_info_works() {
+        let client = Client::new();
+        let thing = client.get_subreddit("aww", 150).await.unwrap();
+        let listing = thing.data.into_listing().unwrap();
+        let mut fullnames: Vec<_> = listing
+            .children
+            .iter()
+            .map(|thing| thing.fullname().unwrap())
+            .collect();
+        fullnames.reverse();
+
+        let things = client.get_info(&fullnames, 2).await.unwrap();
+        let names: Vec<_> = things
+            .iter()
+            .map(|thing| thing.fullname().unwrap())
+            .collect();
+        assert_eq!(names, fullnames);
+    }
+
+    #[test]
+    fn parse_relationship_page() {
+        let page: RelationshipPage = serde_json::from_str(
+            r#"{
+                "kind": "Listing",
+                "data": {
+                    "after": "rb_1a2b3d",
+                    "before": null,
+                    "children": [
+                        {
+                            "date": 1650000000.0,
+                            "days_left": 3,
+                            "rel_id": "rb_1a2b3c",
+                            "note": "spam",
+                            "name": "some_user",
+                            "id": "t2_1w72"
+                        },
+                        {
+                            "date": 1640000000.0,
+                            "rel_id": "rb_1a2b3d",
+                            "name": "other_user",
+                            "id": "t2_1w73"
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(page.data.after.as_deref(), Some("rb_1a2b3d"));
+
+        let [banned, contributor] = &page.data.children[..] else {
+            panic!("expected 2 relationships");
+        };
+        assert_eq!(&*banned.name, "some_user");
+        assert_eq!(banned.days_left, Some(3));
+        assert_eq!(banned.note.as_deref(), Some("spam"));
+        assert!(contributor.note.is_none());
+        assert!(contributor.days_left.is_none());
+    }
 }
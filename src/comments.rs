@@ -0,0 +1,204 @@
+use crate::types::{
+    Comment,
+    Link,
+    Listing,
+    Thing,
+    ThingData,
+};
+
+impl Listing {
+    /// Walk the comments of this listing and all of their replies in the order reddit displays them.
+    ///
+    /// Each comment is yielded with its depth, starting at 0 for the children of this listing.
+    /// "more" placeholders are skipped, as they have not been loaded.
+    pub fn walk_comments(&self) -> WalkComments<'_> {
+        WalkComments {
+            stack: vec![self.children.iter()],
+        }
+    }
+
+    /// Remove a comment, and its replies, from this listing or the replies of the comments in it.
+    ///
+    /// fullname is the fullname of the comment, like "t1_c3v7f8u".
+    pub fn take_comment(&mut self, fullname: &str) -> Option<Box<Comment>> {
+        let index = self.children.iter().position(|thing| {
+            thing
+                .data
+                .as_comment()
+                .is_some_and(|comment| &*comment.name == fullname)
+        });
+        if let Some(index) = index {
+            return self.children.remove(index).data.into_comment();
+        }
+
+        self.children
+            .iter_mut()
+            .filter_map(|thing| match &mut thing.data {
+                ThingData::Comment(comment) => comment.replies.as_deref_mut(),
+                _ => None,
+            })
+            .find_map(|replies| replies.take_comment(fullname))
+    }
+}
+
+/// The maximum number of ancestors reddit returns with a comment.
+pub const MAX_COMMENT_CONTEXT: u8 = 8;
+
+/// A comment with the comments it replies to, from [`Client::get_comment_with_context`](crate::Client::get_comment_with_context).
+#[derive(Debug)]
+pub struct CommentContext {
+    /// the post the comment is in
+    pub link: Box<Link>,
+
+    /// the comments the comment replies to, oldest ancestor first.
+    /// their replies are removed, as they contain the comment
+    pub ancestors: Vec<Comment>,
+
+    /// the comment, including its replies
+    pub comment: Comment,
+}
+
+/// Remove a comment and its ancestors from a comment tree, where fullname is the fullname of the comment.
+///
+/// The comments are returned oldest ancestor first, and the replies of the ancestors are removed.
+pub(crate) fn take_comment_with_ancestors(
+    listing: &mut Listing,
+    fullname: &str,
+) -> Option<Vec<Comment>> {
+    for index in 0..listing.children.len() {
+        let ThingData::Comment(comment) = &mut listing.children[index].data else {
+            continue;
+        };
+        if &*comment.name == fullname {
+            return Some(vec![*listing.children.remove(index).data.into_comment()?]);
+        }
+
+        let Some(mut comments) = comment
+            .replies
+            .as_deref_mut()
+            .and_then(|replies| take_comment_with_ancestors(replies, fullname))
+        else {
+            continue;
+        };
+        let mut ancestor = *listing.children.remove(index).data.into_comment()?;
+        ancestor.replies = None;
+        comments.insert(0, ancestor);
+        return Some(comments);
+    }
+
+    None
+}
+
+impl Comment {
+    /// Walk the replies of this comment and all of their replies in the order reddit displays them.
+    ///
+    /// Each reply is yielded with its depth, starting at 0 for the direct replies of this comment.
+    pub fn walk_replies(&self) -> WalkComments<'_> {
+        WalkComments {
+            stack: self
+                .replies
+                .iter()
+                .map(|replies| replies.children.iter())
+                .collect(),
+        }
+    }
+}
+
+/// An iterator over a tree of comments, in depth-first order.
+///
+/// See [`Listing::walk_comments`].
+#[derive(Debug, Clone)]
+pub struct WalkComments<'a> {
+    stack: Vec<std::slice::Iter<'a, Thing>>,
+}
+
+impl<'a> Iterator for WalkComments<'a> {
+    type Item = (&'a Comment, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let depth = self.stack.len().checked_sub(1)?;
+            let Some(thing) = self.stack[depth].next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            if let ThingData::Comment(comment) = &thing.data {
+                if let Some(replies) = comment.replies.as_deref() {
+                    self.stack.push(replies.children.iter());
+                }
+                return Some((comment, depth));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const COMMENT_SAMPLE_1: &str = include_str!("../test_data/comment_h966lq.json");
+    const COMMENT_SAMPLE_2: &str = include_str!("../test_data/comment_h8p0py.json");
+
+    #[test]
+    fn walk_comments() {
+        for sample in [COMMENT_SAMPLE_1, COMMENT_SAMPLE_2] {
+            let things: Vec<Thing> = serde_json::from_str(sample).unwrap();
+            let comments = things[1].data.as_listing().unwrap();
+
+            let walked: Vec<_> = comments.walk_comments().collect();
+            assert_eq!(walked.len(), sample.matches(r#""kind": "t1""#).count());
+            for (comment, depth) in walked.iter() {
+                assert_eq!(comment.depth, Some(*depth as u32), "{}", comment.name);
+            }
+
+            // Replies are yielded right after the comment they reply to.
+            for window in walked.windows(2) {
+                let (previous, previous_depth) = window[0];
+                let (comment, depth) = window[1];
+                if depth > previous_depth {
+                    assert_eq!(&*comment.parent_id, &*previous.name);
+                }
+            }
+
+            let (first, _) = walked[0];
+            assert!(first
+                .walk_replies()
+                .all(|(reply, depth)| reply.depth == Some(depth as u32 + 1)));
+        }
+    }
+
+    #[test]
+    fn take_comment() {
+        let mut things: Vec<Thing> = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let comments = things[1].data.as_listing_mut().unwrap();
+        let count = comments.walk_comments().count();
+
+        let comment = comments.take_comment("t1_fut2zpi").unwrap();
+        assert_eq!(comment.depth, Some(5));
+        let replies = comment.walk_replies().count();
+        assert!(replies > 0);
+        assert_eq!(comments.walk_comments().count(), count - replies - 1);
+
+        assert!(comments.take_comment("t1_fut2zpi").is_none());
+    }
+
+    #[test]
+    fn take_comment_with_ancestors_works() {
+        let mut things: Vec<Thing> = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let comments = things[1].data.as_listing_mut().unwrap();
+
+        let path = take_comment_with_ancestors(comments, "t1_fut2zpi").unwrap();
+        let (comment, ancestors) = path.split_last().unwrap();
+        assert_eq!(&*comment.name, "t1_fut2zpi");
+        assert!(comment.replies.is_some());
+        assert_eq!(ancestors.len(), 5);
+        for (depth, ancestor) in ancestors.iter().enumerate() {
+            assert_eq!(ancestor.depth, Some(depth as u32));
+            assert!(ancestor.replies.is_none());
+        }
+        for pair in path.windows(2) {
+            assert_eq!(pair[1].parent_id, pair[0].name);
+        }
+    }
+}
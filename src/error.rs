@@ -1,9 +1,14 @@
+use std::time::Duration;
+
 /// Error type for this library
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
 
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     /// Json parse error
     #[error("failed to parse json")]
     Json {
@@ -13,16 +18,456 @@ pub enum Error {
         /// The parse error
         #[source]
         error: serde_json::Error,
+
+        /// The url of the response, if the data came from one
+        url: Option<Box<url::Url>>,
+
+        /// The status of the response, if the data came from one
+        status: Option<reqwest::StatusCode>,
+
+        /// The value of the `x-reddit-request-id` header of the response, if present.
+        /// This is useful when reporting issues to reddit
+        request_id: Option<Box<str>>,
+    },
+
+    /// Feed parse error
+    #[cfg(feature = "rss")]
+    #[error("failed to parse feed")]
+    Feed {
+        /// The data that was being parsed
+        data: Box<str>,
+
+        /// The parse error
+        #[source]
+        error: quick_xml::DeError,
     },
 
     /// Failed to find subreddit
     #[error("failed to locate the subreddit")]
     SubredditNotFound,
+
+    /// Failed to find post
+    #[error("failed to locate the post")]
+    PostNotFound {
+        /// The url of the response, if the error came from one
+        url: Option<Box<url::Url>>,
+
+        /// The status of the response, if reddit reported the error with an error status
+        status: Option<reqwest::StatusCode>,
+    },
+
+    /// Failed to find comment
+    #[error("failed to locate the comment")]
+    CommentNotFound,
+
+    /// The subreddit is private, and the client is not an approved user
+    #[error("the subreddit is private")]
+    SubredditPrivate {
+        /// The url of the response, if the error came from one
+        url: Option<Box<url::Url>>,
+
+        /// The status of the response, if reddit reported the error with an error status
+        status: Option<reqwest::StatusCode>,
+    },
+
+    /// The subreddit is quarantined, and the client has not opted in to viewing it.
+    /// See [`ClientBuilder::quarantine_opt_in`](crate::ClientBuilder::quarantine_opt_in)
+    #[error("the subreddit is quarantined")]
+    SubredditQuarantined {
+        /// The url of the response, if the error came from one
+        url: Option<Box<url::Url>>,
+
+        /// The status of the response, if reddit reported the error with an error status
+        status: Option<reqwest::StatusCode>,
+    },
+
+    /// The subreddit was banned by reddit
+    #[error("the subreddit is banned")]
+    SubredditBanned {
+        /// The url of the response, if the error came from one
+        url: Option<Box<url::Url>>,
+
+        /// The status of the response, if reddit reported the error with an error status
+        status: Option<reqwest::StatusCode>,
+    },
+
+    /// The post does not have the requested media
+    #[error("the post has no media")]
+    MissingMedia,
+
+    /// The response was expected to be a listing, but was not
+    #[error("expected a listing")]
+    ExpectedListing,
+
+    /// The url is not a reddit url of the expected kind
+    #[error("invalid reddit url")]
+    InvalidUrl,
+
+    /// A user-agent does not follow reddit's rules
+    #[error("invalid user-agent: {reason}")]
+    InvalidUserAgent {
+        /// Why the user-agent is invalid
+        reason: &'static str,
+    },
+
+    /// A post does not meet the requirements of a subreddit
+    #[error("invalid submission: {problem}")]
+    InvalidSubmission {
+        /// The first problem found with the post
+        problem: crate::submit::SubmissionProblem,
+    },
+
+    /// A string is not a valid value of a query parameter, like a sort
+    #[error("invalid {kind} \"{value}\"")]
+    InvalidParam {
+        /// The kind of parameter, like "sort"
+        kind: &'static str,
+
+        /// The invalid value
+        value: Box<str>,
+    },
+
+    /// Some, but not all, of the environment variables of a configuration are set
+    #[error("missing environment variable {name}")]
+    MissingEnvVar {
+        /// The name of the first missing variable
+        name: &'static str,
+    },
+
+    /// The endpoint requires an access token, but the client does not have one
+    #[error("missing access token")]
+    MissingAccessToken,
+
+    /// Reddit rejected the request because too many requests were made
+    #[error("rate limited, retry after {retry_after:?}")]
+    RateLimited {
+        /// How long to wait before retrying
+        retry_after: Duration,
+
+        /// The url of the response, if the error came from one
+        url: Option<Box<url::Url>>,
+
+        /// The status of the response, if reddit reported the error with an error status
+        status: Option<reqwest::StatusCode>,
+    },
+
+    /// Reddit rejected the request with an api error, like `THREAD_LOCKED` or `NO_TEXT`
+    #[error("reddit api error {code}: {message}")]
+    Api {
+        /// The error code
+        code: Box<str>,
+
+        /// The human readable message
+        message: Box<str>,
+
+        /// The name of the field the error is about, if any
+        field: Option<Box<str>>,
+    },
+
+    /// A background task, like the prefetch of a [`Paginator`](crate::pagination::Paginator), was cancelled before it finished
+    #[error("a background task was cancelled")]
+    Cancelled,
+
+    /// A middleware of the client failed
+    #[cfg(feature = "middleware")]
+    #[error("middleware error")]
+    Middleware(#[source] reqwest_middleware::Error),
+}
+
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for Error {
+    fn from(error: reqwest_middleware::Error) -> Self {
+        match error {
+            reqwest_middleware::Error::Reqwest(error) => Self::Reqwest(error),
+            error => Self::Middleware(error),
+        }
+    }
 }
 
 impl Error {
+    /// Returns `true` if the error type is `InvalidUrl`, `false` otherwise.
+    pub fn is_invalid_url(&self) -> bool {
+        matches!(self, Self::InvalidUrl)
+    }
+
+    /// Returns `true` if the error type is `InvalidUserAgent`, `false` otherwise.
+    pub fn is_invalid_user_agent(&self) -> bool {
+        matches!(self, Self::InvalidUserAgent { .. })
+    }
+
+    /// Returns `true` if the error type is `InvalidSubmission`, `false` otherwise.
+    pub fn is_invalid_submission(&self) -> bool {
+        matches!(self, Self::InvalidSubmission { .. })
+    }
+
+    /// Returns `true` if the error type is `InvalidParam`, `false` otherwise.
+    pub fn is_invalid_param(&self) -> bool {
+        matches!(self, Self::InvalidParam { .. })
+    }
+
     /// Returns `true` if the error type is `SubredditNotFound`, `false` otherwise.
     pub fn is_subreddit_not_found(&self) -> bool {
         matches!(self, Self::SubredditNotFound)
     }
+
+    /// Returns `true` if the error type is `PostNotFound`, `false` otherwise.
+    pub fn is_post_not_found(&self) -> bool {
+        matches!(self, Self::PostNotFound { .. })
+    }
+
+    /// Returns `true` if the error type is `CommentNotFound`, `false` otherwise.
+    pub fn is_comment_not_found(&self) -> bool {
+        matches!(self, Self::CommentNotFound)
+    }
+
+    /// Returns `true` if the error type is `SubredditPrivate`, `false` otherwise.
+    pub fn is_subreddit_private(&self) -> bool {
+        matches!(self, Self::SubredditPrivate { .. })
+    }
+
+    /// Returns `true` if the error type is `SubredditQuarantined`, `false` otherwise.
+    pub fn is_subreddit_quarantined(&self) -> bool {
+        matches!(self, Self::SubredditQuarantined { .. })
+    }
+
+    /// Returns `true` if the error type is `SubredditBanned`, `false` otherwise.
+    pub fn is_subreddit_banned(&self) -> bool {
+        matches!(self, Self::SubredditBanned { .. })
+    }
+
+    /// Returns `true` if the error type is `RateLimited`, `false` otherwise.
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, Self::RateLimited { .. })
+    }
+
+    /// Returns `true` if the error type is `Cancelled`, `false` otherwise.
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled)
+    }
+
+    /// Returns `true` if reddit rejected the request because a captcha is required, or its solution was wrong.
+    ///
+    /// See [`Client::new_captcha`](crate::Client::new_captcha).
+    pub fn is_bad_captcha(&self) -> bool {
+        matches!(self, Self::Api { code, .. } if &**code == "BAD_CAPTCHA")
+    }
+
+    /// Returns `true` if retrying the request that caused this error may succeed.
+    ///
+    /// Timeouts, connection failures, rate limits, and server errors are retryable.
+    /// Errors like missing subreddits and parse failures are permanent.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Io(error) => matches!(
+                error.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::Interrupted
+            ),
+            Self::Reqwest(error) => {
+                if error.is_timeout() || error.is_connect() {
+                    return true;
+                }
+
+                // A request or body error without a status means the connection was dropped.
+                match error.status() {
+                    Some(status) => {
+                        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    }
+                    None => error.is_request() || error.is_body(),
+                }
+            }
+            Self::RateLimited { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Get the http status of the response that caused this error, if it was caused by one.
+    ///
+    /// Errors that reddit reports in the body of a successful response, like api errors, have no status.
+    pub fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            Self::Reqwest(error) => error.status(),
+            Self::Json { status, .. }
+            | Self::PostNotFound { status, .. }
+            | Self::SubredditPrivate { status, .. }
+            | Self::SubredditQuarantined { status, .. }
+            | Self::SubredditBanned { status, .. }
+            | Self::RateLimited { status, .. } => *status,
+            _ => None,
+        }
+    }
+
+    /// Get the final url of the response that caused this error, if it is known.
+    pub fn url(&self) -> Option<&url::Url> {
+        match self {
+            Self::Reqwest(error) => error.url(),
+            Self::Json { url, .. }
+            | Self::PostNotFound { url, .. }
+            | Self::SubredditPrivate { url, .. }
+            | Self::SubredditQuarantined { url, .. }
+            | Self::SubredditBanned { url, .. }
+            | Self::RateLimited { url, .. } => url.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The body of an error response, like `{"reason": "private", "message": "Forbidden", "error": 403}`.
+#[derive(serde::Deserialize)]
+struct ErrorBody {
+    reason: Option<Box<str>>,
+}
+
+/// The body of a response with api errors, like
+/// `{"json": {"errors": [["NO_TEXT", "we need something here", "text"]]}}`.
+#[derive(serde::Deserialize)]
+struct ApiErrorBody {
+    json: ApiErrorJson,
+}
+
+#[derive(serde::Deserialize)]
+struct ApiErrorJson {
+    #[serde(default)]
+    errors: Vec<Vec<Option<Box<str>>>>,
+}
+
+impl Error {
+    /// Try to get an api error from a response body.
+    ///
+    /// Reddit reports these with a 200 status, so every body should be checked.
+    /// `RATELIMIT` errors are returned as `RateLimited`.
+    pub(crate) fn from_api_errors(body: &[u8]) -> Option<Self> {
+        // Avoid parsing every body twice.
+        const ERRORS_KEY: &[u8] = b"\"errors\"";
+        if !body
+            .windows(ERRORS_KEY.len())
+            .any(|window| window == ERRORS_KEY)
+        {
+            return None;
+        }
+
+        let body: ApiErrorBody = serde_json::from_slice(body).ok()?;
+        let mut error = body.json.errors.into_iter().next()?.into_iter();
+        let code = error.next().flatten()?;
+        let message = error.next().flatten().unwrap_or_default();
+        let field = error.next().flatten().filter(|field| !field.is_empty());
+
+        if &*code == "RATELIMIT" {
+            return Some(Self::RateLimited {
+                retry_after: crate::rate_limit::retry_after_from_message(&message),
+                url: None,
+                status: None,
+            });
+        }
+
+        Some(Self::Api {
+            code,
+            message,
+            field,
+        })
+    }
+
+    /// Try to get a more specific error from the body of an error response, with its status and final url.
+    pub(crate) fn from_error_body(
+        text: &str,
+        status: reqwest::StatusCode,
+        url: &url::Url,
+    ) -> Option<Self> {
+        let body: ErrorBody = serde_json::from_str(text).ok()?;
+        let url = Some(Box::new(url.clone()));
+        let status = Some(status);
+        match body.reason.as_deref()? {
+            "private" => Some(Self::SubredditPrivate { url, status }),
+            "quarantined" => Some(Self::SubredditQuarantined { url, status }),
+            "banned" => Some(Self::SubredditBanned { url, status }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_error_body() {
+        use reqwest::StatusCode;
+
+        let url = url::Url::parse("https://www.reddit.com/r/test.json").unwrap();
+
+        let error = Error::from_error_body(
+            r#"{"reason": "private", "message": "Forbidden", "error": 403}"#,
+            StatusCode::FORBIDDEN,
+            &url,
+        )
+        .unwrap();
+        assert!(error.is_subreddit_private());
+
+        let error = Error::from_error_body(
+            r#"{"reason": "quarantined", "quarantine_message": "", "message": "Forbidden", "error": 403}"#,
+            StatusCode::FORBIDDEN,
+            &url,
+        )
+        .unwrap();
+        assert!(error.is_subreddit_quarantined());
+
+        let error = Error::from_error_body(
+            r#"{"reason": "banned", "message": "Not Found", "error": 404}"#,
+            StatusCode::NOT_FOUND,
+            &url,
+        )
+        .unwrap();
+        assert!(error.is_subreddit_banned());
+        assert!(!error.is_subreddit_not_found());
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        assert_eq!(error.url(), Some(&url));
+        assert!(!error.is_retryable());
+
+        assert!(Error::from_error_body(
+            r#"{"message": "Forbidden", "error": 403}"#,
+            StatusCode::FORBIDDEN,
+            &url
+        )
+        .is_none());
+        assert!(Error::from_error_body("<html></html>", StatusCode::FORBIDDEN, &url).is_none());
+    }
+
+    #[test]
+    fn from_api_errors() {
+        let error = Error::from_api_errors(
+            br#"{"json": {"errors": [["THREAD_LOCKED", "that comment is locked", "parent"]]}}"#,
+        )
+        .unwrap();
+        match error {
+            Error::Api {
+                code,
+                message,
+                field,
+            } => {
+                assert_eq!(&*code, "THREAD_LOCKED");
+                assert_eq!(&*message, "that comment is locked");
+                assert_eq!(field.as_deref(), Some("parent"));
+            }
+            error => panic!("unexpected error {error:?}"),
+        }
+
+        let error = Error::from_api_errors(
+            br#"{"json": {"errors": [["RATELIMIT", "you are doing that too much. try again in 9 minutes.", "ratelimit"]]}}"#,
+        )
+        .unwrap();
+        assert!(error.is_rate_limited());
+        assert!(error.is_retryable());
+        // The body of a successful response reported this, so there is no error status.
+        assert_eq!(error.status(), None);
+
+        let error = Error::from_api_errors(
+            br#"{"json": {"captcha": "xk5fgJ2IbCnH0TqkHNyk4TfTxaphGoWD", "errors": [["BAD_CAPTCHA", "care to try these again?", "captcha"]]}}"#,
+        )
+        .unwrap();
+        assert!(error.is_bad_captcha());
+
+        assert!(Error::from_api_errors(br#"{"json": {"errors": []}}"#).is_none());
+        assert!(Error::from_api_errors(b"{}").is_none());
+    }
 }
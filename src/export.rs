@@ -0,0 +1,172 @@
+use crate::types::Link;
+use std::io::Write;
+
+/// A column of a csv export of links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkColumn {
+    Id,
+    Name,
+    Title,
+    Author,
+    Subreddit,
+    Score,
+    UpvoteCount,
+    UpvoteRatio,
+    NumComments,
+    CreatedUtc,
+    Url,
+    Permalink,
+    Domain,
+    IsSelf,
+    Over18,
+    Spoiler,
+    Selftext,
+    LinkFlairText,
+}
+
+/// The columns used when exporting links, if no others are needed.
+pub const DEFAULT_LINK_COLUMNS: &[LinkColumn] = &[
+    LinkColumn::Id,
+    LinkColumn::Title,
+    LinkColumn::Author,
+    LinkColumn::Subreddit,
+    LinkColumn::Score,
+    LinkColumn::NumComments,
+    LinkColumn::CreatedUtc,
+    LinkColumn::Url,
+    LinkColumn::Permalink,
+];
+
+impl LinkColumn {
+    /// Get the name of this column, used in the header row.
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Title => "title",
+            Self::Author => "author",
+            Self::Subreddit => "subreddit",
+            Self::Score => "score",
+            Self::UpvoteCount => "ups",
+            Self::UpvoteRatio => "upvote_ratio",
+            Self::NumComments => "num_comments",
+            Self::CreatedUtc => "created_utc",
+            Self::Url => "url",
+            Self::Permalink => "permalink",
+            Self::Domain => "domain",
+            Self::IsSelf => "is_self",
+            Self::Over18 => "over_18",
+            Self::Spoiler => "spoiler",
+            Self::Selftext => "selftext",
+            Self::LinkFlairText => "link_flair_text",
+        }
+    }
+
+    /// Get the value of this column for a link.
+    pub fn value(self, link: &Link) -> String {
+        match self {
+            Self::Id => link.id.to_string(),
+            Self::Name => link.name.to_string(),
+            Self::Title => link.title.to_string(),
+            Self::Author => link.author.to_string(),
+            Self::Subreddit => link.subreddit.to_string(),
+            Self::Score => link.score.to_string(),
+            Self::UpvoteCount => link.votable.ups.to_string(),
+            Self::UpvoteRatio => link
+                .upvote_ratio
+                .map(|ratio| ratio.to_string())
+                .unwrap_or_default(),
+            Self::NumComments => link.num_comments.to_string(),
+            Self::CreatedUtc => link.created.created_utc.to_string(),
+            Self::Url => link.url.to_string(),
+            Self::Permalink => link.permalink.to_string(),
+            Self::Domain => link.domain.to_string(),
+            Self::IsSelf => link.is_self.to_string(),
+            Self::Over18 => link.over_18.to_string(),
+            Self::Spoiler => link.spoiler.to_string(),
+            Self::Selftext => link.selftext.to_string(),
+            Self::LinkFlairText => link
+                .link_flair_text
+                .as_deref()
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+}
+
+/// Write links as csv, with a header row followed by a row for each link.
+///
+/// Fields are quoted as needed, following RFC 4180.
+pub fn write_links_csv<W>(
+    mut writer: W,
+    links: &[Link],
+    columns: &[LinkColumn],
+) -> std::io::Result<()>
+where
+    W: Write,
+{
+    let header = columns.iter().map(|column| column.header().to_string());
+    write_csv_row(&mut writer, header)?;
+
+    for link in links {
+        let row = columns.iter().map(|column| column.value(link));
+        write_csv_row(&mut writer, row)?;
+    }
+
+    writer.flush()
+}
+
+/// Write a row of csv fields, quoting them as needed.
+fn write_csv_row<W, I>(writer: &mut W, fields: I) -> std::io::Result<()>
+where
+    W: Write,
+    I: Iterator<Item = String>,
+{
+    for (i, field) in fields.enumerate() {
+        if i != 0 {
+            writer.write_all(b",")?;
+        }
+
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+    }
+
+    writer.write_all(b"\r\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn links_csv() {
+        let links = [
+            Link {
+                id: "15bfi0".into(),
+                title: "Hello, \"world\"".into(),
+                score: 42,
+                ..Link::default()
+            },
+            Link {
+                id: "15bfi1".into(),
+                title: "two\nlines".into(),
+                ..Link::default()
+            },
+        ];
+
+        let mut csv = Vec::new();
+        write_links_csv(
+            &mut csv,
+            &links,
+            &[LinkColumn::Id, LinkColumn::Title, LinkColumn::Score],
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "id,title,score\r\n15bfi0,\"Hello, \"\"world\"\"\",42\r\n15bfi1,\"two\nlines\",0\r\n"
+        );
+    }
+}
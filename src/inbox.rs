@@ -0,0 +1,246 @@
+use crate::types::{
+    Listing,
+    Message,
+    ThingData,
+};
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+/// The outcome of [`Client::mark_all_read`](crate::Client::mark_all_read)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReadAllStatus {
+    /// All messages were marked as read
+    Done,
+
+    /// Reddit queued the request with a 202 response, and will mark the messages as read shortly.
+    /// Until then, messages may still be returned as unread
+    Queued,
+}
+
+impl ReadAllStatus {
+    /// Returns `true` if reddit queued the request.
+    pub fn is_queued(self) -> bool {
+        matches!(self, Self::Queued)
+    }
+}
+
+/// A private message conversation
+#[derive(Debug)]
+pub struct MessageThread {
+    /// the fullname of the first message of the conversation, like "t4_1x9d2kq"
+    pub first_message_name: Box<str>,
+
+    /// the messages that do not reply to another message of the conversation, oldest first.
+    /// This is only the first message, unless the messages it replies to were not loaded
+    pub messages: Vec<ThreadMessage>,
+}
+
+/// A message of a [`MessageThread`], with the messages that reply to it
+#[derive(Debug)]
+pub struct ThreadMessage {
+    /// the message. Its replies are moved into [`ThreadMessage::replies`]
+    pub message: Message,
+
+    /// the messages that reply to this one, oldest first
+    pub replies: Vec<ThreadMessage>,
+}
+
+impl MessageThread {
+    /// Group messages into conversations by their first message.
+    ///
+    /// The replies included with first messages are grouped too, and duplicate messages are skipped.
+    /// Comment replies and mentions are skipped, as they are not part of a conversation.
+    /// Conversations are returned with the most recently active first.
+    pub fn from_messages(messages: impl IntoIterator<Item = Message>) -> Vec<Self> {
+        let mut flattened = Vec::new();
+        for message in messages {
+            flatten_message(message, &mut flattened);
+        }
+        let mut seen = HashSet::new();
+        flattened.retain(|message| !message.was_comment && seen.insert(message.name.clone()));
+        flattened.sort_by(|a, b| a.created.created_utc.total_cmp(&b.created.created_utc));
+
+        let mut order = Vec::new();
+        let mut groups: HashMap<Box<str>, Vec<Message>> = HashMap::new();
+        for message in flattened {
+            let key = message
+                .first_message_name
+                .clone()
+                .unwrap_or_else(|| message.name.clone());
+            groups
+                .entry(key)
+                .or_insert_with_key(|key| {
+                    order.push(key.clone());
+                    Vec::new()
+                })
+                .push(message);
+        }
+
+        let mut threads: Vec<Self> = order
+            .into_iter()
+            .filter_map(|key| {
+                let messages = groups.remove(&key)?;
+                Some(Self {
+                    first_message_name: key,
+                    messages: nest_messages(messages),
+                })
+            })
+            .collect();
+        threads.sort_by(|a, b| b.last_activity_utc().total_cmp(&a.last_activity_utc()));
+
+        threads
+    }
+
+    /// Iterate over the messages of this conversation, with each message followed by its replies.
+    pub fn iter(&self) -> impl Iterator<Item = &Message> + '_ {
+        let mut stack: Vec<&ThreadMessage> = self.messages.iter().rev().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.replies.iter().rev());
+            Some(&node.message)
+        })
+    }
+
+    /// Get the time of the newest message of this conversation, in UTC epoch-second format.
+    pub fn last_activity_utc(&self) -> f64 {
+        self.iter()
+            .map(|message| message.created.created_utc)
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl Listing {
+    /// Group the messages of an inbox listing into conversations.
+    ///
+    /// See [`MessageThread::from_messages`].
+    pub fn into_message_threads(self) -> Vec<MessageThread> {
+        MessageThread::from_messages(
+            self.children
+                .into_iter()
+                .filter_map(|thing| thing.data.into_message())
+                .map(|message| *message),
+        )
+    }
+}
+
+/// Push a message and the messages in its replies.
+fn flatten_message(mut message: Message, messages: &mut Vec<Message>) {
+    let replies = message.replies.take();
+    messages.push(message);
+    for thing in replies.into_iter().flat_map(|replies| replies.children) {
+        if let ThingData::Message(reply) = thing.data {
+            flatten_message(*reply, messages);
+        }
+    }
+}
+
+/// Nest the messages of a conversation under the messages they reply to, keeping their order.
+fn nest_messages(messages: Vec<Message>) -> Vec<ThreadMessage> {
+    let names: HashSet<Box<str>> = messages
+        .iter()
+        .map(|message| message.name.clone())
+        .collect();
+
+    let mut roots = Vec::new();
+    let mut replies: HashMap<Box<str>, Vec<Message>> = HashMap::new();
+    for message in messages {
+        match message
+            .parent_id
+            .clone()
+            .filter(|parent_id| names.contains(parent_id) && *parent_id != message.name)
+        {
+            Some(parent_id) => replies.entry(parent_id).or_default().push(message),
+            None => roots.push(message),
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|message| nest_replies(message, &mut replies))
+        .collect()
+}
+
+fn nest_replies(message: Message, replies: &mut HashMap<Box<str>, Vec<Message>>) -> ThreadMessage {
+    let children = replies.remove(&message.name).unwrap_or_default();
+    ThreadMessage {
+        replies: children
+            .into_iter()
+            .map(|reply| nest_replies(reply, replies))
+            .collect(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Thing;
+
+    const INBOX_SAMPLE_1: &str = include_str!("../test_data/inbox_unread.json");
+
+    #[test]
+    fn group_message_threads() {
+        let inbox: serde_json::Value = serde_json::from_str(INBOX_SAMPLE_1).unwrap();
+        let comment: Message =
+            serde_json::from_value(inbox["data"]["children"][0]["data"].clone()).unwrap();
+        let template = inbox["data"]["children"][1].clone();
+        let message = |name: &str, parent: Option<&str>, first: Option<&str>, created: f64| {
+            let mut message = template.clone();
+            message["data"]["name"] = name.into();
+            message["data"]["parent_id"] = parent.into();
+            message["data"]["first_message_name"] = first.into();
+            message["data"]["created_utc"] = created.into();
+            message
+        };
+
+        let mut first = message("t4_a", None, None, 100.0);
+        first["data"]["replies"] = serde_json::json!({
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "modhash": "",
+                "children": [
+                    message("t4_c", Some("t4_b"), Some("t4_a"), 300.0),
+                    message("t4_b", Some("t4_a"), Some("t4_a"), 200.0),
+                ],
+            },
+        });
+        let listing = serde_json::json!({
+            "kind": "Listing",
+            "data": {
+                "after": null,
+                "before": null,
+                "modhash": "",
+                "children": [
+                    message("t4_d", None, None, 250.0),
+                    first,
+                    message("t4_b", Some("t4_a"), Some("t4_a"), 200.0),
+                ],
+            },
+        });
+        let listing: Thing = serde_json::from_value(listing).unwrap();
+
+        let threads = listing.data.into_listing().unwrap().into_message_threads();
+        let names: Vec<_> = threads
+            .iter()
+            .map(|thread| &*thread.first_message_name)
+            .collect();
+        assert_eq!(names, ["t4_a", "t4_d"]);
+
+        let thread = &threads[0];
+        assert_eq!(thread.last_activity_utc(), 300.0);
+        assert_eq!(thread.messages.len(), 1);
+        assert_eq!(
+            &*thread.messages[0].replies[0].replies[0].message.name,
+            "t4_c"
+        );
+        let names: Vec<_> = thread.iter().map(|message| &*message.name).collect();
+        assert_eq!(names, ["t4_a", "t4_b", "t4_c"]);
+        assert!(thread.iter().all(|message| message.replies.is_none()));
+
+        assert!(MessageThread::from_messages([comment]).is_empty());
+    }
+}
@@ -1,14 +1,57 @@
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod archive;
+#[cfg(feature = "disk-cache")]
+pub mod cache;
+pub mod captcha;
 pub mod client;
+pub mod comments;
 pub mod error;
+pub mod export;
+pub mod inbox;
+pub mod light;
+pub mod markdown;
+pub mod media;
+#[cfg(feature = "opengraph")]
+pub mod opengraph;
+pub mod pagination;
+pub mod parse;
+#[cfg(feature = "pushshift")]
+pub mod pushshift;
+pub mod rate_limit;
+pub mod reddit_url;
+#[cfg(feature = "rss")]
+pub mod rss;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod stream;
+pub mod submit;
+#[cfg(feature = "test_util")]
+pub mod test_util;
 pub mod types;
+mod upload;
+pub mod user_agent;
+pub mod widgets;
 
 pub use crate::{
-    client::Client,
+    client::{
+        Client,
+        ClientBuilder,
+    },
     error::Error,
+    pagination::Paginator,
+    parse::ParseMode,
+    reddit_url::RedditUrl,
+    stream::{
+        ResumeToken,
+        StreamOptions,
+        TokenStore,
+    },
     types::{
         Link,
         Listing,
         PostHint,
         Thing,
     },
+    user_agent::UserAgent,
 };
@@ -0,0 +1,178 @@
+use crate::types::CompactStr;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+/// A child of a listing with a light representation of its data.
+#[derive(Debug, serde::Deserialize)]
+pub struct LightThing<T> {
+    /// The kind of the thing, like "t3"
+    pub kind: Box<str>,
+
+    /// Data
+    pub data: T,
+}
+
+/// A light representation of a [`Link`](crate::types::Link).
+///
+/// This only parses commonly used fields.
+/// Heavy fields that are rarely used are kept as raw json, and can be parsed on demand with [`parse_raw`].
+/// Unknown fields are ignored.
+#[derive(Debug, serde::Deserialize)]
+pub struct LightLink {
+    /// this link's identifier, e.g. "8xwlg"
+    pub id: CompactStr,
+
+    /// Fullname of link, e.g. "t3_8xwlg"
+    pub name: CompactStr,
+
+    /// the title of the link. may contain newlines for some reason
+    pub title: Box<str>,
+
+    /// the account name of the poster
+    pub author: CompactStr,
+
+    /// subreddit of thing excluding the /r/ prefix. "pics"
+    pub subreddit: CompactStr,
+
+    /// the net-score of the link
+    pub score: i64,
+
+    /// the number of comments that belong to this link. includes removed comments.
+    pub num_comments: u64,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_utc: f64,
+
+    /// relative URL of the permanent link for this link
+    pub permalink: Box<str>,
+
+    /// the link of this post. the permalink if this is a self-post. May be a relative Url.
+    pub url: Box<str>,
+
+    /// true if this link is a selfpost
+    pub is_self: bool,
+
+    /// the raw text. Empty if not present.
+    pub selftext: Box<str>,
+
+    /// true if the post is tagged as NSFW. False if otherwise
+    pub over_18: bool,
+
+    /// Previews of images in this post, if any
+    pub preview: Option<Box<RawValue>>,
+
+    /// Used for streaming video. Detailed information about the video and it's origins are placed here
+    pub media: Option<Box<RawValue>>,
+
+    /// Metadata about the images of a gallery or the media of a self post, if any
+    pub media_metadata: Option<Box<RawValue>>,
+
+    /// The awards given to this post
+    pub all_awardings: Option<Box<RawValue>>,
+}
+
+/// A light representation of a [`Comment`](crate::types::Comment).
+///
+/// This only parses commonly used fields.
+/// Heavy fields that are rarely used are kept as raw json, and can be parsed on demand with [`parse_raw`].
+/// Unknown fields are ignored.
+#[derive(Debug, serde::Deserialize)]
+pub struct LightComment {
+    /// this comment's identifier, e.g. "c3v7f8u"
+    pub id: CompactStr,
+
+    /// Fullname of comment, e.g. "t1_c3v7f8u"
+    pub name: CompactStr,
+
+    /// the account name of the poster
+    pub author: CompactStr,
+
+    /// the raw text
+    pub body: Box<str>,
+
+    /// subreddit of thing excluding the /r/ prefix. "pics"
+    pub subreddit: CompactStr,
+
+    /// the net-score of the comment
+    pub score: i64,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_utc: f64,
+
+    /// ID of the link this comment is in
+    pub link_id: CompactStr,
+
+    /// ID of the thing this comment is a reply to, either the link or a comment in it
+    pub parent_id: CompactStr,
+
+    /// Metadata about the media in this comment, if any
+    pub media_metadata: Option<Box<RawValue>>,
+
+    /// The awards given to this comment
+    pub all_awardings: Option<Box<RawValue>>,
+
+    /// A listing of replies to this comment, or an empty string if there are none
+    pub replies: Option<Box<RawValue>>,
+}
+
+/// Parse a raw json field, like [`LightLink::preview`].
+///
+/// Returns `None` if the field is missing.
+pub fn parse_raw<T>(raw: Option<&RawValue>) -> Option<Result<T, serde_json::Error>>
+where
+    T: DeserializeOwned,
+{
+    Some(serde_json::from_str(raw?.get()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseMode;
+
+    const SUBREDDIT_SAMPLE_1: &str = include_str!("../test_data/subreddit_dankmemes.json");
+    const COMMENT_SAMPLE_1: &str = include_str!("../test_data/comment_h966lq.json");
+
+    #[derive(serde::Deserialize)]
+    struct LightListing<T> {
+        data: LightListingData<T>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct LightListingData<T> {
+        children: Vec<LightThing<T>>,
+    }
+
+    #[test]
+    fn parse_light_links() {
+        for parse_mode in [ParseMode::Strict, ParseMode::Lenient] {
+            let listing: LightListing<LightLink> = parse_mode
+                .parse_slice(SUBREDDIT_SAMPLE_1.as_bytes())
+                .unwrap();
+            assert!(!listing.data.children.is_empty());
+
+            for child in listing.data.children {
+                assert_eq!(&*child.kind, "t3");
+                let awardings: Option<Result<Vec<serde_json::Value>, _>> =
+                    parse_raw(child.data.all_awardings.as_deref());
+                awardings.unwrap().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn parse_light_comments() {
+        let listings: Vec<serde_json::Value> = serde_json::from_str(COMMENT_SAMPLE_1).unwrap();
+        let listing: LightListing<serde_json::Value> =
+            serde_json::from_value(listings[1].clone()).unwrap();
+        let comments = listing
+            .data
+            .children
+            .into_iter()
+            .filter(|child| &*child.kind == "t1")
+            .map(|child| serde_json::from_value::<LightComment>(child.data))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(!comments.is_empty());
+    }
+}
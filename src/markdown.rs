@@ -0,0 +1,305 @@
+use crate::types::{
+    Comment,
+    Link,
+};
+
+impl Link {
+    /// Get the selftext of this post as plaintext.
+    ///
+    /// See [`to_plaintext`].
+    pub fn selftext_plaintext(&self) -> String {
+        to_plaintext(&self.selftext)
+    }
+}
+
+impl Comment {
+    /// Get the body of this comment as plaintext.
+    ///
+    /// See [`to_plaintext`].
+    pub fn body_plaintext(&self) -> String {
+        to_plaintext(&self.body)
+    }
+}
+
+/// Convert reddit markdown to plaintext.
+///
+/// Links are replaced by their text, and emphasis, strikethrough, superscript, spoiler, heading, quote, and list markers are removed.
+/// Code is kept as-is, horizontal rules are dropped, and html entities are unescaped.
+/// Line breaks are kept, but runs of blank lines are collapsed into one.
+pub fn to_plaintext(markdown: &str) -> String {
+    let markdown = unescape_entities(markdown);
+
+    let mut plaintext = String::with_capacity(markdown.len());
+    let mut in_code_block = false;
+    let mut last_line_blank = true;
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        let start = plaintext.len();
+        if in_code_block {
+            plaintext.push_str(line.trim_end());
+        } else {
+            let Some(line) = strip_block_markers(line) else {
+                continue;
+            };
+            push_inline(line, &mut plaintext);
+        }
+
+        let is_blank = plaintext[start..].trim().is_empty();
+        plaintext.truncate(if is_blank {
+            start
+        } else {
+            plaintext.trim_end().len()
+        });
+        if !(is_blank && last_line_blank) {
+            plaintext.push('\n');
+        }
+        last_line_blank = is_blank;
+    }
+
+    plaintext.truncate(plaintext.trim_end().len());
+    plaintext
+}
+
+/// Undo the html escaping reddit applies to markdown, and remove the zero-width spaces reddit inserts.
+fn unescape_entities(text: &str) -> String {
+    text.replace("&#x200B;", "")
+        .replace('\u{200B}', "")
+        .replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Remove the quote, heading, and list markers of a line.
+///
+/// Returns `None` if the line is only markup, like a horizontal rule or a table separator.
+fn strip_block_markers(line: &str) -> Option<&str> {
+    let mut line = line.trim();
+    while line.starts_with('>') && !line.starts_with(">!") {
+        line = line[1..].trim_start();
+    }
+
+    let is_rule = ['*', '-', '_'].iter().any(|&marker| {
+        line.chars()
+            .filter(|c| !c.is_whitespace())
+            .all(|c| c == marker)
+    }) && line.chars().filter(|c| !c.is_whitespace()).count() >= 3;
+    let is_table_separator = line.contains('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '));
+    if is_rule || is_table_separator {
+        return None;
+    }
+
+    if let Some(heading) = line.strip_prefix('#') {
+        let heading = heading.trim_start_matches('#');
+        if heading.is_empty() || heading.starts_with(char::is_whitespace) {
+            return Some(heading.trim().trim_end_matches('#').trim_end());
+        }
+    }
+
+    for bullet in ["* ", "- ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some(item.trim_start());
+        }
+    }
+
+    Some(line)
+}
+
+/// Push the text of a line with its inline markup removed.
+fn push_inline(text: &str, plaintext: &mut String) {
+    // Table rows become their cells separated by spaces.
+    if text.starts_with('|') {
+        let cells = text
+            .trim_matches('|')
+            .split('|')
+            .map(str::trim)
+            .filter(|cell| !cell.is_empty());
+        for (i, cell) in cells.enumerate() {
+            if i > 0 {
+                plaintext.push(' ');
+            }
+            push_inline(cell, plaintext);
+        }
+        return;
+    }
+
+    let mut in_spoiler = false;
+    let mut rest = text;
+    let mut previous = None;
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        let next = after.chars().next();
+        match c {
+            '\\' if next.is_some_and(|next| next.is_ascii_punctuation()) => {
+                plaintext.push(next.unwrap_or_default());
+                rest = &after[1..];
+                previous = next;
+                continue;
+            }
+            '`' => {
+                let len = rest.len() - rest.trim_start_matches('`').len();
+                let fence = &rest[..len];
+                if let Some(end) = rest[len..].find(fence) {
+                    plaintext.push_str(rest[len..len + end].trim());
+                    rest = &rest[len + end + len..];
+                } else {
+                    plaintext.push_str(fence);
+                    rest = &rest[len..];
+                }
+                previous = Some('`');
+                continue;
+            }
+            '!' if next == Some('[') => {
+                rest = after;
+                continue;
+            }
+            '[' => {
+                if let Some((link_text, url, remaining)) = parse_link(rest) {
+                    if link_text.trim().is_empty() {
+                        plaintext.push_str(url);
+                    } else {
+                        push_inline(link_text, plaintext);
+                    }
+                    rest = remaining;
+                    previous = Some(')');
+                    continue;
+                }
+            }
+            '>' if next == Some('!') => {
+                in_spoiler = true;
+                rest = &after[1..];
+                continue;
+            }
+            '!' if in_spoiler && next == Some('<') => {
+                in_spoiler = false;
+                rest = &after[1..];
+                continue;
+            }
+            '^' if next == Some('(') => {
+                if let Some(end) = find_closing(&after[1..], '(', ')') {
+                    push_inline(&after[1..end + 1], plaintext);
+                    rest = &after[end + 2..];
+                    continue;
+                }
+            }
+            '^' if next.is_some_and(|next| !next.is_whitespace()) => {
+                rest = after;
+                continue;
+            }
+            '*' | '_' | '~' => {
+                let len = rest.len() - rest.trim_start_matches(c).len();
+                let next = rest[len..].chars().next();
+                let opens = next.is_some_and(|next| !next.is_whitespace());
+                let closes = previous.is_some_and(|previous| !previous.is_whitespace());
+                let is_intraword = c == '_'
+                    && previous.is_some_and(char::is_alphanumeric)
+                    && next.is_some_and(char::is_alphanumeric);
+                let is_marker = (opens || closes) && !is_intraword && (c != '~' || len >= 2);
+                if !is_marker {
+                    plaintext.push_str(&rest[..len]);
+                    previous = Some(c);
+                }
+                rest = &rest[len..];
+                continue;
+            }
+            _ => {}
+        }
+
+        plaintext.push(c);
+        rest = after;
+        previous = Some(c);
+    }
+}
+
+/// Parse a link like `[text](url)` at the start of some text.
+///
+/// Returns the text, the url, and the rest of the text.
+fn parse_link(text: &str) -> Option<(&str, &str, &str)> {
+    let after_bracket = text.strip_prefix('[')?;
+    let text_end = find_closing(after_bracket, '[', ']')?;
+    let link_text = &after_bracket[..text_end];
+
+    let after_paren = after_bracket[text_end + 1..].strip_prefix('(')?;
+    let url_end = find_closing(after_paren, '(', ')')?;
+    let url = after_paren[..url_end]
+        .split_whitespace()
+        .next()
+        .unwrap_or("");
+
+    Some((link_text, url, &after_paren[url_end + 1..]))
+}
+
+/// Find the index of the closing delimiter of some text that starts after an opening delimiter.
+fn find_closing(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            if depth == 0 {
+                return Some(i);
+            }
+            depth -= 1;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn markdown_to_plaintext() {
+        let markdown = "# A *Heading* #
+
+&gt; quoted **bold** text
+&gt;&gt; nested ~~struck~~ quote
+
+* a [link](https://example.com \"title\") and [https://example.com](https://example.com)
+- snake_case_words and __underlined__ _words_
+1. 2 * 3 = 6, \\*escaped\\* and ^(super script) ^word
+
+&#x200B;
+
+---
+
+|Name|Value|
+|:-|-:|
+|a|`b*c`|
+
+```
+let x = *y;
+```
+&gt;!spoiler!&lt; &amp; done  ";
+
+        assert_eq!(
+            to_plaintext(markdown),
+            "A Heading
+
+quoted bold text
+nested struck quote
+
+a link and https://example.com
+snake_case_words and underlined words
+1. 2 * 3 = 6, *escaped* and super script word
+
+Name Value
+a b*c
+
+let x = *y;
+spoiler & done"
+        );
+        assert_eq!(to_plaintext(""), "");
+    }
+}
@@ -0,0 +1,362 @@
+use crate::types::{
+    Comment,
+    Link,
+    MediaMetadata,
+    PostHint,
+    RedditVideo,
+};
+use serde::Deserialize;
+
+/// File extensions of urls that are images.
+const IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp"];
+
+/// The names of the audio tracks of v.redd.it videos, newest naming scheme first.
+const AUDIO_TRACK_NAMES: &[&str] = &[
+    "DASH_AUDIO_128.mp4",
+    "DASH_AUDIO_64.mp4",
+    "DASH_audio.mp4",
+    "audio",
+];
+
+/// Undo the html escaping reddit applies to urls when raw_json is not used.
+pub(crate) fn unescape_url(url: &str) -> String {
+    url.replace("&amp;", "&")
+}
+
+/// Returns `true` if the path of a url ends with an image extension.
+fn has_image_extension(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit_once('.')
+        .is_some_and(|(_, extension)| IMAGE_EXTENSIONS.contains(&&*extension.to_ascii_lowercase()))
+}
+
+impl Link {
+    /// Get the url of the best full size image of this post, if it has one.
+    ///
+    /// This is the linked image, the first item of a gallery, or the preview image, in that order.
+    pub fn image_url(&self) -> Option<String> {
+        if self.post_hint == Some(PostHint::Image) || has_image_extension(&self.url) {
+            return Some(unescape_url(&self.url));
+        }
+
+        if let Some(url) = self.gallery_image_urls().into_iter().next() {
+            return Some(url);
+        }
+
+        let image = self.preview.as_ref()?.images.first()?;
+        Some(unescape_url(&image.source.url))
+    }
+
+    /// Get the url of a small thumbnail image of this post, if it has one.
+    pub fn thumbnail_url(&self) -> Option<String> {
+        // This can also be "self", "default", "nsfw", or "spoiler".
+        if self.thumbnail.starts_with("https://") || self.thumbnail.starts_with("http://") {
+            return Some(unescape_url(&self.thumbnail));
+        }
+
+        let image = self.preview.as_ref()?.images.first()?;
+        let source = image.resolutions.first().unwrap_or(&image.source);
+        Some(unescape_url(&source.url))
+    }
+
+    /// Get the v.redd.it video of this post, if it has one.
+    ///
+    /// This includes videos of crossposted posts and video previews of gifs.
+    pub fn reddit_video(&self) -> Option<RedditVideo> {
+        let media = std::iter::once(self)
+            .chain(self.crosspost_parent_list.iter().flatten())
+            .find_map(|link| link.media.get("reddit_video"));
+        if let Some(Ok(video)) = media.map(RedditVideo::deserialize) {
+            return Some(video);
+        }
+
+        self.preview.as_ref()?.reddit_video_preview.clone()
+    }
+
+    /// Get the video reddit transcoded from the external gif or video of this post, like an imgur gifv.
+    ///
+    /// Unlike [`Link::reddit_video`], this never looks at `media`, so it is `None` for videos uploaded to reddit.
+    /// These transcodes have no audio.
+    pub fn reddit_video_preview(&self) -> Option<&RedditVideo> {
+        self.preview.as_ref()?.reddit_video_preview.as_ref()
+    }
+
+    /// Get the urls of the full size images of the gallery of this post, in order.
+    ///
+    /// Items that failed processing are skipped.
+    /// This is empty if the post is not a gallery.
+    pub fn gallery_image_urls(&self) -> Vec<String> {
+        self.gallery_images()
+            .into_iter()
+            .map(|image| image.url)
+            .collect()
+    }
+
+    /// Get the full size images of the gallery of this post, in order.
+    ///
+    /// Items that failed processing are skipped.
+    /// This is empty if the post is not a gallery.
+    pub fn gallery_images(&self) -> Vec<GalleryImage> {
+        let (Some(gallery_data), Some(media_metadata)) = (&self.gallery_data, &self.media_metadata)
+        else {
+            return Vec::new();
+        };
+
+        gallery_data
+            .items
+            .iter()
+            .filter_map(|item| full_size_image(&item.media_id, media_metadata.get(&item.media_id)?))
+            .collect()
+    }
+}
+
+impl Comment {
+    /// Get the full size images and gifs embedded in the body of this comment,
+    /// in the order they appear in the body.
+    ///
+    /// Media that failed processing is skipped.
+    pub fn inline_media(&self) -> Vec<GalleryImage> {
+        let Some(media_metadata) = &self.media_metadata else {
+            return Vec::new();
+        };
+
+        let mut media: Vec<(usize, GalleryImage)> = media_metadata
+            .iter()
+            .filter_map(|(media_id, metadata)| {
+                let position = self.body.find(&**media_id).unwrap_or(usize::MAX);
+                Some((position, full_size_image(media_id, metadata)?))
+            })
+            .collect();
+        media.sort_by(|(a_position, a), (b_position, b)| {
+            a_position
+                .cmp(b_position)
+                .then_with(|| a.media_id.cmp(&b.media_id))
+        });
+
+        media.into_iter().map(|(_, image)| image).collect()
+    }
+}
+
+/// Get the full size image of the media_metadata entry of a post or comment.
+fn full_size_image(media_id: &str, metadata: &MediaMetadata) -> Option<GalleryImage> {
+    let source = metadata.source.as_ref()?;
+    let (url, extension) = match (source.url.as_deref(), source.gif.as_deref()) {
+        (Some(url), _) => {
+            let extension = metadata
+                .mime_type
+                .as_deref()
+                .and_then(extension_from_mime_type)
+                .unwrap_or("jpg");
+            (url, extension)
+        }
+        (None, Some(gif)) => (gif, "gif"),
+        (None, None) => return None,
+    };
+
+    Some(GalleryImage {
+        media_id: media_id.into(),
+        url: unescape_url(url),
+        extension,
+    })
+}
+
+/// A full size image of a gallery, or an image embedded in a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GalleryImage {
+    /// the id of the media, a key of [`Link::media_metadata`] or [`Comment::media_metadata`]
+    pub media_id: Box<str>,
+
+    /// the unescaped url of the image
+    pub url: String,
+
+    /// the file extension of the image, without the dot
+    pub extension: &'static str,
+}
+
+/// Get the file extension of an image mime type, like "image/jpg".
+pub(crate) fn extension_from_mime_type(mime_type: &str) -> Option<&'static str> {
+    match mime_type.strip_prefix("image/")? {
+        "jpg" | "jpeg" => Some("jpg"),
+        "png" => Some("png"),
+        "gif" => Some("gif"),
+        "webp" => Some("webp"),
+        _ => None,
+    }
+}
+
+impl RedditVideo {
+    /// Get the candidate urls of the separate audio track of this video.
+    ///
+    /// Reddit has changed how audio tracks are named over time, and does not say which one a video uses,
+    /// so each should be tried in order until one exists.
+    /// This is empty for gifs, which have no audio.
+    pub fn audio_urls(&self) -> Vec<String> {
+        if self.is_gif {
+            return Vec::new();
+        }
+
+        let fallback_url = unescape_url(&self.fallback_url);
+        let Some((base_url, _)) = fallback_url.rsplit_once('/') else {
+            return Vec::new();
+        };
+
+        AUDIO_TRACK_NAMES
+            .iter()
+            .map(|name| format!("{base_url}/{name}"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Thing;
+
+    const SUBREDDIT_SAMPLE_1: &str = include_str!("../test_data/subreddit_dankmemes.json");
+    const SUBREDDIT_SAMPLE_2: &str = include_str!("../test_data/subreddit_cromch.json");
+    const SUBREDDIT_SAMPLE_3: &str = include_str!("../test_data/subreddit_cuddleroll.json");
+    const COMMENT_SAMPLE_1: &str = include_str!("../test_data/comment_h966lq.json");
+
+    fn links(text: &str) -> Vec<Link> {
+        let thing: Thing = serde_json::from_str(text).unwrap();
+        thing
+            .data
+            .into_listing()
+            .unwrap()
+            .children
+            .into_iter()
+            .filter_map(|child| child.data.into_link().map(|link| *link))
+            .collect()
+    }
+
+    #[test]
+    fn image_urls() {
+        let mut links = links(SUBREDDIT_SAMPLE_1);
+        links.extend(self::links(SUBREDDIT_SAMPLE_2));
+
+        let images = links
+            .iter()
+            .filter(|link| link.post_hint == Some(PostHint::Image))
+            .collect::<Vec<_>>();
+        assert!(!images.is_empty());
+        for link in images {
+            let url = link.image_url().unwrap();
+            assert!(!url.contains("&amp;"));
+            assert!(link.thumbnail_url().is_some());
+        }
+
+        let galleries = links
+            .iter()
+            .filter(|link| link.gallery_data.is_some())
+            .collect::<Vec<_>>();
+        assert!(!galleries.is_empty());
+        for link in galleries {
+            let urls = link.gallery_image_urls();
+            assert!(!urls.is_empty());
+            for image in link.gallery_images() {
+                assert!(IMAGE_EXTENSIONS.contains(&image.extension));
+            }
+            assert_eq!(link.image_url().as_ref(), urls.first());
+        }
+    }
+
+    #[test]
+    fn reddit_video() {
+        let links = links(SUBREDDIT_SAMPLE_3);
+        let videos = links
+            .iter()
+            .filter(|link| link.post_hint == Some(PostHint::HostedVideo))
+            .collect::<Vec<_>>();
+        assert!(!videos.is_empty());
+        for link in videos {
+            let video = link.reddit_video().unwrap();
+            assert!(video.fallback_url.starts_with("https://v.redd.it/"));
+
+            let audio_urls = video.audio_urls();
+            if video.is_gif {
+                assert!(audio_urls.is_empty());
+                continue;
+            }
+            assert_eq!(audio_urls.len(), AUDIO_TRACK_NAMES.len());
+            assert!(audio_urls[0].ends_with("/DASH_AUDIO_128.mp4"));
+            assert!(!audio_urls[0].contains("source=fallback"));
+        }
+    }
+
+    #[test]
+    fn reddit_video_preview() {
+        let links = links(SUBREDDIT_SAMPLE_1);
+        let previews: Vec<_> = links
+            .iter()
+            .filter_map(|link| Some((link, link.reddit_video_preview()?)))
+            .collect();
+        assert_eq!(previews.len(), 7);
+        for (link, preview) in previews {
+            assert!(link.media.is_null());
+            assert!(preview.is_gif);
+            assert!(preview.fallback_url.starts_with("https://v.redd.it/"));
+            assert!(preview.audio_urls().is_empty());
+        }
+    }
+
+    #[test]
+    fn comment_inline_media() {
+        let mut things: serde_json::Value = serde_json::from_str(COMMENT_SAMPLE_1).unwrap();
+        let comment = &mut things[1]["data"]["children"][0]["data"];
+        comment["body"] = "look ![gif](giphy|hG6ZyQGcb9RLi) and ![img](k3c9xq1abcd1)".into();
+        comment["media_metadata"] = serde_json::json!({
+            "k3c9xq1abcd1": {
+                "status": "valid",
+                "e": "Image",
+                "m": "image/png",
+                "p": [{"y": 108, "x": 108, "u": "https://preview.redd.it/k3c9xq1abcd1.png?width=108&amp;s=1"}],
+                "s": {"y": 512, "x": 512, "u": "https://preview.redd.it/k3c9xq1abcd1.png?width=512&amp;s=2"},
+                "id": "k3c9xq1abcd1"
+            },
+            "giphy|hG6ZyQGcb9RLi": {
+                "status": "valid",
+                "e": "AnimatedImage",
+                "m": "image/gif",
+                "s": {"y": 200, "x": 200, "gif": "https://giphy.com/media/hG6ZyQGcb9RLi/giphy.gif"},
+                "id": "giphy|hG6ZyQGcb9RLi"
+            },
+            "unprocessed1": {"status": "unprocessed", "id": "unprocessed1"}
+        });
+        let comment: Comment = serde_json::from_value(comment.take()).unwrap();
+
+        assert_eq!(
+            comment.inline_media(),
+            [
+                GalleryImage {
+                    media_id: "giphy|hG6ZyQGcb9RLi".into(),
+                    url: "https://giphy.com/media/hG6ZyQGcb9RLi/giphy.gif".into(),
+                    extension: "gif",
+                },
+                GalleryImage {
+                    media_id: "k3c9xq1abcd1".into(),
+                    url: "https://preview.redd.it/k3c9xq1abcd1.png?width=512&s=2".into(),
+                    extension: "png",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn mime_type_extension() {
+        assert_eq!(extension_from_mime_type("image/jpeg"), Some("jpg"));
+        assert_eq!(extension_from_mime_type("image/png"), Some("png"));
+        assert_eq!(extension_from_mime_type("video/mp4"), None);
+    }
+
+    #[test]
+    fn image_extension() {
+        assert!(has_image_extension("https://i.redd.it/example.JPG"));
+        assert!(has_image_extension(
+            "https://preview.redd.it/example.png?auto=webp"
+        ));
+        assert!(!has_image_extension("https://v.redd.it/example"));
+        assert!(!has_image_extension(
+            "https://www.reddit.com/r/aww/comments/10a1b2c/my_cat.found_the_sun/"
+        ));
+    }
+}
@@ -0,0 +1,189 @@
+/// OpenGraph and Twitter card metadata of a web page.
+///
+/// OpenGraph tags are preferred, and Twitter card tags and standard html tags are used for missing values.
+/// See https://ogp.me/
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OpenGraph {
+    /// the title of the page
+    pub title: Option<Box<str>>,
+
+    /// a short description of the page
+    pub description: Option<Box<str>>,
+
+    /// the url of an image that represents the page. May be a relative url
+    pub image: Option<Box<str>>,
+
+    /// the canonical url of the page
+    pub url: Option<Box<str>>,
+
+    /// the name of the site the page is on, like "YouTube"
+    pub site_name: Option<Box<str>>,
+}
+
+impl OpenGraph {
+    /// Returns `true` if no metadata was found.
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.image.is_none()
+            && self.url.is_none()
+            && self.site_name.is_none()
+    }
+}
+
+/// Extract OpenGraph and Twitter card metadata from the html of a page.
+///
+/// This is a lenient scan of `<meta>` and `<title>` tags, not a full html parser.
+pub fn parse_opengraph(html: &str) -> OpenGraph {
+    let mut og = OpenGraph::default();
+    let mut twitter = OpenGraph::default();
+    let mut fallback = OpenGraph::default();
+
+    let lower = html.to_ascii_lowercase();
+    let mut rest = 0;
+    while let Some(start) = lower[rest..].find("<meta").map(|i| i + rest) {
+        let Some(end) = html[start..].find('>').map(|i| i + start) else {
+            break;
+        };
+        rest = end;
+
+        let attributes = parse_attributes(&html[start + "<meta".len()..end]);
+        let Some(content) = find_attribute(&attributes, "content") else {
+            continue;
+        };
+        let Some(key) =
+            find_attribute(&attributes, "property").or_else(|| find_attribute(&attributes, "name"))
+        else {
+            continue;
+        };
+
+        let content = Some(Box::from(unescape_html(content.trim())));
+        match key.to_ascii_lowercase().as_str() {
+            "og:title" => og.title = og.title.or(content),
+            "og:description" => og.description = og.description.or(content),
+            "og:image" | "og:image:url" | "og:image:secure_url" => og.image = og.image.or(content),
+            "og:url" => og.url = og.url.or(content),
+            "og:site_name" => og.site_name = og.site_name.or(content),
+            "twitter:title" => twitter.title = twitter.title.or(content),
+            "twitter:description" => twitter.description = twitter.description.or(content),
+            "twitter:image" | "twitter:image:src" => twitter.image = twitter.image.or(content),
+            "twitter:site" => twitter.site_name = twitter.site_name.or(content),
+            "description" => fallback.description = fallback.description.or(content),
+            _ => {}
+        }
+    }
+
+    if let Some(start) = lower.find("<title") {
+        let title = html[start..]
+            .find('>')
+            .map(|i| i + start + 1)
+            .and_then(|start| Some((start, lower[start..].find("</title")? + start)));
+        if let Some((start, end)) = title {
+            fallback.title = Some(unescape_html(html[start..end].trim()).into());
+        }
+    }
+
+    OpenGraph {
+        title: og.title.or(twitter.title).or(fallback.title),
+        description: og
+            .description
+            .or(twitter.description)
+            .or(fallback.description),
+        image: og.image.or(twitter.image),
+        url: og.url,
+        site_name: og.site_name.or(twitter.site_name),
+    }
+}
+
+/// Parse the attributes of a tag, as (name, value) pairs.
+fn parse_attributes(text: &str) -> Vec<(&str, &str)> {
+    let mut attributes = Vec::new();
+    let mut rest = text.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace() || c == '/')
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let mut value = "";
+        if let Some(after_equals) = rest.strip_prefix('=') {
+            let after_equals = after_equals.trim_start();
+            let (parsed, remaining) = match after_equals.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let after_quote = &after_equals[1..];
+                    let end = after_quote.find(quote).unwrap_or(after_quote.len());
+                    let remaining = after_quote.get(end + 1..).unwrap_or("");
+                    (&after_quote[..end], remaining)
+                }
+                _ => {
+                    let end = after_equals
+                        .find(char::is_whitespace)
+                        .unwrap_or(after_equals.len());
+                    (&after_equals[..end], &after_equals[end..])
+                }
+            };
+            value = parsed;
+            rest = remaining;
+        }
+
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+    }
+
+    attributes
+}
+
+/// Find the value of an attribute by its case-insensitive name.
+fn find_attribute<'a>(attributes: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+}
+
+/// Undo the html escaping of common entities.
+fn unescape_html(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_opengraph_tags() {
+        let html = r#"<!DOCTYPE html>
+<html>
+<head>
+    <title>Fallback &amp; Title</title>
+    <meta name="description" content="A fallback description">
+    <META property="og:title" content="The &quot;Real&quot; Title" />
+    <meta content='https://example.com/image.png' property='og:image'>
+    <meta name="twitter:image" content="https://example.com/twitter.png">
+    <meta name="twitter:site" content="@example">
+    <meta property="og:url" content=https://example.com/article>
+</head>
+</html>"#;
+
+        let og = parse_opengraph(html);
+        assert_eq!(og.title.as_deref(), Some("The \"Real\" Title"));
+        assert_eq!(og.description.as_deref(), Some("A fallback description"));
+        assert_eq!(og.image.as_deref(), Some("https://example.com/image.png"));
+        assert_eq!(og.url.as_deref(), Some("https://example.com/article"));
+        assert_eq!(og.site_name.as_deref(), Some("@example"));
+
+        let og = parse_opengraph("<html><head><title>Only a title</title></head></html>");
+        assert_eq!(og.title.as_deref(), Some("Only a title"));
+        assert!(og.image.is_none());
+        assert!(parse_opengraph("").is_empty());
+    }
+}
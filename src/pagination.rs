@@ -0,0 +1,443 @@
+use crate::{
+    client::Client,
+    error::Error,
+    types::{
+        params::TimeRange,
+        Comment,
+        Link,
+        Listing,
+        Thing,
+    },
+};
+use futures_util::{
+    stream::TryStreamExt,
+    Stream,
+};
+use std::marker::PhantomData;
+
+/// The maximum number of items reddit will return in one page.
+pub const MAX_LIMIT: usize = 100;
+
+/// The number of items reddit returns in one page by default.
+pub const DEFAULT_LIMIT: usize = 25;
+
+/// An item that can be extracted from the children of a [`Listing`].
+pub trait ListingItem: Sized {
+    /// Try to convert a [`Thing`] into this item.
+    ///
+    /// Children that return `None` are skipped.
+    fn from_thing(thing: Thing) -> Option<Self>;
+}
+
+impl ListingItem for Thing {
+    fn from_thing(thing: Thing) -> Option<Self> {
+        Some(thing)
+    }
+}
+
+impl ListingItem for Box<Link> {
+    fn from_thing(thing: Thing) -> Option<Self> {
+        thing.data.into_link()
+    }
+}
+
+impl ListingItem for Link {
+    fn from_thing(thing: Thing) -> Option<Self> {
+        thing.data.into_link().map(|link| *link)
+    }
+}
+
+impl ListingItem for Box<Comment> {
+    fn from_thing(thing: Thing) -> Option<Self> {
+        thing.data.into_comment()
+    }
+}
+
+impl ListingItem for Comment {
+    fn from_thing(thing: Thing) -> Option<Self> {
+        thing.data.into_comment().map(|comment| *comment)
+    }
+}
+
+/// The direction a [`Paginator`] moves in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    /// Use the `after` cursor to get older items.
+    Forward,
+
+    /// Use the `before` cursor to get newer items.
+    Backward,
+}
+
+/// A paginator over a reddit listing endpoint.
+///
+/// See https://www.reddit.com/dev/api#listings
+pub struct Paginator<T> {
+    client: Client,
+    path: Box<str>,
+    limit: usize,
+    direction: Direction,
+    cursor: Option<Box<str>>,
+
+    /// The number of items already fetched, sent as the `count` parameter.
+    count: usize,
+
+    /// Extra query parameters of the endpoint, like the query of a search.
+    params: Vec<(&'static str, String)>,
+    include_over_18: bool,
+    sr_detail: bool,
+
+    done: bool,
+    prefetch: bool,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> Paginator<T>
+where
+    T: ListingItem,
+{
+    /// Create a new [`Paginator`] for a listing endpoint, where path is the path of the endpoint without an extension.
+    ///
+    /// For example, "/r/aww/new" or "/user/spez/submitted".
+    pub fn new(client: Client, path: &str) -> Self {
+        Self {
+            client,
+            path: path.into(),
+            limit: DEFAULT_LIMIT,
+            direction: Direction::Forward,
+            cursor: None,
+            count: 0,
+            params: Vec::new(),
+            include_over_18: false,
+            sr_detail: false,
+            done: false,
+            prefetch: false,
+            _item: PhantomData,
+        }
+    }
+
+    /// Set the number of items to request per page.
+    ///
+    /// This is capped at [`MAX_LIMIT`].
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit.min(MAX_LIMIT);
+        self
+    }
+
+    /// Start after the item with the given fullname, moving towards older items.
+    pub fn after(mut self, fullname: &str) -> Self {
+        self.direction = Direction::Forward;
+        self.cursor = Some(fullname.into());
+        self
+    }
+
+    /// Start before the item with the given fullname, moving towards newer items.
+    pub fn before(mut self, fullname: &str) -> Self {
+        self.direction = Direction::Backward;
+        self.cursor = Some(fullname.into());
+        self
+    }
+
+    /// Add an extra query parameter of the endpoint.
+    pub(crate) fn param(mut self, key: &'static str, value: &str) -> Self {
+        self.params.push((key, value.into()));
+        self
+    }
+
+    /// Set the time range of a top or controversial listing, sent as `t`.
+    ///
+    /// Reddit uses [`TimeRange::Day`] if this is not set.
+    pub fn time_range(self, time_range: TimeRange) -> Self {
+        self.param("t", time_range.as_str())
+    }
+
+    /// Set whether NSFW items are included, by sending `include_over_18=on`.
+    ///
+    /// Some endpoints, like search, filter out NSFW items without it, even for users that allow them.
+    /// This defaults to `false`, which leaves filtering up to reddit.
+    pub fn include_over_18(mut self, include_over_18: bool) -> Self {
+        self.include_over_18 = include_over_18;
+        self
+    }
+
+    /// Set whether links include a summary of their subreddit, by sending `sr_detail=1`.
+    ///
+    /// See [`Link::sr_detail`].
+    /// This defaults to `false`.
+    pub fn sr_detail(mut self, sr_detail: bool) -> Self {
+        self.sr_detail = sr_detail;
+        self
+    }
+
+    /// Set whether streams fetch the next page in the background while the current page is consumed.
+    ///
+    /// This hides request latency for bulk jobs, but requires a tokio runtime.
+    /// This defaults to `false`.
+    pub fn prefetch(mut self, prefetch: bool) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Get the fullname of the cursor that will be used for the next page.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// Get the number of items fetched so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if there are no more pages.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Get the next page of raw listing data.
+    ///
+    /// Returns `None` if there are no more pages.
+    pub async fn next_listing(&mut self) -> Result<Option<Listing>, Error> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let listing = self.client.get_listing(&self.path, &self.query()).await?;
+
+        self.cursor = match self.direction {
+            Direction::Forward => listing.after.clone(),
+            Direction::Backward => listing.before.clone(),
+        };
+        self.count += listing.children.len();
+        if self.cursor.is_none() || listing.children.is_empty() {
+            self.done = true;
+        }
+
+        Ok(Some(listing))
+    }
+
+    /// Fetch up to max_pages pages and merge them into one listing, see [`Listing::merge`].
+    ///
+    /// Returns `None` if there are no more pages.
+    /// The paginator can keep going from where the merged listing ends.
+    pub async fn collect_pages(&mut self, max_pages: usize) -> Result<Option<Listing>, Error> {
+        let mut merged: Option<Listing> = None;
+        for _ in 0..max_pages {
+            let Some(listing) = self.next_listing().await? else {
+                break;
+            };
+            match merged.as_mut() {
+                Some(merged) => merged.merge(listing),
+                None => merged = Some(listing),
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Get the query parameters for the next page.
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = vec![("limit", self.limit.to_string())];
+        query.extend(self.params.iter().cloned());
+        if self.include_over_18 {
+            query.push(("include_over_18", "on".to_string()));
+        }
+        if self.sr_detail {
+            query.push(("sr_detail", "1".to_string()));
+        }
+        if let Some(cursor) = self.cursor.as_deref() {
+            let key = match self.direction {
+                Direction::Forward => "after",
+                Direction::Backward => "before",
+            };
+            query.push((key, cursor.to_string()));
+            query.push(("count", self.count.to_string()));
+        }
+
+        query
+    }
+
+    /// Get the next page of items.
+    ///
+    /// Returns `None` if there are no more pages.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<T>>, Error> {
+        let listing = match self.next_listing().await? {
+            Some(listing) => listing,
+            None => return Ok(None),
+        };
+
+        Ok(Some(
+            listing
+                .children
+                .into_iter()
+                .filter_map(T::from_thing)
+                .collect(),
+        ))
+    }
+
+    /// Turn this into a stream of items, fetching pages as needed.
+    ///
+    /// See [`Paginator::prefetch`] to fetch pages ahead of the consumer.
+    pub fn into_stream(self) -> impl Stream<Item = Result<T, Error>>
+    where
+        T: 'static,
+    {
+        futures_util::stream::try_unfold(PageFetch::Ready(self), |fetch| async move {
+            let (mut paginator, listing) = match fetch {
+                PageFetch::Ready(mut paginator) => {
+                    let listing = paginator.next_listing().await;
+                    (paginator, listing)
+                }
+                PageFetch::Pending(handle) => match handle.await {
+                    Ok(fetched) => fetched,
+                    Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+                    // The runtime is shutting down, so the paginator is gone.
+                    Err(_) => return Err(Error::Cancelled),
+                },
+            };
+            let Some(listing) = listing? else {
+                return Ok(None);
+            };
+
+            let fetch = if paginator.prefetch && !paginator.done {
+                PageFetch::Pending(tokio::spawn(async move {
+                    let listing = paginator.next_listing().await;
+                    (paginator, listing)
+                }))
+            } else {
+                PageFetch::Ready(paginator)
+            };
+
+            let page = listing
+                .children
+                .into_iter()
+                .filter_map(T::from_thing)
+                .map(Ok::<_, Error>);
+            Ok::<_, Error>(Some((futures_util::stream::iter(page), fetch)))
+        })
+        .try_flatten()
+    }
+}
+
+/// A paginator and the page it fetched.
+type FetchedPage<T> = (Paginator<T>, Result<Option<Listing>, Error>);
+
+/// The state of the next page of a paginator stream.
+enum PageFetch<T> {
+    /// The next page has not been requested.
+    Ready(Paginator<T>),
+
+    /// The next page is being fetched in the background.
+    Pending(tokio::task::JoinHandle<FetchedPage<T>>),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[test]
+    fn limit_is_capped() {
+        let paginator = Paginator::<Thing>::new(Client::new(), "/r/aww").limit(500);
+        assert_eq!(paginator.limit, MAX_LIMIT);
+    }
+
+    #[test]
+    fn query_tracks_count() {
+        let mut paginator = Paginator::<Thing>::new(Client::new(), "/r/aww").limit(10);
+        assert_eq!(paginator.query(), [("limit", "10".to_string())]);
+
+        paginator.cursor = Some("t3_15bfi0".into());
+        paginator.count = 20;
+        assert_eq!(
+            paginator.query(),
+            [
+                ("limit", "10".to_string()),
+                ("after", "t3_15bfi0".to_string()),
+                ("count", "20".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_params() {
+        let paginator = Client::new()
+            .subreddit_search_paginator("aww", "cat")
+            .limit(10)
+            .include_over_18(true)
+            .sr_detail(true);
+        assert_eq!(
+            paginator.query(),
+            [
+                ("limit", "10".to_string()),
+                ("q", "cat".to_string()),
+                ("restrict_sr", "on".to_string()),
+                ("include_over_18", "on".to_string()),
+                ("sr_detail", "1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn popular_geo_filter() {
+        let paginator = Client::new().popular_paginator(Some("gb"));
+        assert_eq!(paginator.query()[1], ("g", "GB".to_string()));
+
+        let paginator = Client::new().popular_paginator(None);
+        assert_eq!(paginator.query(), [("limit", DEFAULT_LIMIT.to_string())]);
+    }
+
+    #[test]
+    fn saved_category() {
+        let paginator = Client::new().saved_paginator("spez", Some("recipes"));
+        assert_eq!(&*paginator.path, "/user/spez/saved");
+        assert_eq!(paginator.query()[1], ("category", "recipes".to_string()));
+    }
+
+    #[test]
+    fn sorted_time_range() {
+        let paginator = Client::new()
+            .subreddit_sorted_paginator("aww", "top".parse().unwrap())
+            .time_range("week".parse().unwrap());
+        assert_eq!(&*paginator.path, "/r/aww/top");
+        assert_eq!(paginator.query()[1], ("t", "week".to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn paginate_subreddit() {
+        let client = Client::new();
+        let mut paginator = client.subreddit_paginator("aww").limit(10);
+
+        let first = paginator.next_page().await.unwrap().unwrap();
+        let second = paginator.next_page().await.unwrap().unwrap();
+        assert!(!first.is_empty());
+        assert!(!second.is_empty());
+        assert_ne!(first[0].name, second[0].name);
+        assert_eq!(paginator.count(), first.len() + second.len());
+
+        let links: Vec<_> = client
+            .subreddit_paginator("aww")
+            .limit(10)
+            .into_stream()
+            .take(25)
+            .collect()
+            .await;
+        assert_eq!(links.len(), 25);
+        for link in links {
+            link.unwrap();
+        }
+
+        let links: Vec<_> = client
+            .subreddit_paginator("aww")
+            .limit(10)
+            .prefetch(true)
+            .into_stream()
+            .take(25)
+            .collect()
+            .await;
+        assert_eq!(links.len(), 25);
+        for link in links {
+            link.unwrap();
+        }
+    }
+}
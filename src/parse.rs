@@ -0,0 +1,425 @@
+use crate::types::{
+    Comment,
+    Link,
+    Thing,
+};
+use serde::{
+    de::DeserializeOwned,
+    Deserialize,
+};
+
+/// How strictly responses from reddit are parsed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Any malformed item fails the entire response.
+    #[default]
+    Strict,
+
+    /// Unexpected nulls and missing fields in links and comments are replaced with defaults.
+    ///
+    /// Listing children that still cannot be parsed are skipped instead of failing the entire response.
+    Lenient,
+
+    /// Like [`ParseMode::Strict`], but things with fields this library does not know about fail the entire response.
+    ///
+    /// The error message lists the unknown fields, in the form returned by [`unknown_fields`].
+    /// This is for detecting when reddit adds or renames fields, not for normal use,
+    /// since reddit sends many fields that are not worth typing.
+    ///
+    /// serde's `deny_unknown_fields` cannot be used for this,
+    /// as it does not work with the `#[serde(flatten)]` of [`Votable`](crate::types::Votable) and [`Created`](crate::types::Created)
+    /// in [`Link`] and [`Comment`], so the fields are checked on the json value before parsing.
+    #[cfg(feature = "schema-drift")]
+    DenyUnknownFields,
+}
+
+impl ParseMode {
+    /// Parse json bytes using this mode.
+    pub(crate) fn parse_slice<T>(self, bytes: &[u8]) -> Result<T, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            Self::Strict => from_slice(bytes),
+            Self::Lenient => {
+                let mut value: serde_json::Value = from_slice(bytes)?;
+                repair_value(&mut value);
+                serde_json::from_value(value)
+            }
+            #[cfg(feature = "schema-drift")]
+            Self::DenyUnknownFields => {
+                let value: serde_json::Value = from_slice(bytes)?;
+                // Only things are checked, other responses have no known list of fields.
+                if is_thing_value(&value) {
+                    let mut unknown = Vec::new();
+                    collect_unknown_fields(&value, "", &mut unknown);
+                    if !unknown.is_empty() {
+                        return Err(serde::de::Error::custom(format_args!(
+                            "unknown fields: {}",
+                            unknown.join(", ")
+                        )));
+                    }
+                }
+                serde_json::from_value(value)
+            }
+        }
+    }
+}
+
+/// Parse json bytes, using simd-json if enabled.
+fn from_slice<T>(bytes: &[u8]) -> Result<T, serde_json::Error>
+where
+    T: DeserializeOwned,
+{
+    #[cfg(feature = "simd-json")]
+    {
+        // simd-json parses in place, so it needs its own copy.
+        let mut bytes = bytes.to_vec();
+        if let Ok(value) = simd_json::serde::from_slice(&mut bytes) {
+            return Ok(value);
+        }
+    }
+
+    // simd-json does not support every type, like raw values, and its errors are not serde_json errors,
+    // so failures are parsed again.
+    serde_json::from_slice(bytes)
+}
+
+/// Walk a json value, repairing or removing listing children that fail to parse.
+fn repair_value(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Array(values) => {
+            for value in values.iter_mut() {
+                repair_value(value);
+            }
+        }
+        serde_json::Value::Object(object) => {
+            if object.get("kind").and_then(|kind| kind.as_str()) != Some("Listing") {
+                return;
+            }
+
+            let children = object
+                .get_mut("data")
+                .and_then(|data| data.get_mut("children"))
+                .and_then(|children| children.as_array_mut());
+
+            if let Some(children) = children {
+                children.retain_mut(repair_child);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Try to repair a listing child, returning `false` if it is still malformed.
+fn repair_child(child: &mut serde_json::Value) -> bool {
+    // Repair nested replies first, so one malformed reply does not fail the whole thread above it.
+    if let Some(replies) = child
+        .get_mut("data")
+        .and_then(|data| data.get_mut("replies"))
+    {
+        repair_value(replies);
+    }
+
+    if Thing::deserialize(&*child).is_ok() {
+        return true;
+    }
+
+    let kind = child.get("kind").and_then(|kind| kind.as_str());
+    let merge = match kind {
+        Some("t1") => merge_defaults::<Comment>,
+        Some("t3") => merge_defaults::<Link>,
+        _ => return false,
+    };
+    let Some(data) = child.get_mut("data") else {
+        return false;
+    };
+    if !merge(data) {
+        return false;
+    }
+
+    Thing::deserialize(&*child).is_ok()
+}
+
+/// Replace the nulls and missing fields of a json object with the fields of the default value of a type.
+///
+/// Returns `false` if the value is not an object.
+pub(crate) fn merge_defaults<T>(value: &mut serde_json::Value) -> bool
+where
+    T: Default + serde::Serialize,
+{
+    let Ok(serde_json::Value::Object(mut defaults)) = serde_json::to_value(T::default()) else {
+        return false;
+    };
+    let serde_json::Value::Object(object) = value else {
+        return false;
+    };
+
+    for (key, value) in std::mem::take(object) {
+        if !value.is_null() {
+            defaults.insert(key, value);
+        }
+    }
+    *object = defaults;
+
+    true
+}
+
+/// Find the fields in a json response that this library does not know about.
+///
+/// Paths are returned in the form `data.children[0].data.treatment_tags`.
+/// This is useful for detecting when reddit adds or renames fields.
+/// To fail parses with unknown fields instead, see [`ParseMode::DenyUnknownFields`].
+#[cfg(feature = "schema-drift")]
+pub fn unknown_fields(text: &str) -> Result<Vec<String>, serde_json::Error> {
+    let value: serde_json::Value = serde_json::from_str(text)?;
+
+    let mut unknown = Vec::new();
+    collect_unknown_fields(&value, "", &mut unknown);
+    Ok(unknown)
+}
+
+/// Returns `true` if a json value is a thing or a non-empty array of things.
+#[cfg(feature = "schema-drift")]
+fn is_thing_value(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Array(values) => !values.is_empty() && values.iter().all(is_thing_value),
+        serde_json::Value::Object(object) => {
+            object.get("kind").is_some_and(|kind| kind.is_string()) && object.contains_key("data")
+        }
+        _ => false,
+    }
+}
+
+/// Collect unknown fields from a json value that should be a thing or an array of things.
+#[cfg(feature = "schema-drift")]
+fn collect_unknown_fields(value: &serde_json::Value, path: &str, unknown: &mut Vec<String>) {
+    use crate::types::{
+        Listing,
+        More,
+    };
+
+    const THING_FIELDS: &[&str] = &["id", "name", "kind", "data"];
+
+    let object = match value {
+        serde_json::Value::Array(values) => {
+            for (i, value) in values.iter().enumerate() {
+                collect_unknown_fields(value, &format!("{path}[{i}]"), unknown);
+            }
+            return;
+        }
+        serde_json::Value::Object(object) => object,
+        _ => return,
+    };
+
+    let prefix = if path.is_empty() {
+        String::new()
+    } else {
+        format!("{path}.")
+    };
+    for key in object.keys() {
+        if !THING_FIELDS.contains(&key.as_str()) {
+            unknown.push(format!("{prefix}{key}"));
+        }
+    }
+
+    let data_path = format!("{prefix}data");
+    let Some(data) = object.get("data").and_then(|data| data.as_object()) else {
+        return;
+    };
+    match object.get("kind").and_then(|kind| kind.as_str()) {
+        Some("Listing") => {
+            compare_fields(data, &field_names::<Listing>(), &data_path, unknown);
+            if let Some(children) = data.get("children") {
+                collect_unknown_fields(children, &format!("{data_path}.children"), unknown);
+            }
+        }
+        Some("more") => compare_fields(data, &field_names::<More>(), &data_path, unknown),
+        Some("t1") => {
+            compare_fields(data, &field_names::<Comment>(), &data_path, unknown);
+            // Reddit sends an empty string instead of a listing if there are no replies.
+            if let Some(replies) = data.get("replies").filter(|replies| replies.is_object()) {
+                collect_unknown_fields(replies, &format!("{data_path}.replies"), unknown);
+            }
+        }
+        Some("t3") => collect_unknown_link_fields(data, &data_path, unknown),
+        _ => {}
+    }
+}
+
+/// Collect unknown fields from the data of a link, including crossposted links.
+#[cfg(feature = "schema-drift")]
+fn collect_unknown_link_fields(
+    data: &serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    unknown: &mut Vec<String>,
+) {
+    compare_fields(data, &field_names::<Link>(), path, unknown);
+
+    let crossposts = data
+        .get("crosspost_parent_list")
+        .and_then(|crossposts| crossposts.as_array());
+    for (i, crosspost) in crossposts.into_iter().flatten().enumerate() {
+        if let Some(crosspost) = crosspost.as_object() {
+            let path = format!("{path}.crosspost_parent_list[{i}]");
+            collect_unknown_link_fields(crosspost, &path, unknown);
+        }
+    }
+}
+
+/// Push the keys of a json object that are not in the known list.
+#[cfg(feature = "schema-drift")]
+fn compare_fields(
+    object: &serde_json::Map<String, serde_json::Value>,
+    known: &[String],
+    path: &str,
+    unknown: &mut Vec<String>,
+) {
+    for key in object.keys() {
+        if !known.contains(key) {
+            unknown.push(format!("{path}.{key}"));
+        }
+    }
+}
+
+/// Get the names of the fields a type contains, by serializing its default value.
+#[cfg(feature = "schema-drift")]
+fn field_names<T>() -> Vec<String>
+where
+    T: Default + serde::Serialize,
+{
+    match serde_json::to_value(T::default()) {
+        Ok(serde_json::Value::Object(object)) => object.into_iter().map(|(key, _)| key).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SUBREDDIT_SAMPLE_1: &str = include_str!("../test_data/subreddit_dankmemes.json");
+    const COMMENT_SAMPLE_1: &str = include_str!("../test_data/comment_h8p0py.json");
+
+    #[test]
+    fn lenient_replaces_nulls() {
+        let mut value: serde_json::Value = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let children = value["data"]["children"].as_array_mut().unwrap();
+        let num_children = children.len();
+        children[0]["data"]["title"] = serde_json::Value::Null;
+        children[1]["data"]
+            .as_object_mut()
+            .unwrap()
+            .remove("num_comments");
+        let text = serde_json::to_string(&value).unwrap();
+
+        serde_json::from_str::<Thing>(&text).unwrap_err();
+        ParseMode::Strict
+            .parse_slice::<Thing>(text.as_bytes())
+            .unwrap_err();
+
+        let thing = ParseMode::Lenient
+            .parse_slice::<Thing>(text.as_bytes())
+            .unwrap();
+        let listing = thing.data.as_listing().unwrap();
+        assert_eq!(listing.children.len(), num_children);
+        assert_eq!(&*listing.children[0].data.as_link().unwrap().title, "");
+        assert_eq!(listing.children[1].data.as_link().unwrap().num_comments, 0);
+    }
+
+    #[test]
+    fn lenient_skips_malformed_children() {
+        let mut value: serde_json::Value = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let children = value["data"]["children"].as_array_mut().unwrap();
+        let num_children = children.len();
+        children[0]["data"]["score"] = serde_json::Value::from("not a number");
+        let text = serde_json::to_string(&value).unwrap();
+
+        ParseMode::Strict
+            .parse_slice::<Thing>(text.as_bytes())
+            .unwrap_err();
+
+        let thing = ParseMode::Lenient
+            .parse_slice::<Thing>(text.as_bytes())
+            .unwrap();
+        let listing = thing.data.as_listing().unwrap();
+        assert_eq!(listing.children.len(), num_children - 1);
+    }
+
+    #[test]
+    fn lenient_repairs_nested_replies() {
+        let mut value: serde_json::Value = serde_json::from_str(COMMENT_SAMPLE_1).unwrap();
+        let comment = &mut value[1]["data"]["children"][1]["data"];
+        let replies = comment["replies"]["data"]["children"]
+            .as_array_mut()
+            .unwrap();
+        let num_replies = replies.len();
+        replies[0]["data"]["score"] = serde_json::Value::from("not a number");
+        replies[1]["data"]["body"] = serde_json::Value::Null;
+        let text = serde_json::to_string(&value).unwrap();
+
+        ParseMode::Strict
+            .parse_slice::<Vec<Thing>>(text.as_bytes())
+            .unwrap_err();
+
+        let things = ParseMode::Lenient
+            .parse_slice::<Vec<Thing>>(text.as_bytes())
+            .unwrap();
+        let comments = things[1].data.as_listing().unwrap();
+        let comment = comments.children[1].data.as_comment().unwrap();
+        let replies = comment.replies.as_ref().unwrap();
+        assert_eq!(replies.children.len(), num_replies - 1);
+        assert_eq!(&*replies.children[0].data.as_comment().unwrap().body, "");
+    }
+
+    #[test]
+    #[cfg(feature = "schema-drift")]
+    fn unknown_fields_subreddit() {
+        let unknown = unknown_fields(SUBREDDIT_SAMPLE_1).unwrap();
+        assert!(unknown.contains(&"data.dist".to_string()));
+        assert!(unknown.contains(&"data.children[0].data.treatment_tags".to_string()));
+        assert!(!unknown.contains(&"data.children[0].data.title".to_string()));
+        assert!(!unknown.contains(&"data.children[0].data.ups".to_string()));
+
+        // Unknown fields are only reported, they do not fail parsing.
+        serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_1).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "schema-drift")]
+    fn deny_unknown_fields() {
+        let error = ParseMode::DenyUnknownFields
+            .parse_slice::<Thing>(SUBREDDIT_SAMPLE_1.as_bytes())
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("data.children[0].data.treatment_tags"));
+
+        // Things written by this library only have known fields.
+        let thing: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let text = serde_json::to_string(&thing).unwrap();
+        ParseMode::DenyUnknownFields
+            .parse_slice::<Thing>(text.as_bytes())
+            .unwrap();
+
+        // Responses that are not things are not checked.
+        ParseMode::DenyUnknownFields
+            .parse_slice::<serde_json::Value>(br#"{"s3UploadLease": {}}"#)
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "schema-drift")]
+    fn unknown_fields_nested_replies() {
+        let mut value: serde_json::Value = serde_json::from_str(COMMENT_SAMPLE_1).unwrap();
+        let replies = &mut value[1]["data"]["children"][1]["data"]["replies"];
+        replies["data"]["children"][0]["data"]["new_reply_field"] = serde_json::Value::from(1);
+        let text = serde_json::to_string(&value).unwrap();
+
+        let unknown = unknown_fields(&text).unwrap();
+        assert!(unknown.contains(
+            &"[1].data.children[1].data.replies.data.children[0].data.new_reply_field".to_string()
+        ));
+        assert!(!unknown.contains(&"[1].data.children[1].data.replies".to_string()));
+    }
+}
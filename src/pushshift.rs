@@ -0,0 +1,261 @@
+use crate::{
+    error::Error,
+    parse::merge_defaults,
+    types::{
+        Comment,
+        Link,
+    },
+};
+use serde::de::DeserializeOwned;
+
+/// The base url of the default pushshift-compatible api.
+pub const DEFAULT_BASE_URL: &str = "https://api.pullpush.io";
+
+/// The maximum number of items the api will return for one search.
+pub const MAX_SIZE: usize = 100;
+
+/// A client for a pushshift-compatible api, like pullpush.
+///
+/// Results are converted into this crate's [`Link`] and [`Comment`] types,
+/// with fields the archive does not have set to their defaults.
+#[derive(Debug, Clone)]
+pub struct Pushshift {
+    client: reqwest::Client,
+    base_url: Box<str>,
+}
+
+impl Pushshift {
+    /// Create a new [`Pushshift`] client for the [`DEFAULT_BASE_URL`].
+    pub fn new(client: reqwest::Client) -> Self {
+        Self::with_base_url(client, DEFAULT_BASE_URL)
+    }
+
+    /// Create a new [`Pushshift`] client for a pushshift-compatible api at the given base url.
+    pub fn with_base_url(client: reqwest::Client, base_url: &str) -> Self {
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').into(),
+        }
+    }
+
+    /// Search for submissions.
+    pub async fn search_submissions(&self, query: &SearchQuery) -> Result<Vec<Link>, Error> {
+        self.search("submission", query, "t3_").await
+    }
+
+    /// Search for comments.
+    pub async fn search_comments(&self, query: &SearchQuery) -> Result<Vec<Comment>, Error> {
+        self.search("comment", query, "t1_").await
+    }
+
+    /// Search an endpoint, where prefix is the fullname prefix of the results.
+    async fn search<T>(
+        &self,
+        endpoint: &str,
+        query: &SearchQuery,
+        prefix: &str,
+    ) -> Result<Vec<T>, Error>
+    where
+        T: Default + serde::Serialize + DeserializeOwned,
+    {
+        let url = format!("{}/reddit/search/{endpoint}/", self.base_url);
+        let response = self
+            .client
+            .get(&url)
+            .query(&query.to_query())
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let url = response.url().clone();
+        let status = response.status();
+        let bytes = response.bytes().await?;
+
+        parse_search(&bytes, prefix).map_err(|error| Error::Json {
+            data: String::from_utf8_lossy(&bytes).into(),
+            error,
+            url: Some(Box::new(url)),
+            status: Some(status),
+            request_id: None,
+        })
+    }
+}
+
+/// The order of search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSort {
+    /// Newest first
+    Descending,
+
+    /// Oldest first
+    Ascending,
+}
+
+/// A search of a pushshift-compatible api.
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    q: Option<Box<str>>,
+    subreddit: Option<Box<str>>,
+    author: Option<Box<str>>,
+    after: Option<u64>,
+    before: Option<u64>,
+    size: Option<usize>,
+    sort: Option<SearchSort>,
+}
+
+impl SearchQuery {
+    /// Create a new, empty [`SearchQuery`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Search for text.
+    pub fn q(mut self, q: &str) -> Self {
+        self.q = Some(q.into());
+        self
+    }
+
+    /// Only return items from a subreddit, excluding the /r/ prefix.
+    pub fn subreddit(mut self, subreddit: &str) -> Self {
+        self.subreddit = Some(subreddit.into());
+        self
+    }
+
+    /// Only return items by an author, excluding the /u/ prefix.
+    pub fn author(mut self, author: &str) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Only return items created after a time in UTC epoch-seconds.
+    pub fn after(mut self, after: u64) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    /// Only return items created before a time in UTC epoch-seconds.
+    pub fn before(mut self, before: u64) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    /// Set the number of items to return.
+    ///
+    /// This is capped at [`MAX_SIZE`].
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = Some(size.min(MAX_SIZE));
+        self
+    }
+
+    /// Set the order of results.
+    pub fn sort(mut self, sort: SearchSort) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Get the query parameters of this search.
+    fn to_query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(q) = self.q.as_deref() {
+            query.push(("q", q.to_string()));
+        }
+        if let Some(subreddit) = self.subreddit.as_deref() {
+            query.push(("subreddit", subreddit.to_string()));
+        }
+        if let Some(author) = self.author.as_deref() {
+            query.push(("author", author.to_string()));
+        }
+        if let Some(after) = self.after {
+            query.push(("after", after.to_string()));
+        }
+        if let Some(before) = self.before {
+            query.push(("before", before.to_string()));
+        }
+        if let Some(size) = self.size {
+            query.push(("size", size.to_string()));
+        }
+        if let Some(sort) = self.sort {
+            let sort = match sort {
+                SearchSort::Descending => "desc",
+                SearchSort::Ascending => "asc",
+            };
+            query.push(("sort", sort.to_string()));
+        }
+
+        query
+    }
+}
+
+/// Parse search results, where prefix is the fullname prefix of the results.
+fn parse_search<T>(bytes: &[u8], prefix: &str) -> Result<Vec<T>, serde_json::Error>
+where
+    T: Default + serde::Serialize + DeserializeOwned,
+{
+    #[derive(serde::Deserialize)]
+    struct SearchResults {
+        data: Vec<serde_json::Value>,
+    }
+
+    let results: SearchResults = serde_json::from_slice(bytes)?;
+    results
+        .data
+        .into_iter()
+        .map(|mut item| {
+            // Archived items do not have fullnames.
+            if let Some(object) = item.as_object_mut() {
+                if !object.contains_key("name") {
+                    if let Some(id) = object.get("id").and_then(|id| id.as_str()) {
+                        let name = format!("{prefix}{id}");
+                        object.insert("name".into(), name.into());
+                    }
+                }
+            }
+
+            merge_defaults::<T>(&mut item);
+            serde_json::from_value(item)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SUBMISSIONS_SAMPLE_1: &str = include_str!("../test_data/pushshift_submissions.json");
+    const COMMENTS_SAMPLE_1: &str = include_str!("../test_data/pushshift_comments.json");
+
+    #[test]
+    fn parse_submissions() {
+        let links: Vec<Link> = parse_search(SUBMISSIONS_SAMPLE_1.as_bytes(), "t3_").unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(&*links[0].name, "t3_10a1b2c");
+        assert_eq!(links[0].created.created_utc, 1673546282.0);
+        assert!(links[1].is_deleted());
+    }
+
+    #[test]
+    fn parse_comments() {
+        let comments: Vec<Comment> = parse_search(COMMENTS_SAMPLE_1.as_bytes(), "t1_").unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(&*comments[0].name, "t1_j3x8k2l");
+        assert_eq!(&*comments[0].link_id, "t3_10a1b2c");
+    }
+
+    #[test]
+    fn search_query() {
+        let query = SearchQuery::new()
+            .subreddit("aww")
+            .after(1673540000)
+            .size(500)
+            .sort(SearchSort::Ascending);
+        assert_eq!(
+            query.to_query(),
+            [
+                ("subreddit", "aww".to_string()),
+                ("after", "1673540000".to_string()),
+                ("size", "100".to_string()),
+                ("sort", "asc".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use reqwest::header::HeaderMap;
+use std::time::{
+    Duration,
+    Instant,
+};
+
+/// How long to wait after being rate limited, if reddit does not say.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// The rate limit state reported by reddit.
+///
+/// See https://github.com/reddit-archive/reddit/wiki/API#rules
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// The number of requests that may still be made in this period.
+    pub remaining: f64,
+
+    /// The number of requests made in this period.
+    pub used: u64,
+
+    /// The time until the period resets, as of when this was received.
+    pub reset: Duration,
+
+    /// When this was received.
+    pub received_at: Instant,
+}
+
+impl RateLimit {
+    /// Parse the rate limit from response headers.
+    ///
+    /// Returns `None` if the headers are missing or invalid.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: &str| headers.get(name)?.to_str().ok();
+
+        let remaining = get("x-ratelimit-remaining")?.parse().ok()?;
+        let used = get("x-ratelimit-used")?.parse().ok()?;
+        let reset = get("x-ratelimit-reset")?.parse().ok()?;
+
+        Some(Self {
+            remaining,
+            used,
+            reset: Duration::from_secs(reset),
+            received_at: Instant::now(),
+        })
+    }
+
+    /// Get the time when the period resets.
+    pub fn reset_at(&self) -> Instant {
+        self.received_at + self.reset
+    }
+
+    /// Get how long to wait before making another request.
+    ///
+    /// This is zero if there are requests remaining or the period has reset.
+    pub fn wait_duration(&self) -> Duration {
+        if self.remaining >= 1.0 {
+            return Duration::ZERO;
+        }
+
+        self.reset_at().saturating_duration_since(Instant::now())
+    }
+
+    /// Reserve a request of this period, returning how long to wait first if there are none remaining.
+    ///
+    /// Reserving before sending keeps concurrent requests from all seeing the same remaining request.
+    pub(crate) fn reserve(&mut self) -> Duration {
+        let wait_duration = self.wait_duration();
+        if wait_duration.is_zero() && self.remaining >= 1.0 {
+            self.remaining -= 1.0;
+            self.used += 1;
+        }
+
+        wait_duration
+    }
+}
+
+/// Get how long to wait after a 429 response, from its headers.
+pub(crate) fn retry_after_from_headers(headers: &HeaderMap) -> Duration {
+    let get = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.trim().parse().ok() };
+
+    get("retry-after")
+        .or_else(|| get("x-ratelimit-reset"))
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+/// Get how long to wait from the message of a `RATELIMIT` api error, like "try again in 9 minutes.".
+pub(crate) fn retry_after_from_message(message: &str) -> Duration {
+    parse_retry_message(message).unwrap_or(DEFAULT_RETRY_AFTER)
+}
+
+/// Parse the wait from a message like "try again in 9 minutes.".
+fn parse_retry_message(message: &str) -> Option<Duration> {
+    let mut words = message.split_whitespace();
+    while let Some(word) = words.next() {
+        let Ok(amount) = word.parse::<u64>() else {
+            continue;
+        };
+        let unit = words.next()?;
+        let seconds = if unit.starts_with("millisecond") {
+            return Some(Duration::from_millis(amount));
+        } else if unit.starts_with("second") {
+            amount
+        } else if unit.starts_with("minute") {
+            amount * 60
+        } else if unit.starts_with("hour") {
+            amount * 60 * 60
+        } else {
+            continue;
+        };
+
+        return Some(Duration::from_secs(seconds));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0.0"));
+        headers.insert("x-ratelimit-used", HeaderValue::from_static("100"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("120"));
+
+        let rate_limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(rate_limit.used, 100);
+        assert_eq!(rate_limit.reset, Duration::from_secs(120));
+        assert!(rate_limit.wait_duration() > Duration::from_secs(100));
+
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("12.0"));
+        let rate_limit = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(rate_limit.wait_duration(), Duration::ZERO);
+
+        headers.remove("x-ratelimit-used");
+        assert!(RateLimit::from_headers(&headers).is_none());
+    }
+
+    #[test]
+    fn reserve() {
+        let mut rate_limit = RateLimit {
+            remaining: 2.0,
+            used: 98,
+            reset: Duration::from_secs(120),
+            received_at: Instant::now(),
+        };
+        assert_eq!(rate_limit.reserve(), Duration::ZERO);
+        assert_eq!(rate_limit.reserve(), Duration::ZERO);
+        assert_eq!(rate_limit.used, 100);
+        assert!(rate_limit.reserve() > Duration::from_secs(100));
+        assert_eq!(rate_limit.remaining, 0.0);
+    }
+
+    #[test]
+    fn retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after_from_headers(&headers), DEFAULT_RETRY_AFTER);
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("120"));
+        assert_eq!(retry_after_from_headers(&headers), Duration::from_secs(120));
+        headers.insert("retry-after", HeaderValue::from_static("5"));
+        assert_eq!(retry_after_from_headers(&headers), Duration::from_secs(5));
+
+        assert_eq!(
+            retry_after_from_message("you are doing that too much. try again in 9 minutes."),
+            Duration::from_secs(9 * 60)
+        );
+        assert_eq!(
+            retry_after_from_message("Take a break for 30 seconds before trying again."),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            retry_after_from_message("you are doing that too much."),
+            DEFAULT_RETRY_AFTER
+        );
+    }
+}
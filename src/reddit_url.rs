@@ -0,0 +1,232 @@
+use crate::{
+    client::BASE_URL,
+    error::Error,
+};
+use std::str::FromStr;
+
+/// A parsed reddit url.
+///
+/// This can be parsed from full urls on any reddit host, like `https://old.reddit.com/r/aww/`,
+/// urls without a scheme, like `reddit.com/r/aww/`, relative paths, like `/r/aww/`, and `redd.it` shortlinks.
+/// Query strings and fragments are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedditUrl {
+    /// A subreddit page, like `/r/aww/` or `/r/aww/new/`
+    Subreddit {
+        /// the subreddit excluding the /r/ prefix
+        subreddit: Box<str>,
+    },
+
+    /// A post permalink, like `/r/aww/comments/10a1b2c/my_cat/`
+    Post {
+        /// the subreddit excluding the /r/ prefix. None for shortlinks and `/comments/{id}` urls
+        subreddit: Option<Box<str>>,
+
+        /// the id of the post, without the t3_ prefix
+        post_id: Box<str>,
+    },
+
+    /// A comment permalink, like `/r/aww/comments/10a1b2c/my_cat/j3x8k2l/`
+    Comment {
+        /// the subreddit excluding the /r/ prefix. None for `/comments/{id}` urls
+        subreddit: Option<Box<str>>,
+
+        /// the id of the post, without the t3_ prefix
+        post_id: Box<str>,
+
+        /// the id of the comment, without the t1_ prefix
+        comment_id: Box<str>,
+    },
+
+    /// A user page, like `/user/spez/` or `/u/spez/`
+    User {
+        /// the account name excluding the /u/ prefix
+        username: Box<str>,
+    },
+
+    /// A share link, like `/r/aww/s/AbCdEfGhIj`
+    ///
+    /// These redirect to a post or comment permalink, which reddit does not expose in the url itself.
+    /// See [`Client::resolve_share_link`](crate::Client::resolve_share_link).
+    Share {
+        /// the subreddit excluding the /r/ prefix
+        subreddit: Box<str>,
+
+        /// the opaque id of the share link
+        share_id: Box<str>,
+    },
+}
+
+impl RedditUrl {
+    /// Get the subreddit of this url, if it has one.
+    pub fn subreddit(&self) -> Option<&str> {
+        match self {
+            Self::Subreddit { subreddit } | Self::Share { subreddit, .. } => Some(subreddit),
+            Self::Post { subreddit, .. } | Self::Comment { subreddit, .. } => subreddit.as_deref(),
+            Self::User { .. } => None,
+        }
+    }
+
+    /// Get the id of the post of this url, if it has one.
+    pub fn post_id(&self) -> Option<&str> {
+        match self {
+            Self::Post { post_id, .. } | Self::Comment { post_id, .. } => Some(post_id),
+            _ => None,
+        }
+    }
+
+    /// Get the id of the comment of this url, if it has one.
+    pub fn comment_id(&self) -> Option<&str> {
+        match self {
+            Self::Comment { comment_id, .. } => Some(comment_id),
+            _ => None,
+        }
+    }
+
+    /// Get the account name of the user of this url, if it has one.
+    pub fn username(&self) -> Option<&str> {
+        match self {
+            Self::User { username } => Some(username),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for RedditUrl {
+    type Err = Error;
+
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        parse(url).ok_or(Error::InvalidUrl)
+    }
+}
+
+/// Parse a reddit url.
+fn parse(url: &str) -> Option<RedditUrl> {
+    let url = url.trim();
+    let url = if url.starts_with('/') {
+        url::Url::parse(BASE_URL).ok()?.join(url).ok()?
+    } else if url.contains("://") {
+        url::Url::parse(url).ok()?
+    } else {
+        url::Url::parse(&format!("https://{url}")).ok()?
+    };
+
+    let host = url.host_str()?;
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+
+    if host == "redd.it" {
+        let post_id = segments.next()?.into();
+        return Some(RedditUrl::Post {
+            subreddit: None,
+            post_id,
+        });
+    }
+
+    if host != "reddit.com" && !host.ends_with(".reddit.com") {
+        return None;
+    }
+
+    match segments.next()? {
+        "r" => {
+            let subreddit: Box<str> = segments.next()?.into();
+            match segments.next() {
+                Some("comments") => parse_comments(Some(subreddit), segments),
+                Some("s") => Some(RedditUrl::Share {
+                    subreddit,
+                    share_id: segments.next()?.into(),
+                }),
+                _ => Some(RedditUrl::Subreddit { subreddit }),
+            }
+        }
+        "comments" => parse_comments(None, segments),
+        "u" | "user" => Some(RedditUrl::User {
+            username: segments.next()?.into(),
+        }),
+        _ => None,
+    }
+}
+
+/// Parse the segments of a url after `/comments/`.
+fn parse_comments<'a>(
+    subreddit: Option<Box<str>>,
+    mut segments: impl Iterator<Item = &'a str>,
+) -> Option<RedditUrl> {
+    let post_id = segments.next()?.into();
+
+    // The next segment is the title slug, which can be anything.
+    let _slug = segments.next();
+    match segments.next() {
+        Some(comment_id) => Some(RedditUrl::Comment {
+            subreddit,
+            post_id,
+            comment_id: comment_id.into(),
+        }),
+        None => Some(RedditUrl::Post { subreddit, post_id }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_urls() {
+        let post = RedditUrl::Post {
+            subreddit: Some("aww".into()),
+            post_id: "10a1b2c".into(),
+        };
+        for url in [
+            "https://www.reddit.com/r/aww/comments/10a1b2c/my_cat_found_the_sun/",
+            "https://old.reddit.com/r/aww/comments/10a1b2c/?sort=new",
+            "reddit.com/r/aww/comments/10a1b2c",
+            "/r/aww/comments/10a1b2c/my_cat_found_the_sun/",
+        ] {
+            assert_eq!(url.parse::<RedditUrl>().unwrap(), post, "url = {url}");
+        }
+
+        let comment: RedditUrl = "https://www.reddit.com/r/aww/comments/10a1b2c/my_cat/j3x8k2l/"
+            .parse()
+            .unwrap();
+        assert_eq!(comment.subreddit(), Some("aww"));
+        assert_eq!(comment.post_id(), Some("10a1b2c"));
+        assert_eq!(comment.comment_id(), Some("j3x8k2l"));
+
+        assert_eq!(
+            "https://redd.it/10a1b2c".parse::<RedditUrl>().unwrap(),
+            RedditUrl::Post {
+                subreddit: None,
+                post_id: "10a1b2c".into(),
+            }
+        );
+        assert_eq!(
+            "https://www.reddit.com/r/aww/new/"
+                .parse::<RedditUrl>()
+                .unwrap(),
+            RedditUrl::Subreddit {
+                subreddit: "aww".into(),
+            }
+        );
+        assert_eq!(
+            "https://www.reddit.com/u/spez"
+                .parse::<RedditUrl>()
+                .unwrap()
+                .username(),
+            Some("spez")
+        );
+        assert_eq!(
+            "https://www.reddit.com/r/aww/s/AbCdEfGhIj"
+                .parse::<RedditUrl>()
+                .unwrap(),
+            RedditUrl::Share {
+                subreddit: "aww".into(),
+                share_id: "AbCdEfGhIj".into(),
+            }
+        );
+
+        assert!("https://example.com/r/aww/"
+            .parse::<RedditUrl>()
+            .unwrap_err()
+            .is_invalid_url());
+        assert!("https://www.reddit.com/".parse::<RedditUrl>().is_err());
+    }
+}
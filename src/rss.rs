@@ -0,0 +1,125 @@
+/// An atom feed, like the ones reddit serves at `/r/{subreddit}.rss`.
+#[derive(Debug, serde::Deserialize)]
+struct Feed {
+    #[serde(rename = "entry", default)]
+    entries: Vec<Entry>,
+}
+
+/// An entry of a feed.
+#[derive(Debug, serde::Deserialize)]
+struct Entry {
+    author: Option<EntryAuthor>,
+    category: Option<EntryCategory>,
+    content: Option<Box<str>>,
+    id: Box<str>,
+    link: EntryLink,
+    published: Option<Box<str>>,
+    updated: Option<Box<str>>,
+    title: Box<str>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct EntryAuthor {
+    name: Box<str>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct EntryCategory {
+    #[serde(rename = "@term")]
+    term: Box<str>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct EntryLink {
+    #[serde(rename = "@href")]
+    href: Box<str>,
+}
+
+/// A post from an rss feed.
+///
+/// Feeds have far less data than json listings, so this only has the fields they provide.
+/// These are useful as a fallback when the json endpoints are rate limited or blocked.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct FeedLink {
+    /// this link's identifier, e.g. "8xwlg"
+    pub id: Box<str>,
+
+    /// Fullname of link, e.g. "t3_8xwlg"
+    pub name: Box<str>,
+
+    /// the title of the link
+    pub title: Box<str>,
+
+    /// the account name of the poster, without the /u/ prefix. None if the post has no author.
+    pub author: Option<Box<str>>,
+
+    /// subreddit of thing excluding the /r/ prefix. "pics"
+    pub subreddit: Option<Box<str>>,
+
+    /// the full URL of the permanent link for this link
+    pub permalink: Box<str>,
+
+    /// the rendered body of the post, as escaped HTML
+    pub content_html: Option<Box<str>>,
+
+    /// when the post was published, in RFC 3339 format
+    pub published: Option<Box<str>>,
+
+    /// when the post was last updated, in RFC 3339 format
+    pub updated: Option<Box<str>>,
+}
+
+impl From<Entry> for FeedLink {
+    fn from(entry: Entry) -> Self {
+        let id = entry
+            .id
+            .split_once('_')
+            .map(|(_, id)| id)
+            .unwrap_or(&entry.id)
+            .into();
+        let author = entry.author.map(|author| {
+            author
+                .name
+                .strip_prefix("/u/")
+                .map(Box::from)
+                .unwrap_or(author.name)
+        });
+
+        Self {
+            id,
+            name: entry.id,
+            title: entry.title,
+            author,
+            subreddit: entry.category.map(|category| category.term),
+            permalink: entry.link.href,
+            content_html: entry.content,
+            published: entry.published,
+            updated: entry.updated,
+        }
+    }
+}
+
+/// Parse the posts of an atom feed.
+pub fn parse_feed(text: &str) -> Result<Vec<FeedLink>, quick_xml::DeError> {
+    let feed: Feed = quick_xml::de::from_str(text)?;
+    Ok(feed.entries.into_iter().map(FeedLink::from).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FEED_SAMPLE_1: &str = include_str!("../test_data/subreddit_aww.rss");
+
+    #[test]
+    fn parse_feed_1() {
+        let links = parse_feed(FEED_SAMPLE_1).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(&*links[0].id, "10a1b2c");
+        assert_eq!(&*links[0].name, "t3_10a1b2c");
+        assert_eq!(links[0].author.as_deref(), Some("whiskers_fan"));
+        assert_eq!(links[0].subreddit.as_deref(), Some("aww"));
+        assert_eq!(&*links[1].title, "First day at the beach & he loved it");
+        dbg!(links);
+    }
+}
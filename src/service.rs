@@ -0,0 +1,184 @@
+use crate::{
+    client::{
+        parse_body,
+        request_id,
+    },
+    error::Error,
+    parse::ParseMode,
+    Client,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{
+        Context,
+        Poll,
+    },
+};
+
+/// A request to the reddit api, for use with [`tower_service::Service`].
+///
+/// Requests use the OAuth api if the client has an access token, like the methods of [`Client`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedditRequest {
+    method: reqwest::Method,
+    path: Box<str>,
+    query: Vec<(Box<str>, Box<str>)>,
+    form: Vec<(Box<str>, Box<str>)>,
+}
+
+impl RedditRequest {
+    /// Create a new request with a method and api path, like `/r/aww/new`.
+    pub fn new(method: reqwest::Method, path: &str) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            query: Vec::new(),
+            form: Vec::new(),
+        }
+    }
+
+    /// Create a new GET request with an api path.
+    pub fn get(path: &str) -> Self {
+        Self::new(reqwest::Method::GET, path)
+    }
+
+    /// Create a new POST request with an api path.
+    pub fn post(path: &str) -> Self {
+        Self::new(reqwest::Method::POST, path)
+    }
+
+    /// Add a query parameter.
+    pub fn query(mut self, key: &str, value: &str) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Add a form parameter, sent as the body of the request.
+    pub fn form(mut self, key: &str, value: &str) -> Self {
+        self.form.push((key.into(), value.into()));
+        self
+    }
+
+    /// Get the method of this request.
+    pub fn method(&self) -> &reqwest::Method {
+        &self.method
+    }
+
+    /// Get the api path of this request.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+/// A response from the reddit api, for use with [`tower_service::Service`].
+///
+/// The body is kept as bytes, so it can be parsed into whatever type the request returns.
+#[derive(Debug, Clone)]
+pub struct RedditResponse {
+    url: url::Url,
+    status: reqwest::StatusCode,
+    request_id: Option<Box<str>>,
+    parse_mode: ParseMode,
+    body: bytes::Bytes,
+}
+
+impl RedditResponse {
+    /// Get the url of the response.
+    pub fn url(&self) -> &url::Url {
+        &self.url
+    }
+
+    /// Get the status of the response.
+    pub fn status(&self) -> reqwest::StatusCode {
+        self.status
+    }
+
+    /// Get the reddit request id of the response, if it has one.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// Get the body of the response.
+    pub fn body(&self) -> &bytes::Bytes {
+        &self.body
+    }
+
+    /// Parse the body as json, using the [`ParseMode`] of the client that sent the request.
+    ///
+    /// This fails if reddit reported an api error.
+    pub fn json<T>(&self) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        parse_body(
+            self.parse_mode,
+            &self.body,
+            self.url.clone(),
+            self.status,
+            self.request_id.clone(),
+        )
+    }
+}
+
+impl tower_service::Service<RedditRequest> for Client {
+    type Response = RedditResponse;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    /// The client is always ready, as it waits for the rate limit when a request is sent.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RedditRequest) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move {
+            let mut builder = client
+                .api_request(request.method, &request.path)
+                .query(&request.query);
+            if !request.form.is_empty() {
+                builder = builder.form(&request.form);
+            }
+
+            let response = client.send(builder).await?;
+            let url = response.url().clone();
+            let status = response.status();
+            let request_id = request_id(&response);
+            let body = response.bytes().await?;
+
+            Ok(RedditResponse {
+                url,
+                status,
+                request_id,
+                parse_mode: client.parse_mode(),
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Thing;
+    use tower_service::Service;
+
+    #[test]
+    fn request_builder() {
+        let request = RedditRequest::post("/api/read_message").form("id", "t4_1");
+        assert_eq!(request.method(), reqwest::Method::POST);
+        assert_eq!(request.path(), "/api/read_message");
+        assert_eq!(request.form, [("id".into(), "t4_1".into())]);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn call_works() {
+        let mut client = Client::new();
+        let request = RedditRequest::get("/r/aww/new").query("limit", "5");
+        let response = client.call(request).await.unwrap();
+        let thing: Thing = response.json().unwrap();
+        assert_eq!(thing.data.into_listing().unwrap().children.len(), 5);
+    }
+}
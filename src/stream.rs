@@ -0,0 +1,849 @@
+use crate::{
+    client::Client,
+    error::Error,
+    pagination::{
+        ListingItem,
+        MAX_LIMIT,
+    },
+    types::{
+        Comment,
+        Link,
+        Message,
+        Thing,
+    },
+};
+use futures_util::{
+    Stream,
+    StreamExt,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    collections::{
+        HashSet,
+        VecDeque,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+/// The default shortest time to wait between polls.
+const DEFAULT_MIN_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The default longest time to wait between polls.
+const DEFAULT_MAX_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The maximum number of subreddits joined into the path of a single request.
+///
+/// Reddit does not document a limit, but very long urls are rejected.
+const MAX_SUBREDDITS_PER_REQUEST: usize = 50;
+
+/// The number of polls that try to mark a batch of messages as read before it is given up on.
+const MAX_MARK_READ_ATTEMPTS: usize = 3;
+
+/// The number of fullnames to remember for deduplication, per polled path.
+const SEEN_CAPACITY: usize = MAX_LIMIT * 10;
+
+/// The position of a stream, used to resume it later without re-processing or missing items.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ResumeToken {
+    /// The fullname of the last yielded item.
+    pub fullname: Box<str>,
+
+    /// The time the last yielded item was created in UTC epoch-seconds.
+    pub created_utc: f64,
+}
+
+impl ResumeToken {
+    /// Returns `true` if an item was yielded at or before this token.
+    fn covers(&self, fullname: &str, created_utc: f64) -> bool {
+        if fullname == &*self.fullname {
+            return true;
+        }
+        if created_utc != self.created_utc {
+            return created_utc < self.created_utc;
+        }
+
+        // Ids are base 36 and increase over time, so they break ties between items created in the same second.
+        match (fullname_id(fullname), fullname_id(&self.fullname)) {
+            (Some(id), Some(token_id)) => id < token_id,
+            _ => false,
+        }
+    }
+}
+
+/// Parse the base 36 id of a fullname, like "t3_15bfi0".
+fn fullname_id(fullname: &str) -> Option<u64> {
+    let (_, id) = fullname.split_once('_')?;
+    u64::from_str_radix(id, 36).ok()
+}
+
+/// A place to persist the [`ResumeToken`] of a stream, so it can be resumed after a restart.
+pub trait TokenStore: Send + Sync {
+    /// Load the last stored token, if there is one.
+    fn load(&self) -> Option<ResumeToken>;
+
+    /// Store a token.
+    ///
+    /// This is called every time the stream yields an item.
+    fn store(&self, token: ResumeToken);
+}
+
+impl TokenStore for Mutex<Option<ResumeToken>> {
+    fn load(&self) -> Option<ResumeToken> {
+        self.lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+
+    fn store(&self, token: ResumeToken) {
+        *self.lock().unwrap_or_else(|error| error.into_inner()) = Some(token);
+    }
+}
+
+/// Options for tuning the behavior of a polling stream.
+#[derive(Clone)]
+pub struct StreamOptions {
+    min_interval: Duration,
+    max_interval: Duration,
+    jitter: Duration,
+    page_size: usize,
+    skip_initial: bool,
+    resume_token: Option<ResumeToken>,
+    token_store: Option<Arc<dyn TokenStore>>,
+}
+
+impl StreamOptions {
+    /// Create new [`StreamOptions`] with the defaults.
+    pub fn new() -> Self {
+        Self {
+            min_interval: DEFAULT_MIN_POLL_INTERVAL,
+            max_interval: DEFAULT_MAX_POLL_INTERVAL,
+            jitter: Duration::ZERO,
+            page_size: MAX_LIMIT,
+            skip_initial: false,
+            resume_token: None,
+            token_store: None,
+        }
+    }
+
+    /// Set the shortest time to wait between polls.
+    ///
+    /// This defaults to 2 seconds.
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self.max_interval = self.max_interval.max(min_interval);
+        self
+    }
+
+    /// Set the longest time to wait between polls.
+    ///
+    /// This defaults to 60 seconds.
+    pub fn max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self.min_interval = self.min_interval.min(max_interval);
+        self
+    }
+
+    /// Set the maximum random time added to each wait between polls.
+    ///
+    /// This helps avoid many streams polling at the same time.
+    /// This defaults to zero.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the number of items requested per poll.
+    ///
+    /// This is capped at [`MAX_LIMIT`], which is the default.
+    /// Smaller pages use less traffic, but items may be missed if more than this many are created between polls.
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.clamp(1, MAX_LIMIT);
+        self
+    }
+
+    /// Set whether items that already exist when the stream starts are skipped.
+    ///
+    /// This defaults to `false`.
+    pub fn skip_initial(mut self, skip_initial: bool) -> Self {
+        self.skip_initial = skip_initial;
+        self
+    }
+
+    /// Resume the stream after the item of the given token.
+    ///
+    /// Items created at or before the token are skipped.
+    /// This takes priority over [`StreamOptions::skip_initial`].
+    pub fn resume_from(mut self, token: ResumeToken) -> Self {
+        self.resume_token = Some(token);
+        self
+    }
+
+    /// Set where the stream persists its [`ResumeToken`].
+    ///
+    /// The stream resumes from the token in the store, unless one was given with [`StreamOptions::resume_from`].
+    pub fn token_store(mut self, token_store: Arc<dyn TokenStore>) -> Self {
+        self.token_store = Some(token_store);
+        self
+    }
+
+    /// Get a random duration to add to a wait.
+    fn random_jitter(&self) -> Duration {
+        use std::hash::{
+            BuildHasher,
+            Hasher,
+        };
+
+        if self.jitter.is_zero() {
+            return Duration::ZERO;
+        }
+
+        // RandomState is seeded randomly, which is good enough for jitter.
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let jitter_nanos = u64::try_from(self.jitter.as_nanos()).unwrap_or(u64::MAX);
+
+        Duration::from_nanos(random % jitter_nanos)
+    }
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for StreamOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamOptions")
+            .field("min_interval", &self.min_interval)
+            .field("max_interval", &self.max_interval)
+            .field("jitter", &self.jitter)
+            .field("page_size", &self.page_size)
+            .field("skip_initial", &self.skip_initial)
+            .field("resume_token", &self.resume_token)
+            .field("token_store", &self.token_store.is_some())
+            .finish()
+    }
+}
+
+/// A bounded set of fullnames that have already been yielded.
+#[derive(Debug)]
+struct SeenSet {
+    set: HashSet<Box<str>>,
+    order: VecDeque<Box<str>>,
+    capacity: usize,
+}
+
+impl SeenSet {
+    /// Create an empty set that remembers at most capacity fullnames.
+    fn new(capacity: usize) -> Self {
+        Self {
+            set: HashSet::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Insert a fullname, returning `true` if it was not already present.
+    fn insert(&mut self, fullname: &str) -> bool {
+        if self.set.contains(fullname) {
+            return false;
+        }
+
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.set.remove(&oldest);
+            }
+        }
+
+        self.set.insert(fullname.into());
+        self.order.push_back(fullname.into());
+
+        true
+    }
+}
+
+/// An item that can be yielded by a polling stream.
+pub(crate) trait PollItem: Sized {
+    /// The type of the listing's children.
+    type Child: DeserializeOwned;
+
+    /// Get the fullname of a child, used for deduplication.
+    fn child_fullname(child: &Self::Child) -> Option<&str>;
+
+    /// Get the time a child was created in UTC epoch-seconds, used for resuming.
+    fn child_created_utc(child: &Self::Child) -> Option<f64>;
+
+    /// Try to convert a child into this item.
+    fn from_child(child: Self::Child) -> Option<Self>;
+}
+
+impl<T> PollItem for T
+where
+    T: ListingItem,
+{
+    type Child = Thing;
+
+    fn child_fullname(child: &Self::Child) -> Option<&str> {
+        child.fullname()
+    }
+
+    fn child_created_utc(child: &Self::Child) -> Option<f64> {
+        child.created_utc()
+    }
+
+    fn from_child(child: Self::Child) -> Option<Self> {
+        T::from_thing(child)
+    }
+}
+
+/// A child of an inbox listing.
+///
+/// Inbox listings have comments that are missing most comment fields,
+/// so every child is parsed as a message.
+#[derive(serde::Deserialize)]
+pub(crate) struct InboxChild {
+    data: Message,
+}
+
+impl PollItem for Message {
+    type Child = InboxChild;
+
+    fn child_fullname(child: &Self::Child) -> Option<&str> {
+        Some(&child.data.name)
+    }
+
+    fn child_created_utc(child: &Self::Child) -> Option<f64> {
+        Some(child.data.created.created_utc)
+    }
+
+    fn from_child(child: Self::Child) -> Option<Self> {
+        Some(child.data)
+    }
+}
+
+/// An item from a stream over multiple subreddits, tagged with the subreddit it came from.
+#[derive(Debug)]
+pub struct SourcedItem<T> {
+    /// The subreddit this item came from, as it was passed to the stream.
+    pub source: Box<str>,
+
+    /// The item
+    pub item: T,
+}
+
+/// An item that belongs to a subreddit.
+pub(crate) trait SubredditItem {
+    /// Get the subreddit name excluding the /r/ prefix.
+    fn subreddit(&self) -> &str;
+}
+
+impl SubredditItem for Link {
+    fn subreddit(&self) -> &str {
+        &self.subreddit
+    }
+}
+
+impl SubredditItem for Comment {
+    fn subreddit(&self) -> &str {
+        &self.subreddit
+    }
+}
+
+/// The state of a polling stream.
+struct Poller<T> {
+    client: Client,
+
+    /// The paths that are polled, in turn.
+    paths: Vec<Box<str>>,
+
+    /// The index of the path to poll next.
+    next_path: usize,
+
+    options: StreamOptions,
+    seen: SeenSet,
+
+    /// Queued items, with the token to store once they are yielded.
+    queue: VecDeque<(T, Option<ResumeToken>)>,
+
+    interval: Duration,
+    polled: bool,
+
+    /// Whether each path has been polled successfully, as its first poll is its backlog.
+    path_polled: Vec<bool>,
+
+    /// The token to resume after, if resuming.
+    resume_token: Option<ResumeToken>,
+
+    /// Whether yielded items are marked as read.
+    mark_read: bool,
+
+    /// The fullnames of queued items that should be marked as read once they are processed.
+    unread: Vec<Box<str>>,
+
+    /// The number of polls that have failed to mark the unread items as read in a row.
+    mark_read_failures: usize,
+}
+
+impl<T> Poller<T>
+where
+    T: PollItem,
+{
+    fn new(client: Client, paths: Vec<Box<str>>, options: StreamOptions) -> Self {
+        let resume_token = options.resume_token.clone().or_else(|| {
+            options
+                .token_store
+                .as_ref()
+                .and_then(|token_store| token_store.load())
+        });
+
+        // Every path is polled in turn, so each needs room for its own recent items.
+        let seen = SeenSet::new(SEEN_CAPACITY * paths.len().max(1));
+        let path_polled = vec![false; paths.len()];
+
+        Self {
+            client,
+            paths,
+            next_path: 0,
+            interval: options.min_interval,
+            options,
+            seen,
+            queue: VecDeque::new(),
+            polled: false,
+            path_polled,
+            resume_token,
+            mark_read: false,
+            unread: Vec::new(),
+            mark_read_failures: 0,
+        }
+    }
+
+    /// Fetch the newest items, queueing the ones that have not been seen yet.
+    ///
+    /// If marking the previous items as read fails, the new items are still queued before the error is returned.
+    async fn poll(&mut self) -> Result<(), Error> {
+        // The consumer asked for more items, so every queued item has been processed.
+        // Failing to mark them does not stop new items from being fetched, and they are tried again next poll.
+        let mark_read_result = self.mark_unread_read().await;
+
+        let path_index = self.next_path;
+        self.next_path = (self.next_path + 1) % self.paths.len();
+
+        let query = [("limit", self.options.page_size.to_string())];
+        let children = self
+            .client
+            .get_listing_children::<T::Child>(&self.paths[path_index], &query, false)
+            .await?;
+
+        self.queue_children(path_index, children);
+
+        mark_read_result
+    }
+
+    /// Mark the unread items as read, keeping them to retry unless they have failed too many times.
+    async fn mark_unread_read(&mut self) -> Result<(), Error> {
+        if self.unread.is_empty() {
+            return Ok(());
+        }
+
+        let fullnames: Vec<&str> = self.unread.iter().map(|fullname| &**fullname).collect();
+        match self.client.mark_read(&fullnames).await {
+            Ok(()) => {
+                self.unread.clear();
+                self.mark_read_failures = 0;
+                Ok(())
+            }
+            Err(error) => {
+                self.mark_read_failures += 1;
+                if self.mark_read_failures >= MAX_MARK_READ_ATTEMPTS {
+                    self.unread.clear();
+                    self.mark_read_failures = 0;
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Queue the children of a page fetched from the path at the given index, oldest first.
+    fn queue_children(&mut self, path_index: usize, children: Vec<T::Child>) {
+        // Every path has a backlog, so each is skipped or resumed on its first successful poll.
+        let is_initial = !std::mem::replace(&mut self.path_polled[path_index], true);
+        let resume_token = self.resume_token.as_ref().filter(|_| is_initial);
+        let skip = is_initial && self.options.skip_initial && resume_token.is_none();
+
+        // Listings are newest first, but we want to yield items in the order they were created.
+        let mut num_new = 0;
+        for child in children.into_iter().rev() {
+            let fullname = T::child_fullname(&child);
+            let is_new = match fullname {
+                Some(fullname) => self.seen.insert(fullname),
+                None => true,
+            };
+            if !is_new {
+                continue;
+            }
+
+            num_new += 1;
+            let token =
+                fullname
+                    .zip(T::child_created_utc(&child))
+                    .map(|(fullname, created_utc)| ResumeToken {
+                        fullname: fullname.into(),
+                        created_utc,
+                    });
+            let is_resumed = match (resume_token, &token) {
+                (Some(resume_token), Some(token)) => {
+                    resume_token.covers(&token.fullname, token.created_utc)
+                }
+                _ => false,
+            };
+            if skip || is_resumed {
+                continue;
+            }
+            if self.mark_read {
+                if let Some(fullname) = fullname {
+                    self.unread.push(fullname.into());
+                }
+            }
+            if let Some(item) = T::from_child(child) {
+                self.queue.push_back((item, token));
+            }
+        }
+
+        self.adapt_interval(num_new);
+    }
+
+    /// Poll more often if there are many new items, and less often if there are none.
+    fn adapt_interval(&mut self, num_new: usize) {
+        if num_new == 0 {
+            self.back_off();
+        } else if num_new >= self.options.page_size / 2 {
+            self.interval = (self.interval / 2).max(self.options.min_interval);
+        }
+    }
+
+    /// Poll less often.
+    fn back_off(&mut self) {
+        self.interval = (self.interval * 2).min(self.options.max_interval);
+    }
+
+    /// Get the next item, polling as needed.
+    async fn next(&mut self) -> Result<T, Error> {
+        loop {
+            if let Some((item, token)) = self.queue.pop_front() {
+                if let Some((token_store, token)) = self.options.token_store.as_ref().zip(token) {
+                    token_store.store(token);
+                }
+                return Ok(item);
+            }
+
+            if self.polled {
+                tokio::time::sleep(self.interval + self.options.random_jitter()).await;
+            }
+            self.polled = true;
+
+            if let Err(error) = self.poll().await {
+                self.back_off();
+                return Err(error);
+            }
+        }
+    }
+}
+
+/// Create an infinite stream that polls a listing endpoint for new items.
+///
+/// Items are yielded oldest first, and are deduplicated by fullname.
+/// The stream does not end on errors.
+pub(crate) fn poll_listing<T>(
+    client: Client,
+    path: &str,
+    options: StreamOptions,
+) -> impl Stream<Item = Result<T, Error>>
+where
+    T: PollItem,
+{
+    poll(Poller::new(client, vec![path.into()], options))
+}
+
+/// Create an infinite stream that polls a listing endpoint of many subreddits for new items.
+///
+/// Subreddits are joined into as few requests as possible, which are polled in turn.
+/// Items are tagged with the subreddit they came from.
+/// The listing is the path of the endpoint after the subreddit, like "/new".
+pub(crate) fn poll_subreddits<T>(
+    client: Client,
+    subreddits: &[&str],
+    listing: &str,
+    options: StreamOptions,
+) -> impl Stream<Item = Result<SourcedItem<T>, Error>>
+where
+    T: PollItem + SubredditItem,
+{
+    let paths = subreddits
+        .chunks(MAX_SUBREDDITS_PER_REQUEST)
+        .map(|chunk| format!("/r/{}{listing}", chunk.join("+")).into())
+        .collect();
+    let sources: Vec<Box<str>> = subreddits
+        .iter()
+        .map(|subreddit| Box::<str>::from(*subreddit))
+        .collect();
+
+    poll(Poller::new(client, paths, options)).map(move |item: Result<T, Error>| {
+        let item = item?;
+        let source = sources
+            .iter()
+            .find(|source| source.eq_ignore_ascii_case(item.subreddit()))
+            .cloned()
+            .unwrap_or_else(|| item.subreddit().into());
+
+        Ok(SourcedItem { source, item })
+    })
+}
+
+/// Create an infinite stream that polls an inbox endpoint for new messages.
+///
+/// If mark_read is `true`, each fetched batch of messages is marked as read on the next poll.
+pub(crate) fn poll_inbox(
+    client: Client,
+    path: &str,
+    mark_read: bool,
+    options: StreamOptions,
+) -> impl Stream<Item = Result<Message, Error>> {
+    let mut poller = Poller::new(client, vec![path.into()], options);
+    poller.mark_read = mark_read;
+    poll(poller)
+}
+
+fn poll<T>(poller: Poller<T>) -> impl Stream<Item = Result<T, Error>>
+where
+    T: PollItem,
+{
+    futures_util::stream::unfold(poller, |mut poller| async move {
+        // There is nothing to poll, so the stream is empty.
+        if poller.paths.is_empty() {
+            return None;
+        }
+
+        let item = poller.next().await;
+        Some((item, poller))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INBOX_SAMPLE_1: &str = include_str!("../test_data/inbox_unread.json");
+
+    #[test]
+    fn parse_inbox() {
+        let mut listing: serde_json::Value = serde_json::from_str(INBOX_SAMPLE_1).unwrap();
+        let children: Vec<InboxChild> =
+            serde_json::from_value(listing["data"]["children"].take()).unwrap();
+        let messages: Vec<Message> = children
+            .into_iter()
+            .filter_map(Message::from_child)
+            .collect();
+
+        assert_eq!(messages.len(), 2);
+        assert!(messages[0].was_comment);
+        assert_eq!(&*messages[0].name, "t1_k3c9xq1");
+        assert!(!messages[1].was_comment);
+    }
+
+    #[test]
+    fn seen_set_is_bounded() {
+        let mut seen = SeenSet::new(SEEN_CAPACITY);
+        assert!(seen.insert("t3_0"));
+        assert!(!seen.insert("t3_0"));
+
+        for i in 1..=SEEN_CAPACITY {
+            assert!(seen.insert(&format!("t3_{i}")));
+        }
+        assert_eq!(seen.set.len(), SEEN_CAPACITY);
+        assert!(seen.insert("t3_0"));
+    }
+
+    #[test]
+    fn seen_set_scales_with_paths() {
+        let paths = (0..20).map(|i| format!("/r/test_{i}/new").into()).collect();
+        let poller = Poller::<Link>::new(Client::new(), paths, StreamOptions::new());
+        assert_eq!(poller.seen.capacity, SEEN_CAPACITY * 20);
+    }
+
+    /// Make a link thing whose id and creation time are both n.
+    fn link_thing(n: u64) -> Thing {
+        let link = Link {
+            name: format!("t3_{n}").into(),
+            created: crate::types::Created {
+                created: n as f64,
+                created_utc: n as f64,
+            },
+            ..Link::default()
+        };
+
+        Thing {
+            id: None,
+            name: None,
+            data: crate::types::ThingData::Link(Box::new(link)),
+        }
+    }
+
+    /// Get the fullnames of the queued items of a poller, removing them.
+    fn drain_queue(poller: &mut Poller<Link>) -> Vec<String> {
+        poller
+            .queue
+            .drain(..)
+            .map(|(link, _)| link.name.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn skip_initial_per_path() {
+        let paths = vec!["/r/a/new".into(), "/r/b/new".into()];
+        let options = StreamOptions::new().skip_initial(true);
+        let mut poller = Poller::<Link>::new(Client::new(), paths, options);
+
+        poller.queue_children(0, vec![link_thing(2), link_thing(1)]);
+        assert!(drain_queue(&mut poller).is_empty());
+
+        // The first poll of the second path failed, so the first path is polled again before it.
+        poller.queue_children(0, vec![link_thing(3), link_thing(2), link_thing(1)]);
+        assert_eq!(drain_queue(&mut poller), ["t3_3"]);
+
+        poller.queue_children(1, vec![link_thing(11), link_thing(10)]);
+        assert!(drain_queue(&mut poller).is_empty());
+        poller.queue_children(1, vec![link_thing(12), link_thing(11)]);
+        assert_eq!(drain_queue(&mut poller), ["t3_12"]);
+    }
+
+    #[test]
+    fn resume_per_path() {
+        let paths = vec!["/r/a/new".into(), "/r/b/new".into()];
+        let token = ResumeToken {
+            fullname: "t3_2".into(),
+            created_utc: 2.0,
+        };
+        let options = StreamOptions::new().resume_from(token);
+        let mut poller = Poller::<Link>::new(Client::new(), paths, options);
+
+        poller.queue_children(0, vec![link_thing(3), link_thing(2), link_thing(1)]);
+        assert_eq!(drain_queue(&mut poller), ["t3_3"]);
+
+        // Items older than the token are only skipped on the first successful poll of each path.
+        poller.queue_children(0, vec![link_thing(5), link_thing(0)]);
+        assert_eq!(drain_queue(&mut poller), ["t3_0", "t3_5"]);
+
+        poller.queue_children(1, vec![link_thing(12), link_thing(1)]);
+        assert_eq!(drain_queue(&mut poller), ["t3_12"]);
+    }
+
+    #[tokio::test]
+    async fn mark_read_retries_are_bounded() {
+        // Without an access token, marking items as read fails without making a request.
+        let mut poller = Poller::<Message>::new(
+            Client::new(),
+            vec!["/message/unread".into()],
+            StreamOptions::new(),
+        );
+        poller.unread.push("t4_1".into());
+
+        for _ in 1..MAX_MARK_READ_ATTEMPTS {
+            assert!(poller.mark_unread_read().await.is_err());
+            assert_eq!(poller.unread.len(), 1);
+        }
+        assert!(poller.mark_unread_read().await.is_err());
+        assert!(poller.unread.is_empty());
+        assert!(poller.mark_unread_read().await.is_ok());
+    }
+
+    #[test]
+    fn resume_token_covers() {
+        let token = ResumeToken {
+            fullname: "t3_15bfi0".into(),
+            created_utc: 1000.0,
+        };
+        assert!(token.covers("t3_15bfi0", 1000.0));
+        assert!(token.covers("t3_15bfhz", 1000.0));
+        assert!(token.covers("t3_zzzzz", 999.0));
+        assert!(!token.covers("t3_15bfi1", 1000.0));
+        assert!(!token.covers("t3_1", 1001.0));
+
+        let json = serde_json::to_string(&token).unwrap();
+        assert_eq!(serde_json::from_str::<ResumeToken>(&json).unwrap(), token);
+
+        let token_store = Mutex::new(None);
+        assert!(token_store.load().is_none());
+        token_store.store(token.clone());
+        assert_eq!(token_store.load(), Some(token));
+    }
+
+    #[test]
+    fn options_clamp() {
+        let options = StreamOptions::new()
+            .min_interval(Duration::from_secs(120))
+            .page_size(0);
+        assert_eq!(options.max_interval, Duration::from_secs(120));
+        assert_eq!(options.page_size, 1);
+
+        let options = StreamOptions::new().jitter(Duration::from_secs(5));
+        assert!(options.random_jitter() < Duration::from_secs(5));
+        assert_eq!(StreamOptions::new().random_jitter(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn stream_new_posts() {
+        let client = Client::new();
+        let links: Vec<Result<Link, Error>> = client
+            .stream_new_posts("all", StreamOptions::new())
+            .take(150)
+            .collect()
+            .await;
+
+        let mut seen = HashSet::new();
+        for link in links {
+            let link = link.unwrap();
+            assert!(seen.insert(link.name.clone()));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn stream_new_comments() {
+        let client = Client::new();
+        let comments: Vec<Result<Comment, Error>> = client
+            .stream_new_comments("all", StreamOptions::new())
+            .take(150)
+            .collect()
+            .await;
+
+        let mut seen = HashSet::new();
+        for comment in comments {
+            let comment = comment.unwrap();
+            assert!(seen.insert(comment.name.clone()));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn stream_new_posts_multi() {
+        let client = Client::new();
+        let subreddits = ["aww", "cats", "dankmemes"];
+        let links: Vec<_> = client
+            .stream_new_posts_multi(&subreddits, StreamOptions::new())
+            .take(150)
+            .collect()
+            .await;
+
+        for link in links {
+            let link = link.unwrap();
+            assert!(subreddits.contains(&&*link.source));
+        }
+    }
+}
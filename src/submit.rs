@@ -0,0 +1,640 @@
+use crate::{
+    captcha::CaptchaSolution,
+    client::bool_param,
+    error::Error,
+};
+
+/// The maximum length of a post title, in characters.
+pub const MAX_TITLE_LENGTH: usize = 300;
+
+/// The content of a post
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionKind {
+    /// A text post
+    SelfPost {
+        /// the markdown body of the post. May be empty
+        text: Box<str>,
+    },
+
+    /// A link post
+    Link {
+        /// the url the post links to
+        url: Box<str>,
+    },
+}
+
+/// A post that has not been submitted yet.
+///
+/// Create one with [`SubmissionBuilder`], or the [`SubmissionDraft::self_post`] and [`SubmissionDraft::link`] shorthands.
+/// Use [`SubmissionDraft::validate`] to check it against the rules of a subreddit before submitting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionDraft {
+    title: Box<str>,
+    kind: SubmissionKind,
+    flair_id: Option<Box<str>>,
+    flair_text: Option<Box<str>>,
+    nsfw: bool,
+    spoiler: bool,
+    send_replies: bool,
+    collection_id: Option<Box<str>>,
+    captcha: Option<CaptchaSolution>,
+}
+
+impl SubmissionDraft {
+    /// Create a builder for a post with the given title.
+    ///
+    /// See [`SubmissionBuilder::new`].
+    pub fn builder(title: &str) -> SubmissionBuilder {
+        SubmissionBuilder::new(title)
+    }
+
+    /// Create a draft of a text post.
+    pub fn self_post(title: &str, text: &str) -> Self {
+        SubmissionBuilder::new(title).body(text).build()
+    }
+
+    /// Create a draft of a link post.
+    pub fn link(title: &str, url: &str) -> Self {
+        SubmissionBuilder::new(title).url(url).build()
+    }
+
+    /// Set the id of the link flair template of the post.
+    pub fn flair_id(mut self, flair_id: &str) -> Self {
+        self.flair_id = Some(flair_id.into());
+        self
+    }
+
+    /// Set the text of the link flair of the post, for flair templates with editable text.
+    pub fn flair_text(mut self, flair_text: &str) -> Self {
+        self.flair_text = Some(flair_text.into());
+        self
+    }
+
+    /// Send the solution of a captcha with the post, for accounts that must solve one.
+    pub fn captcha(mut self, captcha: CaptchaSolution) -> Self {
+        self.captcha = Some(captcha);
+        self
+    }
+
+    /// Get the title of the post.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the content of the post.
+    pub fn kind(&self) -> &SubmissionKind {
+        &self.kind
+    }
+
+    /// Returns `true` if the post will be marked nsfw.
+    pub fn is_nsfw(&self) -> bool {
+        self.nsfw
+    }
+
+    /// Returns `true` if the post will be marked as a spoiler.
+    pub fn is_spoiler(&self) -> bool {
+        self.spoiler
+    }
+
+    /// Returns `true` if replies to the post will be sent to the poster's inbox.
+    pub fn send_replies(&self) -> bool {
+        self.send_replies
+    }
+
+    /// Get the id of the collection the post will be added to, if any.
+    pub fn collection_id(&self) -> Option<&str> {
+        self.collection_id.as_deref()
+    }
+
+    /// Check this draft against the requirements of a subreddit.
+    ///
+    /// This fails with [`Error::InvalidSubmission`] for the first problem found.
+    /// Regex rules are not checked.
+    pub fn validate(&self, requirements: &PostRequirements) -> Result<(), Error> {
+        match self.problems(requirements).into_iter().next() {
+            Some(problem) => Err(Error::InvalidSubmission { problem }),
+            None => Ok(()),
+        }
+    }
+
+    /// Get all the ways this draft does not meet the requirements of a subreddit.
+    ///
+    /// Regex rules are not checked.
+    pub fn problems(&self, requirements: &PostRequirements) -> Vec<SubmissionProblem> {
+        let mut problems = Vec::new();
+
+        let title_length = self.title.trim().chars().count();
+        let min_title_length = requirements.title_text_min_length.unwrap_or(1);
+        let max_title_length = requirements
+            .title_text_max_length
+            .unwrap_or(MAX_TITLE_LENGTH)
+            .min(MAX_TITLE_LENGTH);
+        if title_length < min_title_length {
+            problems.push(SubmissionProblem::TitleTooShort {
+                min: min_title_length,
+            });
+        }
+        if title_length > max_title_length {
+            problems.push(SubmissionProblem::TitleTooLong {
+                max: max_title_length,
+            });
+        }
+        check_strings(
+            &self.title,
+            &requirements.title_required_strings,
+            &requirements.title_blacklisted_strings,
+            SubmissionProblem::TitleMissingRequiredString,
+            SubmissionProblem::TitleBlacklistedString,
+            &mut problems,
+        );
+
+        if requirements.is_flair_required && self.flair_id.is_none() {
+            problems.push(SubmissionProblem::FlairRequired);
+        }
+
+        match &self.kind {
+            SubmissionKind::SelfPost { text } => {
+                let body_length = text.trim().chars().count();
+                match requirements.body_restriction_policy.as_deref() {
+                    Some("required") if body_length == 0 => {
+                        problems.push(SubmissionProblem::BodyRequired)
+                    }
+                    Some("notAllowed") if body_length > 0 => {
+                        problems.push(SubmissionProblem::BodyNotAllowed)
+                    }
+                    _ => {}
+                }
+                if body_length > 0 {
+                    if let Some(min) = requirements.body_text_min_length {
+                        if body_length < min {
+                            problems.push(SubmissionProblem::BodyTooShort { min });
+                        }
+                    }
+                    if let Some(max) = requirements.body_text_max_length {
+                        if body_length > max {
+                            problems.push(SubmissionProblem::BodyTooLong { max });
+                        }
+                    }
+                }
+                check_strings(
+                    text,
+                    &requirements.body_required_strings,
+                    &requirements.body_blacklisted_strings,
+                    SubmissionProblem::BodyMissingRequiredString,
+                    SubmissionProblem::BodyBlacklistedString,
+                    &mut problems,
+                );
+            }
+            SubmissionKind::Link { url } => {
+                let domain = url::Url::parse(url)
+                    .ok()
+                    .and_then(|url| Some(url.host_str()?.to_ascii_lowercase()));
+                match domain {
+                    Some(domain) => {
+                        let is_allowed = requirements.domain_whitelist.is_empty()
+                            || requirements
+                                .domain_whitelist
+                                .iter()
+                                .any(|allowed| is_same_site(&domain, allowed));
+                        let is_banned = requirements
+                            .domain_blacklist
+                            .iter()
+                            .any(|banned| is_same_site(&domain, banned));
+                        if !is_allowed || is_banned {
+                            problems.push(SubmissionProblem::DomainNotAllowed {
+                                domain: domain.into(),
+                            });
+                        }
+                    }
+                    None => problems.push(SubmissionProblem::InvalidUrl),
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Get the form parameters of this draft for `/api/submit`.
+    pub(crate) fn form<'a>(&'a self, subreddit: &'a str) -> Vec<(&'static str, &'a str)> {
+        let mut form = vec![
+            ("api_type", "json"),
+            ("sr", subreddit),
+            ("title", &*self.title),
+        ];
+        match &self.kind {
+            SubmissionKind::SelfPost { text } => {
+                form.push(("kind", "self"));
+                form.push(("text", text));
+            }
+            SubmissionKind::Link { url } => {
+                form.push(("kind", "link"));
+                form.push(("url", url));
+            }
+        }
+        if let Some(flair_id) = self.flair_id.as_deref() {
+            form.push(("flair_id", flair_id));
+        }
+        if let Some(flair_text) = self.flair_text.as_deref() {
+            form.push(("flair_text", flair_text));
+        }
+        form.push(("nsfw", bool_param(self.nsfw)));
+        form.push(("spoiler", bool_param(self.spoiler)));
+        form.push(("sendreplies", bool_param(self.send_replies)));
+        if let Some(collection_id) = self.collection_id.as_deref() {
+            form.push(("collection_id", collection_id));
+        }
+        if let Some(captcha) = self.captcha.as_ref() {
+            form.extend(captcha.form());
+        }
+
+        form
+    }
+}
+
+/// A builder for a [`SubmissionDraft`].
+///
+/// Posts are text posts with an empty body until a body or url is set.
+/// Replies are sent to the poster's inbox unless disabled, as on reddit.
+#[derive(Debug, Clone)]
+pub struct SubmissionBuilder {
+    draft: SubmissionDraft,
+}
+
+impl SubmissionBuilder {
+    /// Create a builder for a post with the given title.
+    pub fn new(title: &str) -> Self {
+        Self {
+            draft: SubmissionDraft {
+                title: title.into(),
+                kind: SubmissionKind::SelfPost { text: "".into() },
+                flair_id: None,
+                flair_text: None,
+                nsfw: false,
+                spoiler: false,
+                send_replies: true,
+                collection_id: None,
+                captcha: None,
+            },
+        }
+    }
+
+    /// Set the title of the post.
+    pub fn title(mut self, title: &str) -> Self {
+        self.draft.title = title.into();
+        self
+    }
+
+    /// Set the content of the post.
+    pub fn kind(mut self, kind: SubmissionKind) -> Self {
+        self.draft.kind = kind;
+        self
+    }
+
+    /// Make the post a text post with the given markdown body.
+    pub fn body(self, text: &str) -> Self {
+        self.kind(SubmissionKind::SelfPost { text: text.into() })
+    }
+
+    /// Make the post a link post to the given url.
+    pub fn url(self, url: &str) -> Self {
+        self.kind(SubmissionKind::Link { url: url.into() })
+    }
+
+    /// Set the id of the link flair template of the post.
+    pub fn flair_id(mut self, flair_id: &str) -> Self {
+        self.draft.flair_id = Some(flair_id.into());
+        self
+    }
+
+    /// Set the text of the link flair of the post, for flair templates with editable text.
+    pub fn flair_text(mut self, flair_text: &str) -> Self {
+        self.draft.flair_text = Some(flair_text.into());
+        self
+    }
+
+    /// Set whether the post is marked nsfw.
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.draft.nsfw = nsfw;
+        self
+    }
+
+    /// Set whether the post is marked as a spoiler.
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.draft.spoiler = spoiler;
+        self
+    }
+
+    /// Set whether replies to the post are sent to the poster's inbox.
+    pub fn send_replies(mut self, send_replies: bool) -> Self {
+        self.draft.send_replies = send_replies;
+        self
+    }
+
+    /// Add the post to a collection of the subreddit, by the id of the collection.
+    pub fn collection_id(mut self, collection_id: &str) -> Self {
+        self.draft.collection_id = Some(collection_id.into());
+        self
+    }
+
+    /// Send the solution of a captcha with the post, for accounts that must solve one.
+    pub fn captcha(mut self, captcha: CaptchaSolution) -> Self {
+        self.draft.captcha = Some(captcha);
+        self
+    }
+
+    /// Build the draft.
+    pub fn build(self) -> SubmissionDraft {
+        self.draft
+    }
+}
+
+impl From<SubmissionBuilder> for SubmissionDraft {
+    fn from(builder: SubmissionBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Returns `true` if a domain is a site or one of its subdomains.
+///
+/// Domains are compared case-insensitively.
+fn is_same_site(domain: &str, site: &str) -> bool {
+    let site = site.trim().to_ascii_lowercase();
+    let site = site.trim_start_matches("www.");
+    let domain = domain.to_ascii_lowercase();
+    let domain = domain.trim_start_matches("www.");
+    domain == site
+        || domain
+            .strip_suffix(site)
+            .is_some_and(|subdomain| subdomain.ends_with('.'))
+}
+
+/// Check that text contains one of the required strings, if there are any, and none of the blacklisted strings.
+fn check_strings(
+    text: &str,
+    required: &[Box<str>],
+    blacklisted: &[Box<str>],
+    missing_required: fn(Vec<Box<str>>) -> SubmissionProblem,
+    has_blacklisted: fn(Box<str>) -> SubmissionProblem,
+    problems: &mut Vec<SubmissionProblem>,
+) {
+    let lower = text.to_lowercase();
+    if !required.is_empty()
+        && !required
+            .iter()
+            .any(|string| lower.contains(&string.to_lowercase()))
+    {
+        problems.push(missing_required(required.to_vec()));
+    }
+
+    for string in blacklisted {
+        if lower.contains(&string.to_lowercase()) {
+            problems.push(has_blacklisted(string.clone()));
+        }
+    }
+}
+
+/// A way a post does not meet the requirements of a subreddit
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubmissionProblem {
+    /// The title is shorter than the minimum length
+    #[error("the title must be at least {min} characters")]
+    TitleTooShort {
+        /// the minimum length, in characters
+        min: usize,
+    },
+
+    /// The title is longer than the maximum length
+    #[error("the title must be at most {max} characters")]
+    TitleTooLong {
+        /// the maximum length, in characters
+        max: usize,
+    },
+
+    /// The title does not contain any of the strings the subreddit requires
+    #[error("the title must contain one of {0:?}")]
+    TitleMissingRequiredString(Vec<Box<str>>),
+
+    /// The title contains a string the subreddit does not allow
+    #[error("the title must not contain {0:?}")]
+    TitleBlacklistedString(Box<str>),
+
+    /// The subreddit requires posts to have flair
+    #[error("the post must have flair")]
+    FlairRequired,
+
+    /// The subreddit requires text posts to have a body
+    #[error("the post must have a body")]
+    BodyRequired,
+
+    /// The subreddit does not allow text posts to have a body
+    #[error("the post must not have a body")]
+    BodyNotAllowed,
+
+    /// The body is shorter than the minimum length
+    #[error("the body must be at least {min} characters")]
+    BodyTooShort {
+        /// the minimum length, in characters
+        min: usize,
+    },
+
+    /// The body is longer than the maximum length
+    #[error("the body must be at most {max} characters")]
+    BodyTooLong {
+        /// the maximum length, in characters
+        max: usize,
+    },
+
+    /// The body does not contain any of the strings the subreddit requires
+    #[error("the body must contain one of {0:?}")]
+    BodyMissingRequiredString(Vec<Box<str>>),
+
+    /// The body contains a string the subreddit does not allow
+    #[error("the body must not contain {0:?}")]
+    BodyBlacklistedString(Box<str>),
+
+    /// The url of a link post is not a valid absolute url
+    #[error("the url is invalid")]
+    InvalidUrl,
+
+    /// The subreddit does not allow links to the domain of the url
+    #[error("links to {domain} are not allowed")]
+    DomainNotAllowed {
+        /// the domain of the url
+        domain: Box<str>,
+    },
+}
+
+/// The rules a subreddit has for new posts
+/// See https://www.reddit.com/dev/api#GET_api_v1_{subreddit}_post_requirements
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PostRequirements {
+    /// the minimum length of titles. null if there is no minimum
+    pub title_text_min_length: Option<usize>,
+
+    /// the maximum length of titles. null if there is no maximum besides reddit's
+    pub title_text_max_length: Option<usize>,
+
+    /// titles must contain at least one of these strings, if there are any
+    pub title_required_strings: Vec<Box<str>>,
+
+    /// titles must not contain any of these strings
+    pub title_blacklisted_strings: Vec<Box<str>>,
+
+    /// titles must match these regexes
+    pub title_regexes: Vec<Box<str>>,
+
+    /// whether text posts need a body, like "none", "required", or "notAllowed"
+    pub body_restriction_policy: Option<Box<str>>,
+
+    /// the minimum length of bodies. null if there is no minimum
+    pub body_text_min_length: Option<usize>,
+
+    /// the maximum length of bodies. null if there is no maximum
+    pub body_text_max_length: Option<usize>,
+
+    /// bodies must contain at least one of these strings, if there are any
+    pub body_required_strings: Vec<Box<str>>,
+
+    /// bodies must not contain any of these strings
+    pub body_blacklisted_strings: Vec<Box<str>>,
+
+    /// bodies must match these regexes
+    pub body_regexes: Vec<Box<str>>,
+
+    /// how link domains are restricted, like "none", "whitelist", or "blacklist"
+    pub link_restriction_policy: Option<Box<str>>,
+
+    /// link posts must link to one of these domains, if there are any
+    pub domain_whitelist: Vec<Box<str>>,
+
+    /// link posts must not link to any of these domains
+    pub domain_blacklist: Vec<Box<str>>,
+
+    /// the number of days before a link can be posted again. null if reposts are allowed
+    pub link_repost_age: Option<u64>,
+
+    /// true if posts must have flair
+    pub is_flair_required: bool,
+
+    /// the posting guidelines of the subreddit. null if there are none
+    pub guidelines_text: Option<Box<str>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_draft() {
+        let requirements: PostRequirements = serde_json::from_str(
+            r#"{
+                "title_regexes": [],
+                "body_blacklisted_strings": ["buy now"],
+                "title_blacklisted_strings": [],
+                "body_text_max_length": 20,
+                "title_required_strings": ["[OC]", "[Art]"],
+                "guidelines_text": null,
+                "gallery_min_items": null,
+                "domain_blacklist": ["example.com"],
+                "domain_whitelist": [],
+                "title_text_max_length": null,
+                "body_restriction_policy": "required",
+                "link_restriction_policy": "blacklist",
+                "guidelines_display_policy": null,
+                "body_required_strings": [],
+                "title_text_min_length": 10,
+                "gallery_captions_requirement": "none",
+                "is_flair_required": true,
+                "gallery_max_items": null,
+                "gallery_urls_requirement": "none",
+                "body_regexes": [],
+                "link_repost_age": null,
+                "body_text_min_length": null
+            }"#,
+        )
+        .unwrap();
+
+        let draft = SubmissionDraft::self_post("[OC] My painting", "Took a week").flair_id("abc");
+        assert!(draft.validate(&requirements).is_ok());
+
+        let draft = SubmissionDraft::self_post("A painting", "Buy now, it is cheap!");
+        assert_eq!(
+            draft.problems(&requirements),
+            [
+                SubmissionProblem::TitleMissingRequiredString(vec!["[OC]".into(), "[Art]".into()]),
+                SubmissionProblem::FlairRequired,
+                SubmissionProblem::BodyTooLong { max: 20 },
+                SubmissionProblem::BodyBlacklistedString("buy now".into()),
+            ]
+        );
+        let error = draft.validate(&requirements).unwrap_err();
+        assert!(error.is_invalid_submission());
+
+        let draft = SubmissionDraft::self_post("[art]", "").flair_id("abc");
+        assert_eq!(
+            draft.problems(&requirements),
+            [
+                SubmissionProblem::TitleTooShort { min: 10 },
+                SubmissionProblem::BodyRequired,
+            ]
+        );
+
+        let draft = SubmissionDraft::link("[OC] My painting", "https://img.example.com/a.png")
+            .flair_id("abc");
+        assert_eq!(
+            draft.problems(&requirements),
+            [SubmissionProblem::DomainNotAllowed {
+                domain: "img.example.com".into()
+            }]
+        );
+        let draft =
+            SubmissionDraft::link("[OC] My painting", "https://notexample.com/").flair_id("abc");
+        assert!(draft.validate(&requirements).is_ok());
+    }
+
+    #[test]
+    fn same_site() {
+        assert!(is_same_site("i.imgur.com", "Imgur.com"));
+        assert!(is_same_site("WWW.Imgur.com", "www.imgur.com"));
+        assert!(!is_same_site("notimgur.com", "Imgur.com"));
+    }
+
+    #[test]
+    fn submission_builder_form() {
+        let draft = SubmissionDraft::builder("Release notes")
+            .url("https://example.com/notes")
+            .flair_id("abc")
+            .nsfw(true)
+            .send_replies(false)
+            .collection_id("f5c5b7a1-0000-4f6a-9d18-2b0c9a3c1d2e")
+            .build();
+        assert_eq!(
+            draft.form("rust"),
+            [
+                ("api_type", "json"),
+                ("sr", "rust"),
+                ("title", "Release notes"),
+                ("kind", "link"),
+                ("url", "https://example.com/notes"),
+                ("flair_id", "abc"),
+                ("nsfw", "true"),
+                ("spoiler", "false"),
+                ("sendreplies", "false"),
+                ("collection_id", "f5c5b7a1-0000-4f6a-9d18-2b0c9a3c1d2e"),
+            ]
+        );
+
+        let draft = SubmissionDraft::self_post("Hello", "world")
+            .captcha(crate::captcha::Captcha::new("abc").solve("XYZ"));
+        assert_eq!(
+            &draft.form("rust")[draft.form("rust").len() - 2..],
+            [("iden", "abc"), ("captcha", "XYZ")]
+        );
+
+        let draft = SubmissionDraft::self_post("Hello", "world");
+        assert_eq!(draft, SubmissionBuilder::new("Hello").body("world").build());
+        assert!(draft.send_replies());
+        assert!(!draft.is_nsfw());
+    }
+}
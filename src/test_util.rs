@@ -0,0 +1,383 @@
+use crate::{
+    parse::ParseMode,
+    types::{
+        Comment,
+        CompactStr,
+        Link,
+        Thing,
+        ThingData,
+    },
+};
+
+/// The sample responses this library is tested against, by name.
+///
+/// Names are the file names of the samples without an extension, like "subreddit_dankmemes".
+pub const SAMPLES: &[(&str, &str)] = &[
+    (
+        "comment_h8p0py",
+        include_str!("../test_data/comment_h8p0py.json"),
+    ),
+    (
+        "comment_h966lq",
+        include_str!("../test_data/comment_h966lq.json"),
+    ),
+    (
+        "inbox_unread",
+        include_str!("../test_data/inbox_unread.json"),
+    ),
+    ("karma_list", include_str!("../test_data/karma_list.json")),
+    (
+        "live_thread_about",
+        include_str!("../test_data/live_thread_about.json"),
+    ),
+    (
+        "live_thread_updates",
+        include_str!("../test_data/live_thread_updates.json"),
+    ),
+    ("multireddit", include_str!("../test_data/multireddit.json")),
+    (
+        "subreddit_cromch",
+        include_str!("../test_data/subreddit_cromch.json"),
+    ),
+    (
+        "subreddit_cuddleroll",
+        include_str!("../test_data/subreddit_cuddleroll.json"),
+    ),
+    (
+        "subreddit_cursed_images",
+        include_str!("../test_data/subreddit_cursed_images.json"),
+    ),
+    (
+        "subreddit_dankmemes",
+        include_str!("../test_data/subreddit_dankmemes.json"),
+    ),
+    (
+        "subreddit_rules",
+        include_str!("../test_data/subreddit_rules.json"),
+    ),
+    ("trophy_list", include_str!("../test_data/trophy_list.json")),
+    ("wiki_page", include_str!("../test_data/wiki_page.json")),
+];
+
+/// Get a sample response by name.
+///
+/// See [`SAMPLES`].
+pub fn sample(name: &str) -> Option<&'static str> {
+    SAMPLES
+        .iter()
+        .find(|(sample_name, _)| *sample_name == name)
+        .map(|(_, text)| *text)
+}
+
+/// Parse a thing the way [`ParseMode::Strict`] does, failing on any malformed item.
+///
+/// Fields this library does not know about are ignored; see `parse::unknown_fields` with the `schema-drift` feature to find them.
+pub fn parse_thing_strict(text: &str) -> Result<Thing, serde_json::Error> {
+    ParseMode::Strict.parse_slice(text.as_bytes())
+}
+
+/// A builder for a synthetic [`Link`].
+///
+/// Fields that are not set are filled in as a self post by "test_user" in r/test would have them.
+#[derive(Debug)]
+pub struct LinkBuilder {
+    link: Link,
+    url: Option<Box<str>>,
+}
+
+impl LinkBuilder {
+    /// Create a builder for a link with the given id, like "2zxg4y".
+    pub fn new(id: &str) -> Self {
+        let link = Link {
+            id: id.into(),
+            name: format!("t3_{id}").into(),
+            author: "test_user".into(),
+            subreddit: "test".into(),
+            title: "test post".into(),
+            is_self: true,
+            send_replies: true,
+            link_flair_type: "text".into(),
+            ..Link::default()
+        };
+
+        Self { link, url: None }
+    }
+
+    /// Set the title.
+    pub fn title(mut self, title: &str) -> Self {
+        self.link.title = title.into();
+        self
+    }
+
+    /// Set the account name of the poster.
+    pub fn author(mut self, author: &str) -> Self {
+        self.link.author = author.into();
+        self
+    }
+
+    /// Set the name of the subreddit, without the "r/" prefix.
+    pub fn subreddit(mut self, subreddit: &str) -> Self {
+        self.link.subreddit = subreddit.into();
+        self
+    }
+
+    /// Set the text of the self post.
+    pub fn selftext(mut self, selftext: &str) -> Self {
+        self.link.selftext = selftext.into();
+        self
+    }
+
+    /// Make this a link post to the given url, instead of a self post.
+    pub fn url(mut self, url: &str) -> Self {
+        self.link.is_self = false;
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Set the score, which is also used as the number of upvotes.
+    pub fn score(mut self, score: i64) -> Self {
+        self.link.score = score;
+        self.link.votable.ups = score;
+        self
+    }
+
+    /// Set the number of comments.
+    pub fn num_comments(mut self, num_comments: u64) -> Self {
+        self.link.num_comments = num_comments;
+        self
+    }
+
+    /// Set the time of creation, in UTC epoch-second format.
+    pub fn created_utc(mut self, created_utc: f64) -> Self {
+        self.link.created.created = created_utc;
+        self.link.created.created_utc = created_utc;
+        self
+    }
+
+    /// Set the text of the link flair.
+    pub fn flair(mut self, flair: &str) -> Self {
+        self.link.link_flair_text = Some(flair.into());
+        self
+    }
+
+    /// Set whether the link is marked nsfw.
+    pub fn over_18(mut self, over_18: bool) -> Self {
+        self.link.over_18 = over_18;
+        self
+    }
+
+    /// Set whether the link is marked as a spoiler.
+    pub fn spoiler(mut self, spoiler: bool) -> Self {
+        self.link.spoiler = spoiler;
+        self
+    }
+
+    /// Set whether the link is stickied.
+    pub fn stickied(mut self, stickied: bool) -> Self {
+        self.link.stickied = stickied;
+        self
+    }
+
+    /// Build the link.
+    pub fn build(self) -> Link {
+        let mut link = self.link;
+        let subreddit = link.subreddit.clone();
+
+        link.subreddit_id = format!("t5_{subreddit}").into();
+        link.subreddit_name_prefixed = format!("r/{subreddit}").into();
+        link.permalink = format!("/r/{subreddit}/comments/{}/", link.id).into();
+        match self.url {
+            Some(url) => {
+                link.domain = url::Url::parse(&url)
+                    .ok()
+                    .and_then(|url| {
+                        url.host_str()
+                            .map(|host| host.trim_start_matches("www.").into())
+                    })
+                    .unwrap_or_default();
+                link.url = url;
+            }
+            None => {
+                link.domain = format!("self.{subreddit}").into();
+                link.url = format!("https://www.reddit.com{}", link.permalink).into();
+            }
+        }
+        link.thumbnail = if link.is_self { "self" } else { "default" }.into();
+
+        link
+    }
+
+    /// Build the link, wrapped in a thing.
+    pub fn build_thing(self) -> Thing {
+        Thing {
+            id: None,
+            name: None,
+            data: ThingData::Link(Box::new(self.build())),
+        }
+    }
+}
+
+/// A builder for a synthetic [`Comment`].
+///
+/// Fields that are not set are filled in as a top-level comment by "test_user" in r/test would have them.
+#[derive(Debug)]
+pub struct CommentBuilder {
+    comment: Comment,
+    link_id: Box<str>,
+    parent_id: Option<CompactStr>,
+}
+
+impl CommentBuilder {
+    /// Create a builder for a comment with the given id, like "c3v7f8u".
+    pub fn new(id: &str) -> Self {
+        let comment = Comment {
+            id: id.into(),
+            name: format!("t1_{id}").into(),
+            author: "test_user".into(),
+            subreddit: "test".into(),
+            body: "test comment".into(),
+            ..Comment::default()
+        };
+
+        Self {
+            comment,
+            link_id: "test".into(),
+            parent_id: None,
+        }
+    }
+
+    /// Set the text of the comment.
+    pub fn body(mut self, body: &str) -> Self {
+        self.comment.body = body.into();
+        self
+    }
+
+    /// Set the account name of the commenter.
+    pub fn author(mut self, author: &str) -> Self {
+        self.comment.author = author.into();
+        self
+    }
+
+    /// Set the name of the subreddit, without the "r/" prefix.
+    pub fn subreddit(mut self, subreddit: &str) -> Self {
+        self.comment.subreddit = subreddit.into();
+        self
+    }
+
+    /// Set the id of the link this comment is in, like "2zxg4y".
+    pub fn link_id(mut self, link_id: &str) -> Self {
+        self.link_id = link_id.into();
+        self
+    }
+
+    /// Make this a reply to the comment with the given id, like "c3v7f8u".
+    pub fn parent_comment(mut self, parent_id: &str) -> Self {
+        self.parent_id = Some(format!("t1_{parent_id}").into());
+        self
+    }
+
+    /// Set the score, which is also used as the number of upvotes.
+    pub fn score(mut self, score: i64) -> Self {
+        self.comment.score = score;
+        self.comment.votable.ups = score;
+        self
+    }
+
+    /// Set the time of creation, in UTC epoch-second format.
+    pub fn created_utc(mut self, created_utc: f64) -> Self {
+        self.comment.created.created = created_utc;
+        self.comment.created.created_utc = created_utc;
+        self
+    }
+
+    /// Set the replies of the comment.
+    pub fn replies(mut self, replies: impl IntoIterator<Item = Comment>) -> Self {
+        let children = replies
+            .into_iter()
+            .map(|reply| Thing {
+                id: None,
+                name: None,
+                data: ThingData::Comment(Box::new(reply)),
+            })
+            .collect();
+        self.comment.replies = Some(Box::new(crate::types::Listing {
+            children,
+            ..Default::default()
+        }));
+        self
+    }
+
+    /// Build the comment.
+    pub fn build(self) -> Comment {
+        let mut comment = self.comment;
+        let subreddit = comment.subreddit.clone();
+        let link_fullname = format!("t3_{}", self.link_id);
+
+        comment.subreddit_id = format!("t5_{subreddit}").into();
+        comment.permalink =
+            format!("/r/{subreddit}/comments/{}/_/{}/", self.link_id, comment.id).into();
+        comment.parent_id = self
+            .parent_id
+            .unwrap_or_else(|| link_fullname.as_str().into());
+        comment.link_id = link_fullname.into();
+
+        comment
+    }
+
+    /// Build the comment, wrapped in a thing.
+    pub fn build_thing(self) -> Thing {
+        Thing {
+            id: None,
+            name: None,
+            data: ThingData::Comment(Box::new(self.build())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn samples() {
+        assert!(sample("subreddit_dankmemes").is_some());
+        assert!(sample("subreddit_aww").is_none());
+
+        let thing = parse_thing_strict(sample("subreddit_cromch").unwrap()).unwrap();
+        assert!(thing.data.as_listing().is_some());
+        assert!(parse_thing_strict(r#"{"kind": "t3", "data": {}}"#).is_err());
+    }
+
+    #[test]
+    fn builders() {
+        let link = LinkBuilder::new("abc")
+            .subreddit("rust")
+            .url("https://www.example.com/post")
+            .score(10)
+            .build();
+        assert_eq!(&*link.name, "t3_abc");
+        assert_eq!(&*link.permalink, "/r/rust/comments/abc/");
+        assert_eq!(&*link.domain, "example.com");
+        assert!(!link.is_self);
+
+        let reply = CommentBuilder::new("def")
+            .link_id("abc")
+            .parent_comment("ghi")
+            .build();
+        let comment = CommentBuilder::new("ghi")
+            .link_id("abc")
+            .replies([reply])
+            .build();
+        assert_eq!(&*comment.parent_id, "t3_abc");
+        let walked: Vec<_> = comment.walk_replies().collect();
+        assert_eq!(&*walked[0].0.parent_id, "t1_ghi");
+
+        // Built values round-trip through the strict parser.
+        let text = serde_json::to_string(&CommentBuilder::new("def").build_thing()).unwrap();
+        let thing = parse_thing_strict(&text).unwrap();
+        assert_eq!(
+            thing.data.as_comment().unwrap().body.as_ref(),
+            "test comment"
+        );
+    }
+}
@@ -1,8 +1,17 @@
+/// Values of common query parameters, like sorts and time ranges.
+///
+/// These display as the value reddit expects, and parse from it case-insensitively.
+pub mod params;
+
+mod compact_string;
+
+pub use self::compact_string::CompactStr;
+
 /// Reddit base class.
 /// Listing things have neither name nor id because they are indefinite objects.
 /// That is, they are system generated, not user submitted, and are subject to change quickly and expire.
 /// See https://github.com/reddit-archive/reddit/wiki/JSON#thing-reddit-base-class
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct Thing {
     /// this item's identifier, e.g. "8xwlg"
     pub id: Option<Box<str>>,
@@ -15,6 +24,34 @@ pub struct Thing {
     pub data: ThingData,
 }
 
+impl Thing {
+    /// Get the fullname of this thing, if it has one.
+    pub fn fullname(&self) -> Option<&str> {
+        if let Some(name) = self.name.as_deref() {
+            return Some(name);
+        }
+
+        match &self.data {
+            ThingData::Link(link) => Some(&link.name),
+            ThingData::Comment(comment) => Some(&comment.name),
+            ThingData::Message(message) => Some(&message.name),
+            ThingData::Subreddit(subreddit) => Some(&subreddit.name),
+            _ => None,
+        }
+    }
+
+    /// Get the time this thing was created in UTC epoch-seconds, if it has one.
+    pub fn created_utc(&self) -> Option<f64> {
+        match &self.data {
+            ThingData::Link(link) => Some(link.created.created_utc),
+            ThingData::Comment(comment) => Some(comment.created.created_utc),
+            ThingData::Message(message) => Some(message.created.created_utc),
+            ThingData::Subreddit(subreddit) => subreddit.created_utc,
+            _ => None,
+        }
+    }
+}
+
 /// kind:
 /// All things have a kind. The kind is a String identifier that denotes the object's type.
 /// Some examples: Listing, more, t1, t2
@@ -22,24 +59,60 @@ pub struct Thing {
 /// A custom data structure used to hold valuable information.
 /// This object's format will follow the data structure respective of its kind. See below for specific structures.
 /// See https://www.reddit.com/dev/api#fullnames
-#[derive(Debug, serde::Deserialize)]
-#[serde(tag = "kind", content = "data")]
+///
+/// Kinds this library does not know about are parsed as [`ThingData::Unknown`],
+/// so one unexpected child does not fail the parse of a whole listing.
+#[derive(Debug)]
 pub enum ThingData {
-    #[serde(rename = "Listing")]
+    /// kind == "Listing"
     Listing(Box<Listing>),
 
     // More is small + it already has a vector of things as a vec
-    #[serde(rename = "more")]
+    /// kind == "more"
     More(More),
 
-    #[serde(rename = "t1")]
+    /// kind == "t1"
     Comment(Box<Comment>),
 
     // TODO: Finish type
-    // #[serde(rename = "t2")]
+    // /// kind == "t2"
     // Account(serde_json::Value),
-    #[serde(rename = "t3")]
+    /// kind == "t3"
     Link(Box<Link>),
+
+    /// kind == "t4"
+    Message(Box<Message>),
+
+    /// kind == "t5"
+    Subreddit(Box<Subreddit>),
+
+    /// kind == "t6"
+    Trophy(Box<Trophy>),
+
+    // TrophyList is small + it already has a vector of things as a vec
+    /// kind == "TrophyList"
+    TrophyList(TrophyList),
+
+    /// kind == "LabeledMulti"
+    LabeledMulti(Box<Multireddit>),
+
+    /// kind == "wikipage"
+    WikiPage(Box<WikiPage>),
+
+    /// kind == "LiveUpdateEvent"
+    LiveThread(Box<LiveThread>),
+
+    /// kind == "LiveUpdate"
+    LiveUpdate(Box<LiveUpdate>),
+
+    /// A kind this library does not know about, like "t2" or "modaction"
+    Unknown {
+        /// the kind of the thing
+        kind: Box<str>,
+
+        /// the raw data of the thing
+        data: serde_json::Value,
+    },
 }
 
 impl ThingData {
@@ -82,6 +155,189 @@ impl ThingData {
             _ => None,
         }
     }
+
+    /// Tries to get this ThingData as a comment
+    pub fn as_comment(&self) -> Option<&Comment> {
+        match self {
+            ThingData::Comment(comment) => Some(comment),
+            _ => None,
+        }
+    }
+
+    /// Tries to turn this ThingData into a comment
+    pub fn into_comment(self) -> Option<Box<Comment>> {
+        match self {
+            ThingData::Comment(comment) => Some(comment),
+            _ => None,
+        }
+    }
+
+    /// Tries to get this ThingData as a subreddit
+    pub fn as_subreddit(&self) -> Option<&Subreddit> {
+        match self {
+            ThingData::Subreddit(subreddit) => Some(subreddit),
+            _ => None,
+        }
+    }
+
+    /// Tries to turn this ThingData into a subreddit
+    pub fn into_subreddit(self) -> Option<Box<Subreddit>> {
+        match self {
+            ThingData::Subreddit(subreddit) => Some(subreddit),
+            _ => None,
+        }
+    }
+
+    /// Tries to turn this ThingData into a message
+    pub fn into_message(self) -> Option<Box<Message>> {
+        match self {
+            ThingData::Message(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// Get the kind of this ThingData, like "t3"
+    pub fn kind(&self) -> &str {
+        match self {
+            ThingData::Listing(_) => "Listing",
+            ThingData::More(_) => "more",
+            ThingData::Comment(_) => "t1",
+            ThingData::Link(_) => "t3",
+            ThingData::Message(_) => "t4",
+            ThingData::Subreddit(_) => "t5",
+            ThingData::Trophy(_) => "t6",
+            ThingData::TrophyList(_) => "TrophyList",
+            ThingData::LabeledMulti(_) => "LabeledMulti",
+            ThingData::WikiPage(_) => "wikipage",
+            ThingData::LiveThread(_) => "LiveUpdateEvent",
+            ThingData::LiveUpdate(_) => "LiveUpdate",
+            ThingData::Unknown { kind, .. } => kind,
+        }
+    }
+
+    /// Returns `true` if this is a kind this library does not know about.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, ThingData::Unknown { .. })
+    }
+}
+
+impl serde::Serialize for ThingData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ThingData", 2)?;
+        state.serialize_field("kind", self.kind())?;
+        match self {
+            ThingData::Listing(data) => state.serialize_field("data", data)?,
+            ThingData::More(data) => state.serialize_field("data", data)?,
+            ThingData::Comment(data) => state.serialize_field("data", data)?,
+            ThingData::Link(data) => state.serialize_field("data", data)?,
+            ThingData::Message(data) => state.serialize_field("data", data)?,
+            ThingData::Subreddit(data) => state.serialize_field("data", data)?,
+            ThingData::Trophy(data) => state.serialize_field("data", data)?,
+            ThingData::TrophyList(data) => state.serialize_field("data", data)?,
+            ThingData::LabeledMulti(data) => state.serialize_field("data", data)?,
+            ThingData::WikiPage(data) => state.serialize_field("data", data)?,
+            ThingData::LiveThread(data) => state.serialize_field("data", data)?,
+            ThingData::LiveUpdate(data) => state.serialize_field("data", data)?,
+            ThingData::Unknown { data, .. } => state.serialize_field("data", data)?,
+        }
+        state.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ThingData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("ThingData", &["kind", "data"], ThingDataVisitor)
+    }
+}
+
+/// Deserializes the kind and data of a thing, choosing the type of the data by the kind.
+struct ThingDataVisitor;
+
+impl<'de> serde::de::Visitor<'de> for ThingDataVisitor {
+    type Value = ThingData;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a thing with a kind and data")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        use serde::de::Error;
+
+        let mut kind: Option<Box<str>> = None;
+        let mut data: Option<ThingData> = None;
+        // Reddit sends the kind first, but the data is buffered if it comes before the kind.
+        let mut buffered_data: Option<serde_json::Value> = None;
+        while let Some(key) = map.next_key::<Box<str>>()? {
+            match &*key {
+                "kind" if kind.is_none() && data.is_none() => kind = Some(map.next_value()?),
+                "data" if data.is_none() && buffered_data.is_none() => match kind.take() {
+                    Some(kind) => data = Some(map.next_value_seed(ThingDataSeed { kind })?),
+                    None => buffered_data = Some(map.next_value()?),
+                },
+                "kind" => return Err(A::Error::duplicate_field("kind")),
+                "data" => return Err(A::Error::duplicate_field("data")),
+                _ => {
+                    map.next_value::<serde::de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        if let Some(data) = data {
+            return Ok(data);
+        }
+        let kind = kind.ok_or_else(|| A::Error::missing_field("kind"))?;
+        let data = buffered_data.ok_or_else(|| A::Error::missing_field("data"))?;
+        serde::de::DeserializeSeed::deserialize(ThingDataSeed { kind }, data)
+            .map_err(A::Error::custom)
+    }
+}
+
+/// Deserializes the data of a thing of a known kind.
+struct ThingDataSeed {
+    kind: Box<str>,
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for ThingDataSeed {
+    type Value = ThingData;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::Deserialize;
+
+        let data = match &*self.kind {
+            "Listing" => ThingData::Listing(Deserialize::deserialize(deserializer)?),
+            "more" => ThingData::More(Deserialize::deserialize(deserializer)?),
+            "t1" => ThingData::Comment(Deserialize::deserialize(deserializer)?),
+            "t3" => ThingData::Link(Deserialize::deserialize(deserializer)?),
+            "t4" => ThingData::Message(Deserialize::deserialize(deserializer)?),
+            "t5" => ThingData::Subreddit(Deserialize::deserialize(deserializer)?),
+            "t6" => ThingData::Trophy(Deserialize::deserialize(deserializer)?),
+            "TrophyList" => ThingData::TrophyList(Deserialize::deserialize(deserializer)?),
+            "LabeledMulti" => ThingData::LabeledMulti(Deserialize::deserialize(deserializer)?),
+            "wikipage" => ThingData::WikiPage(Deserialize::deserialize(deserializer)?),
+            "LiveUpdateEvent" => ThingData::LiveThread(Deserialize::deserialize(deserializer)?),
+            "LiveUpdate" => ThingData::LiveUpdate(Deserialize::deserialize(deserializer)?),
+            _ => ThingData::Unknown {
+                kind: self.kind,
+                data: Deserialize::deserialize(deserializer)?,
+            },
+        };
+
+        Ok(data)
+    }
 }
 
 /// Used to paginate content that is too long to display in one go.
@@ -92,7 +348,7 @@ impl ThingData {
 /// A listing's kind will always be Listing and its data will be a List of things.
 /// kind == "Listing"
 /// See https://github.com/reddit-archive/reddit/wiki/JSON#listing
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Listing {
     /// The fullname of the listing that follows before this page. null if there is no previous page.
     pub before: Option<Box<str>>,
@@ -109,9 +365,54 @@ pub struct Listing {
     pub children: Vec<Thing>,
 }
 
+impl Listing {
+    /// Take the children of this listing, dropping its cursors.
+    pub fn into_children(self) -> Vec<Thing> {
+        self.children
+    }
+
+    /// Append the children of the next page of this listing, skipping children already in this listing.
+    ///
+    /// Children are compared by fullname, and children without one are always appended.
+    /// The `after` cursor is taken from the other page, so it points past the combined children.
+    pub fn merge(&mut self, other: Listing) {
+        let mut seen: std::collections::HashSet<Box<str>> = self
+            .children
+            .iter()
+            .filter_map(|thing| thing.fullname())
+            .map(Box::from)
+            .collect();
+        self.children
+            .extend(other.children.into_iter().filter(|thing| {
+                thing
+                    .fullname()
+                    .is_none_or(|fullname| seen.insert(fullname.into()))
+            }));
+        self.after = other.after;
+    }
+}
+
+impl IntoIterator for Listing {
+    type Item = Thing;
+    type IntoIter = std::vec::IntoIter<Thing>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Listing {
+    type Item = &'a Thing;
+    type IntoIter = std::slice::Iter<'a, Thing>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.iter()
+    }
+}
+
 /// Implementation
 /// See: https://github.com/reddit-archive/reddit/wiki/JSON#votable-implementation
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Votable {
     /// the number of upvotes. (includes own)
     pub ups: i64,
@@ -121,12 +422,101 @@ pub struct Votable {
 
     /// true if thing is liked by the user, false if thing is disliked, null if the user has not voted or you are not logged in.
     /// Certain languages such as Java may need to use a boolean wrapper that supports null assignment.
+    ///
+    /// When this is flattened into a [`Link`] or [`Comment`], their own `likes` field takes the value, so this is always `None`.
+    /// This is not serialized, as it would duplicate that field.
+    #[serde(default, skip_serializing)]
     pub likes: Option<bool>,
 }
 
+impl Votable {
+    /// Get how the logged in user has voted.
+    ///
+    /// This defers to the `likes` field of this votable, which is always empty when flattened into a [`Link`] or [`Comment`],
+    /// so use [`Link::my_vote`] or [`Comment::my_vote`] for them.
+    pub fn my_vote(&self) -> VoteDirection {
+        self.likes.into()
+    }
+}
+
+/// How a user has voted on, or is voting on, a post or comment
+/// See https://www.reddit.com/dev/api#POST_api_vote
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VoteDirection {
+    /// An upvote
+    Up,
+
+    /// No vote. Voting this way removes an existing vote
+    #[default]
+    Neutral,
+
+    /// A downvote
+    Down,
+}
+
+impl VoteDirection {
+    /// Get the value reddit expects for this vote direction.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Up => "1",
+            Self::Neutral => "0",
+            Self::Down => "-1",
+        }
+    }
+
+    /// Get the change this vote makes to a score.
+    pub fn as_score(self) -> i64 {
+        match self {
+            Self::Up => 1,
+            Self::Neutral => 0,
+            Self::Down => -1,
+        }
+    }
+
+    /// Get this vote direction as reddit's `likes` fields represent it.
+    pub fn to_likes(self) -> Option<bool> {
+        match self {
+            Self::Up => Some(true),
+            Self::Neutral => None,
+            Self::Down => Some(false),
+        }
+    }
+
+    /// Returns `true` if this is an upvote.
+    pub fn is_up(self) -> bool {
+        matches!(self, Self::Up)
+    }
+
+    /// Returns `true` if this is a downvote.
+    pub fn is_down(self) -> bool {
+        matches!(self, Self::Down)
+    }
+
+    /// Returns `true` if this is not a vote.
+    pub fn is_neutral(self) -> bool {
+        matches!(self, Self::Neutral)
+    }
+}
+
+impl From<Option<bool>> for VoteDirection {
+    fn from(likes: Option<bool>) -> Self {
+        match likes {
+            Some(true) => Self::Up,
+            None => Self::Neutral,
+            Some(false) => Self::Down,
+        }
+    }
+}
+
+impl From<VoteDirection> for Option<bool> {
+    fn from(direction: VoteDirection) -> Self {
+        direction.to_likes()
+    }
+}
+
 /// Implementation
 /// See https://github.com/reddit-archive/reddit/wiki/JSON#created-implementation
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Created {
     // TODO: use chrono?
     /// the time of creation in local epoch-second format. ex: 1331042771.0
@@ -139,22 +529,46 @@ pub struct Created {
 /// Implements votable | created
 /// kind == "t1"
 /// See https://github.com/reddit-archive/reddit/wiki/JSON#comment-implements-votable--created
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Comment {
     /// who approved this comment. null if nobody or you are not a mod
-    pub approved_by: Option<Box<str>>,
+    pub approved_by: Option<CompactStr>,
+
+    /// when this comment was approved, in UTC epoch-second format. null if not approved or you are not a mod
+    pub approved_at_utc: Option<f64>,
 
     /// the account name of the poster
-    pub author: Box<str>,
+    pub author: CompactStr,
 
     /// the CSS class of the author's flair. subreddit specific
-    pub author_flair_css_class: Option<Box<str>>,
+    pub author_flair_css_class: Option<CompactStr>,
 
     /// the text of the author's flair. subreddit specific
-    pub author_flair_text: Option<Box<str>>,
+    pub author_flair_text: Option<CompactStr>,
+
+    /// true if the author has reddit premium. null for deleted authors
+    pub author_premium: Option<bool>,
+
+    /// true if the logged in user has blocked the author. Only present in some listings
+    pub author_is_blocked: Option<bool>,
+
+    /// true if it is the anniversary of the creation of the author's account. Only present if true
+    pub author_cakeday: Option<bool>,
 
     /// who removed this comment. null if nobody or you are not a mod
-    pub banned_by: Option<Box<str>>,
+    pub banned_by: Option<CompactStr>,
+
+    /// when this comment was removed, in UTC epoch-second format. null if not removed or you are not a mod
+    pub banned_at_utc: Option<f64>,
+
+    /// the note a moderator left when removing this comment. null if there is none or you are not a mod
+    pub mod_note: Option<Box<str>>,
+
+    /// the title of the removal reason used to remove this comment. null if there is none or you are not a mod
+    pub mod_reason_title: Option<Box<str>>,
+
+    /// who added the removal reason. null if there is none or you are not a mod
+    pub mod_reason_by: Option<CompactStr>,
 
     /// the raw text.
     /// this is the unformatted text which includes the raw markup characters such as ** for bold. <, >, and & are escaped.
@@ -173,15 +587,23 @@ pub struct Comment {
     /// the number of times this comment received reddit gold
     pub gilded: u64,
 
+    /// the awards this comment received
+    #[serde(default)]
+    pub all_awardings: Vec<Awarding>,
+
+    /// the number of each kind of gilding received
+    #[serde(default)]
+    pub gildings: Gildings,
+
     /// how the logged-in user has voted on the comment - True = upvoted, False = downvoted, null = no vote
     pub likes: Option<bool>,
 
     /// present if the comment is being displayed outside its thread (user pages, /r/subreddit/comments/.json, etc.).
     /// Contains the author of the parent link
-    pub link_author: Option<Box<str>>,
+    pub link_author: Option<CompactStr>,
 
     /// ID of the link this comment is in
-    pub link_id: Box<str>,
+    pub link_id: CompactStr,
 
     /// present if the comment is being displayed outside its thread (user pages, /r/subreddit/comments/.json, etc.).
     /// Contains the title of the parent link
@@ -194,12 +616,31 @@ pub struct Comment {
     /// how many times this comment has been reported, null if not a mod
     pub num_reports: Option<u64>,
 
+    /// the reports of this comment by users. empty if none or you are not a mod
+    #[serde(default)]
+    pub user_reports: Vec<UserReport>,
+
+    /// the reports of this comment by moderators. empty if none or you are not a mod
+    #[serde(default)]
+    pub mod_reports: Vec<ModReport>,
+
+    /// the reasons this comment was reported for. null if you are not a mod
+    pub report_reasons: Option<Vec<Box<str>>>,
+
     /// ID of the thing this comment is a reply to, either the link or a comment in it
-    pub parent_id: Box<str>,
+    pub parent_id: CompactStr,
+
+    /// A list of replies to this comment.
+    /// Reddit sends an empty string instead of a listing if there are no replies, which is parsed as None
+    #[serde(default, with = "replies")]
+    pub replies: Option<Box<Listing>>,
+
+    /// Metadata about the images and gifs embedded in the body of this comment, keyed by media id
+    pub media_metadata: Option<std::collections::HashMap<Box<str>, MediaMetadata>>,
 
-    // TODO: Find out why this is a string sometimes
-    // /// A list of replies to this comment
-    // pub replies: Thing,
+    /// how deeply this comment is nested in its thread, starting at 0 for top-level comments.
+    /// only present if the comment was loaded as part of a thread
+    pub depth: Option<u32>,
 
     /// true if this post is saved by the logged in user
     pub saved: bool,
@@ -211,17 +652,17 @@ pub struct Comment {
     pub score_hidden: bool,
 
     /// subreddit of thing excluding the /r/ prefix. "pics"
-    pub subreddit: Box<str>,
+    pub subreddit: CompactStr,
 
     /// the id of the subreddit in which the thing is locatedss
-    pub subreddit_id: Box<str>,
+    pub subreddit_id: CompactStr,
 
     /// to allow determining whether they have been distinguished by moderators/admins.
     /// null = not distinguished.
     /// moderator = the green \[M\].
     /// admin = the red \[A\].
     /// special = various other special distinguishes http://redd.it/19ak1b
-    pub distinguished: Option<Box<str>>,
+    pub distinguished: Option<CompactStr>,
 
     /// Voting Implementation
     #[serde(flatten)]
@@ -230,30 +671,59 @@ pub struct Comment {
     /// Created Implementation
     #[serde(flatten)]
     pub created: Created,
+
     // Experimentally determined fields
     // TODO: These are VERY best-effort, but i should still try to document what i can
+    /// this comment's identifier, e.g. "c3v7f8u"
+    pub id: CompactStr,
+
+    /// Fullname of comment, e.g. "t1_c3v7f8u"
+    pub name: CompactStr,
+
+    /// the relative url of this comment, e.g. "/r/rust/comments/abc123/title/c3v7f8u/"
+    #[serde(default)]
+    pub permalink: Box<str>,
+
+    /// true if the author of this comment is the author of the post
+    #[serde(default)]
+    pub is_submitter: bool,
+
+    /// true if this comment is stickied to the top of the thread by a moderator
+    #[serde(default)]
+    pub stickied: bool,
+
+    /// true if this comment is collapsed by default
+    #[serde(default)]
+    pub collapsed: bool,
+
+    /// why this comment is collapsed, like "comment score below threshold". null if not collapsed or no reason is given
+    pub collapsed_reason: Option<Box<str>>,
+
+    /// 1 if this comment has many upvotes and downvotes, 0 otherwise
+    #[serde(default)]
+    pub controversiality: u8,
 }
 
 /// Implements votable | created
 /// kind == "t3"
 /// See https://github.com/reddit-archive/reddit/wiki/JSON#link-implements-votable--created
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Link {
     /// the account name of the poster. null if this is a promotional link
-    pub author: Box<str>,
+    pub author: CompactStr,
 
     /// the CSS class of the author's flair. subreddit specific
-    pub author_flair_css_class: Option<Box<str>>,
+    pub author_flair_css_class: Option<CompactStr>,
 
     /// the text of the author's flair. subreddit specific
-    pub author_flair_text: Option<Box<str>>,
+    pub author_flair_text: Option<CompactStr>,
 
     /// probably always returns false
     pub clicked: bool,
 
     /// the domain of this link.
     /// Self posts will be self.<subreddit> while other examples include en.wikipedia.org and s3.amazon.com
-    pub domain: Box<str>,
+    pub domain: CompactStr,
 
     /// true if the post is hidden by the logged in user. false if not logged in or not hidden.
     pub hidden: bool,
@@ -265,10 +735,10 @@ pub struct Link {
     pub likes: Option<bool>,
 
     /// the CSS class of the link's flair.
-    pub link_flair_css_class: Option<Box<str>>,
+    pub link_flair_css_class: Option<CompactStr>,
 
     /// the text of the link's flair.
-    pub link_flair_text: Option<Box<str>>,
+    pub link_flair_text: Option<CompactStr>,
 
     /// whether the link is locked (closed to new comments) or not.
     pub locked: bool,
@@ -302,6 +772,12 @@ pub struct Link {
     /// The points score is correct, but the vote totals are "fuzzed".
     pub score: i64,
 
+    /// the fraction of votes on the link that are upvotes, rounded to 2 decimal places. ex: 0.98
+    ///
+    /// Some responses, like crosspost parents, omit this.
+    #[serde(default)]
+    pub upvote_ratio: Option<f64>,
+
     /// the raw text.
     /// this is the unformatted text which includes the raw markup characters such as ** for bold.
     /// <, >, and & are escaped.
@@ -316,10 +792,10 @@ pub struct Link {
     pub selftext_html: Option<Box<str>>,
 
     /// subreddit of thing excluding the /r/ prefix. "pics"
-    pub subreddit: Box<str>,
+    pub subreddit: CompactStr,
 
     /// the id of the subreddit in which the thing is located
-    pub subreddit_id: Box<str>,
+    pub subreddit_id: CompactStr,
 
     /// full URL to the thumbnail for this link;
     /// "self" if this is a self post;
@@ -344,7 +820,7 @@ pub struct Link {
     /// admin = the red \[A\].
     /// special = various other special distinguishes
     /// http://bit.ly/ZYI47B
-    pub distinguished: Option<Box<str>>,
+    pub distinguished: Option<CompactStr>,
 
     /// true if the post is set as the sticky in its subreddit.
     pub stickied: bool,
@@ -360,11 +836,21 @@ pub struct Link {
     // Experimentally determined fields
     // TODO: These are VERY best-effort, but i should still try to document what i can
     pub archived: bool,
-    pub author_flair_template_id: Option<Box<str>>,
-    pub author_flair_text_color: Option<Box<str>>,
-    pub author_flair_type: Option<Box<str>>,
-    pub author_fullname: Option<Box<str>>,
+    pub author_flair_template_id: Option<CompactStr>,
+    pub author_flair_text_color: Option<CompactStr>,
+    pub author_flair_type: Option<CompactStr>,
+    pub author_fullname: Option<CompactStr>,
     pub author_patreon_flair: Option<bool>,
+
+    /// true if the author has reddit premium. null for deleted authors
+    pub author_premium: Option<bool>,
+
+    /// true if the logged in user has blocked the author. Only present in some listings
+    pub author_is_blocked: Option<bool>,
+
+    /// true if it is the anniversary of the creation of the author's account. Only present if true
+    pub author_cakeday: Option<bool>,
+
     pub can_gild: bool,
     pub can_mod_post: bool,
     pub contest_mode: bool,
@@ -373,8 +859,17 @@ pub struct Link {
     pub crosspost_parent_list: Option<Vec<Link>>,
 
     pub gilded: u64,
+
+    /// the awards this post received
+    #[serde(default)]
+    pub all_awardings: Vec<Awarding>,
+
+    /// the number of each kind of gilding received
+    #[serde(default)]
+    pub gildings: Gildings,
+
     pub hide_score: bool,
-    pub id: Box<str>,
+    pub id: CompactStr,
     pub is_crosspostable: bool,
     pub is_meta: bool,
     pub is_original_content: bool,
@@ -384,13 +879,13 @@ pub struct Link {
     /// Returns true if its a video
     pub is_video: bool,
 
-    pub link_flair_text_color: Option<Box<str>>,
-    pub link_flair_type: Box<str>,
+    pub link_flair_text_color: Option<CompactStr>,
+    pub link_flair_type: CompactStr,
     pub media_only: bool,
-    pub name: Box<str>,
+    pub name: CompactStr,
     pub no_follow: bool,
     pub num_crossposts: u64,
-    pub parent_whitelist_status: Option<Box<str>>,
+    pub parent_whitelist_status: Option<CompactStr>,
 
     /// Whether this post is pinned
     pub pinned: bool,
@@ -398,79 +893,2098 @@ pub struct Link {
     /// A "hint" about what this post may be
     pub post_hint: Option<PostHint>,
 
+    /// Previews of the images of this post, if any
+    pub preview: Option<Preview>,
+
+    /// Metadata about the images of a gallery or the media of a self post, keyed by media id
+    pub media_metadata: Option<std::collections::HashMap<Box<str>, MediaMetadata>>,
+
+    /// The order and captions of the images of a gallery
+    pub gallery_data: Option<GalleryData>,
+
+    /// A summary of the subreddit of this link. Only present if `sr_detail` was requested
+    pub sr_detail: Option<SubredditDetail>,
+
     pub pwls: Option<u64>,
     pub quarantine: bool,
+
+    /// Why this post was removed, like "moderator", "deleted", or "reddit". null if not removed
+    pub removed_by_category: Option<CompactStr>,
+
+    /// who removed this post. null if nobody or you are not a mod
+    pub banned_by: Option<CompactStr>,
+
+    /// when this post was removed, in UTC epoch-second format. null if not removed or you are not a mod
+    pub banned_at_utc: Option<f64>,
+
+    /// who approved this post. null if nobody or you are not a mod
+    pub approved_by: Option<CompactStr>,
+
+    /// when this post was approved, in UTC epoch-second format. null if not approved or you are not a mod
+    pub approved_at_utc: Option<f64>,
+
+    /// the note a moderator left when removing this post. null if there is none or you are not a mod
+    pub mod_note: Option<Box<str>>,
+
+    /// the title of the removal reason used to remove this post. null if there is none or you are not a mod
+    pub mod_reason_title: Option<Box<str>>,
+
+    /// who added the removal reason. null if there is none or you are not a mod
+    pub mod_reason_by: Option<CompactStr>,
+
+    /// the reports of this post by users. empty if none or you are not a mod
+    #[serde(default)]
+    pub user_reports: Vec<UserReport>,
+
+    /// the reports of this post by moderators. empty if none or you are not a mod
+    #[serde(default)]
+    pub mod_reports: Vec<ModReport>,
+
+    /// the reasons this post was reported for. null if you are not a mod
+    pub report_reasons: Option<Vec<Box<str>>>,
+
     pub send_replies: bool,
 
     /// Whether this post has a spoiler
     pub spoiler: bool,
 
-    pub subreddit_name_prefixed: Box<str>,
+    pub subreddit_name_prefixed: CompactStr,
     pub subreddit_subscribers: u64,
-    pub subreddit_type: Box<str>,
-    pub suggested_sort: Option<Box<str>>,
+    pub subreddit_type: CompactStr,
+    pub suggested_sort: Option<CompactStr>,
     pub thumbnail_height: Option<u32>,
     pub thumbnail_width: Option<u32>,
     pub visited: bool,
-    pub whitelist_status: Option<Box<str>>,
+    pub whitelist_status: Option<CompactStr>,
     pub wls: Option<u32>,
 }
 
+/// A private message, or an item in the inbox.
+///
+/// Comment replies and username mentions in the inbox are also parsed as messages,
+/// with `was_comment` set to `true`.
+/// kind == "t4"
+/// See https://github.com/reddit-archive/reddit/wiki/JSON#message-implements-created
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Message {
+    /// this item's identifier, e.g. "8xwlg"
+    pub id: Box<str>,
+
+    /// Fullname of message, e.g. "t4_8xwlg"
+    pub name: Box<str>,
+
+    /// the account name of the sender. null if sent by a subreddit
+    pub author: Option<Box<str>>,
+
+    /// the account name of the recipient, or the subreddit name prefixed with "#"
+    pub dest: Box<str>,
+
+    /// the subject of the message
+    pub subject: Box<str>,
+
+    /// the raw markdown text of the message
+    pub body: Box<str>,
+
+    /// the formatted escaped HTML text of the message
+    pub body_html: Box<str>,
+
+    /// true if the message has not been read
+    pub new: bool,
+
+    /// true if this is a comment reply or mention, rather than a private message
+    pub was_comment: bool,
+
+    /// the kind of inbox item, e.g. "username_mention", "comment_reply", or "unknown"
+    #[serde(rename = "type")]
+    pub message_type: Option<Box<str>>,
+
+    /// the permalink of the comment, if this was a comment. Empty otherwise.
+    pub context: Box<str>,
+
+    /// the fullname of the thing this is a reply to. null if this starts a conversation
+    pub parent_id: Option<Box<str>>,
+
+    /// the fullname of the first message in the conversation. null if this starts a conversation
+    pub first_message_name: Option<Box<str>>,
+
+    /// the title of the link, if this was a comment
+    pub link_title: Option<Box<str>>,
+
+    /// the subreddit of the comment or subreddit message, excluding the /r/ prefix. null otherwise
+    pub subreddit: Option<Box<str>>,
+
+    /// to allow determining whether they have been distinguished by moderators/admins.
+    /// null = not distinguished.
+    pub distinguished: Option<Box<str>>,
+
+    /// the replies to this message, if this is the first message of a conversation.
+    /// reddit only includes these in the sent and messages inboxes
+    #[serde(default, with = "replies")]
+    pub replies: Option<Box<Listing>>,
+
+    /// Created Implementation
+    #[serde(flatten)]
+    pub created: Created,
+}
+
 /// kind == "more"
 /// See https://github.com/reddit-archive/reddit/wiki/JSON#more
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct More {
     /// A list of String ids that are the additional things that can be downloaded but are not because there are too many to list.
     pub children: Vec<Box<str>>,
 }
 
-/// Info on what the post may contain
-#[derive(Debug, serde::Deserialize, PartialEq, Eq)]
-pub enum PostHint {
-    /// The post is an image
-    #[serde(rename = "image")]
-    Image,
+/// An award given to a user, displayed on their profile
+/// kind == "t6"
+/// See https://www.reddit.com/dev/api#GET_api_v1_user_{username}_trophies
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Trophy {
+    /// A 70x70 icon url for the trophy
+    pub icon_70: Box<str>,
 
-    /// The post is a link
-    #[serde(rename = "link")]
-    Link,
+    /// A 40x40 icon url for the trophy
+    pub icon_40: Box<str>,
 
-    /// A video hosted on reddit
-    #[serde(rename = "hosted:video")]
-    HostedVideo,
+    /// The name of the trophy, e.g. "Verified Email"
+    pub name: Box<str>,
 
-    #[serde(rename = "rich:video")]
-    RichVideo,
+    /// The id of the trophy. null for some trophies
+    pub id: Option<Box<str>>,
 
-    #[serde(rename = "self")]
-    DataSelf,
+    /// The id of the award this trophy is for. null for some trophies
+    pub award_id: Option<Box<str>>,
 
-    #[serde(rename = "gallery")]
-    Gallery,
+    /// the time this trophy was granted in UTC epoch-second format. null for some trophies
+    pub granted_at: Option<f64>,
+
+    /// A url relating to the trophy. null if not present
+    pub url: Option<Box<str>>,
+
+    /// A description of the trophy. null if not present
+    pub description: Option<Box<str>>,
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+/// A list of trophies
+/// kind == "TrophyList"
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct TrophyList {
+    /// The trophies in this list. These should all be of kind "t6".
+    pub trophies: Vec<Thing>,
+}
 
-    const SUBREDDIT_SAMPLE_1: &str = include_str!("../test_data/subreddit_dankmemes.json");
-    const SUBREDDIT_SAMPLE_2: &str = include_str!("../test_data/subreddit_cromch.json");
-    const SUBREDDIT_SAMPLE_3: &str = include_str!("../test_data/subreddit_cuddleroll.json");
-    const SUBREDDIT_SAMPLE_4: &str = include_str!("../test_data/subreddit_cursed_images.json");
+/// A collection of subreddits, owned by a user
+/// kind == "LabeledMulti"
+/// See https://www.reddit.com/dev/api#section_multis
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Multireddit {
+    /// the name of the multireddit, used in its path, e.g. "cute"
+    pub name: Box<str>,
 
-    const COMMENT_SAMPLE_1: &str = include_str!("../test_data/comment_h966lq.json");
-    const COMMENT_SAMPLE_2: &str = include_str!("../test_data/comment_h8p0py.json");
+    /// the human readable name of the multireddit
+    pub display_name: Box<str>,
 
-    #[test]
-    fn parse_subreddit_1() {
-        let res = serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_1).unwrap();
-        dbg!(res);
-    }
+    /// the path of the multireddit, e.g. "/user/spez/m/cute"
+    pub path: Box<str>,
 
-    #[test]
-    fn parse_subreddit_2() {
-        let res = serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_2).unwrap();
-        dbg!(res);
+    /// the account name of the owner
+    pub owner: Box<str>,
+
+    /// the fullname of the owner, e.g. "t2_1w72"
+    pub owner_id: Box<str>,
+
+    /// the raw markdown description
+    pub description_md: Box<str>,
+
+    /// the formatted escaped HTML description
+    pub description_html: Box<str>,
+
+    /// the subreddits in this multireddit
+    pub subreddits: Vec<MultiredditSubreddit>,
+
+    /// who can see this multireddit
+    pub visibility: MultiredditVisibility,
+
+    /// the path of the multireddit this was copied from. null if it was not copied
+    pub copied_from: Option<Box<str>>,
+
+    /// an icon url for the multireddit
+    pub icon_url: Box<str>,
+
+    /// a hex color for the multireddit, e.g. "#cee3f8"
+    pub key_color: Box<str>,
+
+    /// the number of users subscribed to this multireddit
+    pub num_subscribers: u64,
+
+    /// true if any subreddit in this multireddit is NSFW
+    pub over_18: bool,
+
+    /// true if the logged in user can edit this multireddit
+    pub can_edit: bool,
+
+    /// true if the logged in user has favorited this multireddit
+    pub is_favorited: bool,
+
+    /// true if the logged in user is subscribed to this multireddit
+    pub is_subscriber: bool,
+
+    /// Created Implementation
+    #[serde(flatten)]
+    pub created: Created,
+}
+
+/// A subreddit in a multireddit
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MultiredditSubreddit {
+    /// subreddit name excluding the /r/ prefix. "pics"
+    pub name: Box<str>,
+}
+
+/// Who can see a multireddit
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum MultiredditVisibility {
+    /// Only the owner can see this multireddit
+    #[serde(rename = "private")]
+    Private,
+
+    /// Anyone can see this multireddit
+    #[serde(rename = "public")]
+    Public,
+
+    /// Anyone with the link can see this multireddit, but it is not listed on the owner's profile
+    #[serde(rename = "hidden")]
+    Hidden,
+}
+
+/// A page of a subreddit's wiki
+/// kind == "wikipage"
+/// See https://www.reddit.com/dev/api#GET_wiki_{page}
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct WikiPage {
+    /// the raw markdown content of the page
+    pub content_md: Box<str>,
+
+    /// the formatted escaped HTML content of the page
+    pub content_html: Box<str>,
+
+    /// true if the logged in user may edit this page
+    pub may_revise: bool,
+
+    /// the time of the latest revision in UTC epoch-second format
+    pub revision_date: f64,
+
+    // TODO: Finish type, this is a "t2" thing
+    /// the account that made the latest revision. null if unknown
+    pub revision_by: Option<serde_json::Value>,
+
+    /// the id of the latest revision, a uuid. null if unknown
+    pub revision_id: Option<Box<str>>,
+
+    /// the reason given for the latest revision. null if not present
+    pub reason: Option<Box<str>>,
+}
+
+impl Link {
+    /// Returns `true` if this post was removed by a moderator, reddit, or a filter.
+    ///
+    /// Moderators can see removed posts as usual, so `banned_by` is used for them.
+    pub fn is_removed(&self) -> bool {
+        match self.removed_by_category.as_deref() {
+            Some("deleted" | "author") => false,
+            Some(_) => true,
+            None => self.banned_by.is_some() || &*self.selftext == "[removed]",
+        }
+    }
+
+    /// Returns `true` if this post was deleted by its author.
+    pub fn is_deleted(&self) -> bool {
+        match self.removed_by_category.as_deref() {
+            Some("deleted" | "author") => true,
+            Some(_) => false,
+            None => &*self.author == "[deleted]" || &*self.selftext == "[deleted]",
+        }
+    }
+
+    /// Returns `true` if the author has reddit premium.
+    pub fn is_author_premium(&self) -> bool {
+        self.author_premium.unwrap_or(false)
+    }
+
+    /// Returns `true` if the logged in user has blocked the author.
+    pub fn is_author_blocked(&self) -> bool {
+        self.author_is_blocked.unwrap_or(false)
+    }
+
+    /// Returns `true` if it is the cake day of the author.
+    pub fn is_author_cakeday(&self) -> bool {
+        self.author_cakeday.unwrap_or(false)
+    }
+
+    /// Get how the logged in user has voted on this.
+    pub fn my_vote(&self) -> VoteDirection {
+        self.likes.into()
+    }
+
+    /// Estimate the number of upvotes of this post from its score and upvote ratio.
+    ///
+    /// Returns `None` if the votes cannot be estimated, like when the score is 0 or the ratio is missing.
+    /// Since the ratio is rounded, the estimate is rough for posts with many votes.
+    pub fn estimated_upvotes(&self) -> Option<u64> {
+        estimate_votes(self.score, self.upvote_ratio?).map(|(upvotes, _)| upvotes)
+    }
+
+    /// Estimate the number of downvotes of this post from its score and upvote ratio.
+    ///
+    /// See [`Link::estimated_upvotes`].
+    pub fn estimated_downvotes(&self) -> Option<u64> {
+        estimate_votes(self.score, self.upvote_ratio?).map(|(_, downvotes)| downvotes)
+    }
+}
+
+/// Estimate the upvotes and downvotes of a score, where ratio is the fraction of votes that are upvotes.
+///
+/// With `u` upvotes and `d` downvotes, `score = u - d` and `ratio = u / (u + d)`,
+/// so the total number of votes is `score / (2 * ratio - 1)`.
+fn estimate_votes(score: i64, ratio: f64) -> Option<(u64, u64)> {
+    // A score of 0 means there are as many upvotes as downvotes, but not how many.
+    if score == 0 || !(0.0..=1.0).contains(&ratio) {
+        return None;
+    }
+
+    let total = score as f64 / (2.0 * ratio - 1.0);
+    if !total.is_finite() || total < 0.0 {
+        return None;
+    }
+    let upvotes = (ratio * total).round();
+    let downvotes = (total - upvotes).round().max(0.0);
+
+    Some((upvotes as u64, downvotes as u64))
+}
+
+impl Comment {
+    /// Returns `true` if this comment was removed by a moderator, reddit, or a filter.
+    ///
+    /// Moderators can see removed comments as usual, so `banned_by` is used for them.
+    pub fn is_removed(&self) -> bool {
+        self.banned_by.is_some() || &*self.body == "[removed]"
+    }
+
+    /// Returns `true` if this comment was deleted by its author.
+    pub fn is_deleted(&self) -> bool {
+        &*self.body == "[deleted]"
+    }
+
+    /// Returns `true` if the author has reddit premium.
+    pub fn is_author_premium(&self) -> bool {
+        self.author_premium.unwrap_or(false)
+    }
+
+    /// Returns `true` if the logged in user has blocked the author.
+    pub fn is_author_blocked(&self) -> bool {
+        self.author_is_blocked.unwrap_or(false)
+    }
+
+    /// Returns `true` if it is the cake day of the author.
+    pub fn is_author_cakeday(&self) -> bool {
+        self.author_cakeday.unwrap_or(false)
+    }
+
+    /// Get how the logged in user has voted on this.
+    pub fn my_vote(&self) -> VoteDirection {
+        self.likes.into()
+    }
+}
+
+impl WikiPage {
+    /// Get the account name of the user that made the latest revision
+    pub fn revision_by_name(&self) -> Option<&str> {
+        self.revision_by
+            .as_ref()?
+            .get("data")?
+            .get("name")?
+            .as_str()
+    }
+}
+
+/// A rule of a subreddit
+/// See https://www.reddit.com/dev/api#GET_r_{subreddit}_about_rules
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SubredditRule {
+    /// what this rule applies to
+    pub kind: SubredditRuleKind,
+
+    /// a short name for the rule, e.g. "No reposts"
+    pub short_name: Box<str>,
+
+    /// the raw markdown description of the rule
+    pub description: Box<str>,
+
+    /// the formatted escaped HTML description of the rule. Null if not present.
+    pub description_html: Option<Box<str>>,
+
+    /// the reason shown when reporting something for breaking this rule
+    pub violation_reason: Box<str>,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_utc: f64,
+
+    /// the position of this rule, starting at 0
+    pub priority: u32,
+}
+
+/// What a subreddit rule applies to
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum SubredditRuleKind {
+    /// The rule applies to posts
+    #[serde(rename = "link")]
+    Link,
+
+    /// The rule applies to comments
+    #[serde(rename = "comment")]
+    Comment,
+
+    /// The rule applies to posts and comments
+    #[serde(rename = "all")]
+    All,
+}
+
+/// Karma earned in a single subreddit
+/// See https://www.reddit.com/dev/api#GET_api_v1_me_karma
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SubredditKarma {
+    /// subreddit name excluding the /r/ prefix. "pics"
+    #[serde(rename = "sr")]
+    pub subreddit: Box<str>,
+
+    /// the karma earned from posts in this subreddit
+    pub link_karma: i64,
+
+    /// the karma earned from comments in this subreddit
+    pub comment_karma: i64,
+}
+
+/// An ordered collection of posts in a subreddit, like the parts of a series
+/// See https://www.reddit.com/dev/api#section_collections
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Collection {
+    /// the id of the collection, a uuid
+    pub collection_id: Box<str>,
+
+    /// the fullname of the subreddit of the collection, e.g. "t5_2qh1o"
+    pub subreddit_id: Box<str>,
+
+    /// the title of the collection
+    pub title: Box<str>,
+
+    /// the description of the collection
+    pub description: Box<str>,
+
+    /// the account name of the user that created the collection
+    pub author_name: Box<str>,
+
+    /// the fullname of the account of the user that created the collection
+    pub author_id: Box<str>,
+
+    /// the full url of the collection
+    pub permalink: Box<str>,
+
+    /// the fullnames of the posts in the collection, in order
+    #[serde(default)]
+    pub link_ids: Vec<Box<str>>,
+
+    /// a listing of the posts in the collection, in order. only present if the links were requested
+    pub sorted_links: Option<Thing>,
+
+    /// how the collection is displayed
+    pub display_layout: Option<CollectionDisplayLayout>,
+
+    /// the fullname of the first post of the collection. null if the collection is empty
+    pub primary_link_id: Option<Box<str>>,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_at_utc: f64,
+
+    /// the time of the last update in UTC epoch-second format
+    pub last_update_utc: f64,
+}
+
+impl Collection {
+    /// Get the posts in this collection, in order.
+    ///
+    /// This is empty if the links were not requested.
+    pub fn links(&self) -> impl Iterator<Item = &Link> {
+        self.sorted_links
+            .iter()
+            .filter_map(|thing| thing.data.as_listing())
+            .flat_map(|listing| listing.children.iter())
+            .filter_map(|thing| thing.data.as_link())
+    }
+}
+
+/// How a collection is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CollectionDisplayLayout {
+    /// The posts are shown as a list, in order
+    Timeline,
+
+    /// The posts are shown as a grid of thumbnails
+    Gallery,
+}
+
+impl CollectionDisplayLayout {
+    /// Get the value reddit expects for this layout.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Timeline => "TIMELINE",
+            Self::Gallery => "GALLERY",
+        }
+    }
+}
+
+/// The name, avatar, and karma of an account
+/// See https://www.reddit.com/dev/api#GET_api_user_data_by_account_ids
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PartialUser {
+    /// the name of the account, excluding the u/ prefix
+    pub name: Box<str>,
+
+    /// the time the account was created in UTC epoch-second format
+    pub created_utc: f64,
+
+    /// the karma from posts
+    pub link_karma: i64,
+
+    /// the karma from comments
+    pub comment_karma: i64,
+
+    /// the url of the avatar of the account. This is html escaped unless raw_json is used
+    pub profile_img: Box<str>,
+
+    /// the color of the profile of the account as a hex string. Empty if not set
+    pub profile_color: Box<str>,
+
+    /// true if the profile of the account is marked as NSFW
+    pub profile_over_18: bool,
+}
+
+/// A custom emoji that can be used in the flair of a subreddit
+/// See https://www.reddit.com/dev/api#GET_api_v1_{subreddit}_emojis_all
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Emoji {
+    /// the name of the emoji, used like `:name:` in flair text.
+    /// reddit sends this as the key of the emoji, so it is filled in after parsing
+    #[serde(default)]
+    pub name: Box<str>,
+
+    /// the url of the image of the emoji
+    pub url: Box<str>,
+
+    /// true if users can use the emoji in their user flair
+    pub user_flair_allowed: bool,
+
+    /// true if users can use the emoji in post flair
+    pub post_flair_allowed: bool,
+
+    /// true if only moderators can use the emoji
+    pub mod_flair_only: bool,
+
+    /// the fullname of the account that created the emoji. null for reddit's snoomojis
+    pub created_by: Option<Box<str>>,
+}
+
+/// Where a custom emoji can be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmojiPermissions {
+    /// true if users can use the emoji in their user flair
+    pub user_flair_allowed: bool,
+
+    /// true if users can use the emoji in post flair
+    pub post_flair_allowed: bool,
+
+    /// true if only moderators can use the emoji
+    pub mod_flair_only: bool,
+}
+
+impl Default for EmojiPermissions {
+    /// Reddit's defaults, which allow everyone to use the emoji in any flair.
+    fn default() -> Self {
+        Self {
+            user_flair_allowed: true,
+            post_flair_allowed: true,
+            mod_flair_only: false,
+        }
+    }
+}
+
+/// The settings of a subreddit, as shown to its moderators
+/// See https://www.reddit.com/dev/api#GET_r_{subreddit}_about_edit
+///
+/// Reddit resets settings that are left out of an update to their defaults,
+/// so settings this library does not model are kept in `other` and sent back with updates.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SubredditSettings {
+    /// the fullname of the subreddit, e.g. "t5_2qh1o"
+    pub subreddit_id: Box<str>,
+
+    /// the title of the subreddit
+    pub title: Box<str>,
+
+    /// the short description shown in search results and sidebars
+    pub public_description: Box<str>,
+
+    /// the raw markdown of the old reddit sidebar
+    pub description: Box<str>,
+
+    /// the raw markdown shown on the submit page
+    pub submit_text: Box<str>,
+
+    /// who can see and post in the subreddit, like "public", "restricted", or "private"
+    pub subreddit_type: Box<str>,
+
+    /// the kinds of posts allowed, "any", "link", or "self"
+    pub content_options: Box<str>,
+
+    /// the language of the subreddit, like "en"
+    pub language: Box<str>,
+
+    /// true if the subreddit is marked as NSFW
+    pub over_18: bool,
+
+    /// true if image posts are allowed
+    pub allow_images: bool,
+
+    /// true if video posts are allowed
+    pub allow_videos: bool,
+
+    /// true if gallery posts are allowed
+    pub allow_galleries: bool,
+
+    /// true if poll posts are allowed
+    pub allow_polls: bool,
+
+    /// true if posts can be marked as spoilers
+    pub spoilers_enabled: bool,
+
+    /// how aggressively link posts are filtered as spam
+    pub spam_links: SpamLevel,
+
+    /// how aggressively text posts are filtered as spam
+    pub spam_selfposts: SpamLevel,
+
+    /// how aggressively comments are filtered as spam
+    pub spam_comments: SpamLevel,
+
+    /// who can edit the wiki
+    pub wikimode: WikiMode,
+
+    /// the minimum account age in days to edit the wiki
+    pub wiki_edit_age: Option<u32>,
+
+    /// the minimum karma in the subreddit to edit the wiki
+    pub wiki_edit_karma: Option<u32>,
+
+    /// how long the scores of new comments are hidden, in minutes
+    pub comment_score_hide_mins: u32,
+
+    /// the default order of comments. null if the user's preference is used
+    pub suggested_comment_sort: Option<params::CommentSort>,
+
+    /// the settings this library does not model
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl SubredditSettings {
+    /// Get the form parameters of these settings for `/api/site_admin`.
+    ///
+    /// Null, array, and object settings in `other` are left out, as the form can not represent them.
+    pub(crate) fn site_admin_form(&self) -> Vec<(String, String)> {
+        let strings = [
+            ("sr", &self.subreddit_id),
+            ("title", &self.title),
+            ("public_description", &self.public_description),
+            ("description", &self.description),
+            ("submit_text", &self.submit_text),
+            ("type", &self.subreddit_type),
+            ("link_type", &self.content_options),
+            ("lang", &self.language),
+        ];
+        let bools = [
+            ("over_18", self.over_18),
+            ("allow_images", self.allow_images),
+            ("allow_videos", self.allow_videos),
+            ("allow_galleries", self.allow_galleries),
+            ("allow_polls", self.allow_polls),
+            ("spoilers_enabled", self.spoilers_enabled),
+        ];
+        let spam_levels = [
+            ("spam_links", self.spam_links),
+            ("spam_selfposts", self.spam_selfposts),
+            ("spam_comments", self.spam_comments),
+        ];
+
+        let mut form = vec![("api_type".to_string(), "json".to_string())];
+        for (name, value) in self.other.iter() {
+            let value = match value {
+                serde_json::Value::String(value) => value.clone(),
+                serde_json::Value::Bool(value) => value.to_string(),
+                serde_json::Value::Number(value) => value.to_string(),
+                _ => continue,
+            };
+            form.push((name.clone(), value));
+        }
+        form.extend(
+            strings
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        form.extend(
+            bools
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        form.extend(
+            spam_levels
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.as_str().to_string())),
+        );
+        form.push(("wikimode".to_string(), self.wikimode.as_str().to_string()));
+        for (name, value) in [
+            ("wiki_edit_age", self.wiki_edit_age),
+            ("wiki_edit_karma", self.wiki_edit_karma),
+            (
+                "comment_score_hide_mins",
+                Some(self.comment_score_hide_mins),
+            ),
+        ] {
+            if let Some(value) = value {
+                form.push((name.to_string(), value.to_string()));
+            }
+        }
+        if let Some(sort) = self.suggested_comment_sort {
+            form.push(("suggested_comment_sort".to_string(), sort.to_string()));
+        }
+
+        form
+    }
+}
+
+/// How aggressively a subreddit filters a kind of content as spam
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpamLevel {
+    /// Only content reddit's filter thinks is spam is filtered
+    Low,
+
+    /// Content from new or low karma accounts is filtered too
+    High,
+
+    /// All content is filtered until approved
+    All,
+}
+
+impl SpamLevel {
+    /// Get the value reddit expects for this spam level.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::High => "high",
+            Self::All => "all",
+        }
+    }
+}
+
+/// Who can edit the wiki of a subreddit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WikiMode {
+    /// The wiki is disabled
+    Disabled,
+
+    /// Only moderators can edit the wiki
+    ModOnly,
+
+    /// Anyone who meets the age and karma requirements can edit the wiki
+    Anyone,
+}
+
+impl WikiMode {
+    /// Get the value reddit expects for this wiki mode.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::ModOnly => "modonly",
+            Self::Anyone => "anyone",
+        }
+    }
+}
+
+/// A user's relationship with a subreddit, like a ban or an approved user
+/// See https://www.reddit.com/dev/api#GET_about_{where}
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SubredditRelationship {
+    /// the account name of the user
+    pub name: Box<str>,
+
+    /// the fullname of the user's account, e.g. "t2_1w72"
+    pub id: Box<str>,
+
+    /// the id of the relationship, e.g. "rb_1a2b3c"
+    pub rel_id: Box<str>,
+
+    /// when the relationship started, in UTC epoch-seconds
+    pub date: f64,
+
+    /// the note left by the moderator. only present for bans
+    pub note: Option<Box<str>>,
+
+    /// the number of days left of a temporary ban. null if the ban is permanent or this is not a ban
+    pub days_left: Option<u64>,
+}
+
+/// The preferences of the logged in user.
+///
+/// This only models a subset of the available preferences.
+/// Fields are `None` if they were not present,
+/// and fields that are `None` are left unchanged when used as a patch.
+/// See https://www.reddit.com/dev/api#GET_api_v1_me_prefs
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Preferences {
+    /// the language to use, an IETF language tag, e.g. "en"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<Box<str>>,
+
+    /// true if the user is over 18 and wants to see NSFW content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub over_18: Option<bool>,
+
+    /// true if NSFW content should be included in search results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_include_over_18: Option<bool>,
+
+    /// true if NSFW content should be labeled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_nsfw: Option<bool>,
+
+    /// whether thumbnails are shown. One of "on", "off", or "subreddit"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media: Option<Box<str>>,
+
+    /// whether media previews are expanded. One of "on", "off", or "subreddit"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_preview: Option<Box<str>>,
+
+    /// the default sort of comments, e.g. "confidence"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_comment_sort: Option<Box<str>>,
+
+    /// true if a post's suggested sort should be ignored
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_suggested_sort: Option<bool>,
+
+    /// the number of comments to display by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_comments: Option<u32>,
+
+    /// the number of links to display at once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numsites: Option<u32>,
+
+    /// links with a score less than this are hidden
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_link_score: Option<i64>,
+
+    /// comments with a score less than this are hidden
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_comment_score: Option<i64>,
+
+    /// true if user flair should be shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_flair: Option<bool>,
+
+    /// true if link flair should be shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_link_flair: Option<bool>,
+
+    /// true if controversial comments should be highlighted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_controversial: Option<bool>,
+
+    /// true if private messages should be shown as threads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threaded_messages: Option<bool>,
+
+    /// true if messages should be marked as read when the inbox is opened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_messages_read: Option<bool>,
+
+    /// true if private messages should also be sent by email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_messages: Option<bool>,
+
+    /// true if the user has unsubscribed from all emails
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_unsubscribe_all: Option<bool>,
+
+    /// true if other users can follow this user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_followers: Option<bool>,
+
+    /// true if the user's votes are public
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_votes: Option<bool>,
+
+    /// true if the user's online status is shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_presence: Option<bool>,
+
+    /// true if the user's profile should be hidden from search engines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_from_robots: Option<bool>,
+
+    /// true if night mode is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nightmode: Option<bool>,
+}
+
+/// A live thread
+/// kind == "LiveUpdateEvent"
+/// See https://www.reddit.com/dev/api#section_live
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LiveThread {
+    /// the id of this live thread, e.g. "ta535s1hq2je"
+    pub id: Box<str>,
+
+    /// the fullname of this live thread, e.g. "LiveUpdateEvent_ta535s1hq2je"
+    pub name: Box<str>,
+
+    /// the title of the live thread
+    pub title: Box<str>,
+
+    /// the raw markdown description
+    pub description: Box<str>,
+
+    /// the formatted escaped HTML description. Null if not present.
+    pub description_html: Option<Box<str>>,
+
+    /// the raw markdown of the resources sidebar
+    pub resources: Box<str>,
+
+    /// the formatted escaped HTML of the resources sidebar. Null if not present.
+    pub resources_html: Option<Box<str>>,
+
+    /// whether the thread is still accepting updates
+    pub state: LiveThreadState,
+
+    /// true if the thread is tagged as NSFW
+    pub nsfw: bool,
+
+    /// the number of people currently viewing the thread. null if the thread is complete
+    pub viewer_count: Option<u64>,
+
+    /// whether viewer_count has been fuzzed
+    pub viewer_count_fuzzed: Option<bool>,
+
+    /// the total number of views. null if not yet calculated
+    pub total_views: Option<u64>,
+
+    /// the websocket url to receive new updates from. null if the thread is complete
+    pub websocket_url: Option<Box<str>>,
+
+    /// Created Implementation
+    #[serde(flatten)]
+    pub created: Created,
+}
+
+/// The state of a live thread
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum LiveThreadState {
+    /// The thread is accepting new updates
+    #[serde(rename = "live")]
+    Live,
+
+    /// The thread has been closed
+    #[serde(rename = "complete")]
+    Complete,
+}
+
+/// A single update in a live thread
+/// kind == "LiveUpdate"
+/// See https://www.reddit.com/dev/api#section_live
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LiveUpdate {
+    /// the id of this update, a uuid
+    pub id: Box<str>,
+
+    /// the fullname of this update, e.g. "LiveUpdate_ff87068e-a126-11e3-9f93-12313b0b3603"
+    pub name: Box<str>,
+
+    /// the raw markdown text
+    pub body: Box<str>,
+
+    /// the formatted escaped HTML text. Null if not present.
+    pub body_html: Option<Box<str>>,
+
+    /// the account name of the poster. null if the account was deleted
+    pub author: Option<Box<str>>,
+
+    /// true if this update has been struck out by a contributor
+    pub stricken: bool,
+
+    /// media embedded from links in the body
+    pub embeds: Vec<LiveUpdateEmbed>,
+
+    // TODO: Finish type
+    /// oembed data for embeds, used by mobile clients
+    pub mobile_embeds: Vec<serde_json::Value>,
+
+    /// Created Implementation
+    #[serde(flatten)]
+    pub created: Created,
+}
+
+/// Media embedded in a live update
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LiveUpdateEmbed {
+    /// the url of the embedded media
+    pub url: Box<str>,
+
+    /// the width of the embed, if known
+    pub width: Option<u32>,
+
+    /// the height of the embed, if known
+    pub height: Option<u32>,
+}
+
+/// Info on what the post may contain
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub enum PostHint {
+    /// The post is an image
+    #[serde(rename = "image")]
+    Image,
+
+    /// The post is a link
+    #[serde(rename = "link")]
+    Link,
+
+    /// A video hosted on reddit
+    #[serde(rename = "hosted:video")]
+    HostedVideo,
+
+    #[serde(rename = "rich:video")]
+    RichVideo,
+
+    #[serde(rename = "self")]
+    DataSelf,
+
+    #[serde(rename = "gallery")]
+    Gallery,
+}
+
+/// Previews of the images of a post
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Preview {
+    /// The images of the post
+    pub images: Vec<PreviewImage>,
+
+    /// Whether previews are shown for this post
+    pub enabled: bool,
+
+    /// A video reddit transcoded from an external gif or video, hosted on v.redd.it.
+    /// This is separate from `media.reddit_video`, which is only set for videos uploaded to reddit
+    pub reddit_video_preview: Option<RedditVideo>,
+}
+
+/// A preview of an image of a post
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PreviewImage {
+    /// The id of the image
+    pub id: Box<str>,
+
+    /// The full size image
+    pub source: PreviewSource,
+
+    /// Scaled down versions of the image, smallest first
+    pub resolutions: Vec<PreviewSource>,
+
+    // TODO: Finish type
+    /// Other formats of the image, like "gif", "mp4", or "obfuscated" for blurred nsfw images
+    pub variants: serde_json::Value,
+}
+
+/// A version of a preview image
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PreviewSource {
+    /// The url of the image. This is html escaped unless raw_json is used
+    pub url: Box<str>,
+
+    /// The width in pixels
+    pub width: u32,
+
+    /// The height in pixels
+    pub height: u32,
+}
+
+/// A video hosted on v.redd.it
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RedditVideo {
+    /// the bitrate of the fallback video in kilobits per second
+    pub bitrate_kbps: Option<u32>,
+
+    /// the url of an mp4 of the video without audio
+    pub fallback_url: Box<str>,
+
+    /// the url of the DASH playlist, which includes the audio
+    pub dash_url: Box<str>,
+
+    /// the url of the HLS playlist, which includes the audio
+    pub hls_url: Box<str>,
+
+    /// the url of a low quality version, used for scrubbing
+    pub scrubber_media_url: Box<str>,
+
+    /// the length of the video in seconds
+    pub duration: Option<u64>,
+
+    /// the width in pixels
+    pub width: u32,
+
+    /// the height in pixels
+    pub height: u32,
+
+    /// true if this is a gif converted to a video, which has no audio
+    pub is_gif: bool,
+
+    /// "completed" if the video was processed
+    pub transcoding_status: Box<str>,
+}
+
+/// Metadata about an image or video of a post
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MediaMetadata {
+    /// "valid" if the media was processed, "failed" or "unprocessed" otherwise
+    pub status: Box<str>,
+
+    /// the id of the media
+    pub id: Option<Box<str>>,
+
+    /// the kind of the media, like "Image" or "AnimatedImage"
+    #[serde(rename = "e")]
+    pub kind: Option<Box<str>>,
+
+    /// the mime type of the media, like "image/jpg"
+    #[serde(rename = "m")]
+    pub mime_type: Option<Box<str>>,
+
+    /// the full size media
+    #[serde(rename = "s")]
+    pub source: Option<MediaMetadataSource>,
+
+    /// scaled down versions of the media, smallest first
+    #[serde(rename = "p", default)]
+    pub previews: Vec<MediaMetadataSource>,
+}
+
+/// A version of an image or video of a post
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct MediaMetadataSource {
+    /// the url of an image. This is html escaped unless raw_json is used
+    #[serde(rename = "u")]
+    pub url: Option<Box<str>>,
+
+    /// the url of an animated image as a gif
+    pub gif: Option<Box<str>>,
+
+    /// the url of an animated image as an mp4
+    pub mp4: Option<Box<str>>,
+
+    /// the width in pixels
+    #[serde(rename = "x")]
+    pub width: u32,
+
+    /// the height in pixels
+    #[serde(rename = "y")]
+    pub height: u32,
+}
+
+/// A subreddit
+/// kind == "t5"
+/// See https://www.reddit.com/dev/api#GET_r_{subreddit}_about
+///
+/// Only the fields present in every subreddit listing are required.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Subreddit {
+    /// this subreddit's identifier, e.g. "2qh1o"
+    pub id: CompactStr,
+
+    /// Fullname of the subreddit, e.g. "t5_2qh1o"
+    pub name: CompactStr,
+
+    /// the name of the subreddit excluding the /r/ prefix, e.g. "aww"
+    pub display_name: CompactStr,
+
+    /// the name of the subreddit including the /r/ prefix, e.g. "r/aww"
+    #[serde(default)]
+    pub display_name_prefixed: CompactStr,
+
+    /// the title of the subreddit
+    #[serde(default)]
+    pub title: Box<str>,
+
+    /// the short description shown in search results and sidebars
+    #[serde(default)]
+    pub public_description: Box<str>,
+
+    /// the raw markdown of the old reddit sidebar
+    pub description: Option<Box<str>>,
+
+    /// the relative url of the subreddit, e.g. "/r/aww/"
+    #[serde(default)]
+    pub url: Box<str>,
+
+    /// the number of subscribers
+    pub subscribers: Option<u64>,
+
+    /// the number of users viewing the subreddit
+    pub active_user_count: Option<u64>,
+
+    /// true if the subreddit is marked as NSFW
+    #[serde(default)]
+    pub over18: bool,
+
+    /// true if the subreddit is quarantined
+    pub quarantine: Option<bool>,
+
+    /// the type of the subreddit, like "public", "restricted", or "private"
+    pub subreddit_type: Option<CompactStr>,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_utc: Option<f64>,
+
+    /// the old reddit icon of the subreddit
+    #[serde(default, with = "asset_url")]
+    pub icon_img: Option<url::Url>,
+
+    /// the width and height of icon_img in pixels
+    pub icon_size: Option<[u32; 2]>,
+
+    /// the new reddit icon of the subreddit
+    #[serde(default, with = "asset_url")]
+    pub community_icon: Option<url::Url>,
+
+    /// the new reddit banner of the subreddit
+    #[serde(default, with = "asset_url")]
+    pub banner_background_image: Option<url::Url>,
+
+    /// the old reddit banner of the subreddit
+    #[serde(default, with = "asset_url")]
+    pub banner_img: Option<url::Url>,
+
+    /// the old reddit header image of the subreddit, shown next to its name
+    #[serde(default, with = "asset_url")]
+    pub header_img: Option<url::Url>,
+
+    /// the width and height of header_img in pixels
+    pub header_size: Option<[u32; 2]>,
+
+    /// the primary color of the subreddit
+    #[serde(default, with = "asset_color")]
+    pub primary_color: Option<Color>,
+
+    /// the key color of the subreddit, used for buttons and links
+    #[serde(default, with = "asset_color")]
+    pub key_color: Option<Color>,
+
+    /// the background color of the banner
+    #[serde(default, with = "asset_color")]
+    pub banner_background_color: Option<Color>,
+}
+
+impl Subreddit {
+    /// Get the icon of this subreddit, preferring the new reddit icon.
+    pub fn icon(&self) -> Option<&url::Url> {
+        self.community_icon.as_ref().or(self.icon_img.as_ref())
+    }
+
+    /// Get the banner of this subreddit, preferring the new reddit banner.
+    pub fn banner(&self) -> Option<&url::Url> {
+        self.banner_background_image
+            .as_ref()
+            .or(self.banner_img.as_ref())
+    }
+
+    /// Get the color of this subreddit, preferring the primary color.
+    pub fn color(&self) -> Option<Color> {
+        self.primary_color.or(self.key_color)
+    }
+}
+
+/// A subreddit a user moderates
+/// See https://www.reddit.com/user/{username}/moderated_subreddits.json
+///
+/// Only the fields needed to identify the subreddit and the user's permissions are modeled.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ModeratedSubreddit {
+    /// Fullname of the subreddit, e.g. "t5_2qh1o"
+    pub name: CompactStr,
+
+    /// the name of the subreddit excluding the /r/ prefix, e.g. "aww"
+    #[serde(rename = "sr")]
+    pub display_name: CompactStr,
+
+    /// the name of the subreddit including the /r/ prefix, e.g. "r/aww"
+    #[serde(default, rename = "sr_display_name_prefixed")]
+    pub display_name_prefixed: CompactStr,
+
+    /// the title of the subreddit
+    #[serde(default)]
+    pub title: Box<str>,
+
+    /// the relative url of the subreddit, e.g. "/r/aww/"
+    #[serde(default)]
+    pub url: Box<str>,
+
+    /// the number of subscribers
+    #[serde(default)]
+    pub subscribers: u64,
+
+    /// true if the subreddit is marked as NSFW
+    #[serde(default)]
+    pub over_18: bool,
+
+    /// the type of the subreddit, like "public", "restricted", or "private"
+    pub subreddit_type: Option<CompactStr>,
+
+    /// the moderator permissions of the user, like "all", "posts", or "wiki"
+    #[serde(default)]
+    pub mod_permissions: Vec<Box<str>>,
+
+    /// true if the logged in user is subscribed to the subreddit. null if you are not logged in
+    pub user_is_subscriber: Option<bool>,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_utc: Option<f64>,
+
+    /// the old reddit icon of the subreddit
+    #[serde(default, with = "asset_url")]
+    pub icon_img: Option<url::Url>,
+
+    /// the new reddit icon of the subreddit
+    #[serde(default, with = "asset_url")]
+    pub community_icon: Option<url::Url>,
+}
+
+impl ModeratedSubreddit {
+    /// Returns `true` if the user has full permissions in the subreddit.
+    pub fn has_full_permissions(&self) -> bool {
+        self.mod_permissions
+            .iter()
+            .any(|permission| &**permission == "all")
+    }
+
+    /// Returns `true` if the user has a permission in the subreddit, like "posts" or "wiki".
+    ///
+    /// Moderators with full permissions have every permission.
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.mod_permissions
+            .iter()
+            .any(|granted| &**granted == "all" || &**granted == permission)
+    }
+
+    /// Get the icon of this subreddit, preferring the new reddit icon.
+    pub fn icon(&self) -> Option<&url::Url> {
+        self.community_icon.as_ref().or(self.icon_img.as_ref())
+    }
+}
+
+/// A color of a subreddit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    /// the red channel
+    pub red: u8,
+
+    /// the green channel
+    pub green: u8,
+
+    /// the blue channel
+    pub blue: u8,
+}
+
+impl std::str::FromStr for Color {
+    type Err = crate::Error;
+
+    /// Parse a hex color, like "#ff4500" or "#f40".
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::Error::InvalidParam {
+            kind: "color",
+            value: value.into(),
+        };
+
+        let hex = value.trim().strip_prefix('#').ok_or_else(invalid)?;
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+        match hex.len() {
+            6 => Ok(Self {
+                red: channel(&hex[0..2])?,
+                green: channel(&hex[2..4])?,
+                blue: channel(&hex[4..6])?,
+            }),
+            3 => Ok(Self {
+                red: channel(&hex[0..1])? * 17,
+                green: channel(&hex[1..2])? * 17,
+                blue: channel(&hex[2..3])? * 17,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
+
+/// A report of a post or comment by users, for one reason.
+///
+/// Reddit sends these as arrays, like `["Spam", 2, false, false]`.
+/// Only the reason and count are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserReport {
+    /// the reason given by the users. null if they gave none
+    pub reason: Option<Box<str>>,
+
+    /// the number of users that reported it for this reason
+    pub count: u64,
+}
+
+/// A report of a post or comment by a moderator.
+///
+/// Reddit sends these as arrays, like `["Spam", "moderator_name"]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModReport {
+    /// the reason given by the moderator. null if they gave none
+    pub reason: Option<Box<str>>,
+
+    /// the account name of the moderator
+    pub moderator: Box<str>,
+}
+
+impl serde::Serialize for UserReport {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (&self.reason, self.count).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UserReport {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (reason, count) = deserializer.deserialize_seq(ReportVisitor::new())?;
+        Ok(Self { reason, count })
+    }
+}
+
+impl serde::Serialize for ModReport {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (&self.reason, &self.moderator).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ModReport {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (reason, moderator) = deserializer.deserialize_seq(ReportVisitor::new())?;
+        Ok(Self { reason, moderator })
+    }
+}
+
+/// Deserializes the reason and second element of a report array, ignoring any elements after them.
+struct ReportVisitor<T>(std::marker::PhantomData<T>);
+
+impl<T> ReportVisitor<T> {
+    fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<'de, T> serde::de::Visitor<'de> for ReportVisitor<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    type Value = (Option<Box<str>>, T);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a report array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        use serde::de::Error;
+
+        let reason = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let value = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        // Newer reports include whether they are snoozed, which is not needed.
+        while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+
+        Ok((reason, value))
+    }
+}
+
+/// (De)serialize the image urls of a subreddit, which reddit sends html escaped, and as an empty string if not set.
+///
+/// Invalid urls are parsed as `None`.
+mod asset_url {
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+
+    pub fn serialize<S>(url: &Option<url::Url>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(url.as_ref().map_or("", |url| url.as_str()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<url::Url>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let url: Option<Box<str>> = Option::deserialize(deserializer)?;
+        Ok(url
+            .filter(|url| !url.is_empty())
+            .and_then(|url| url::Url::parse(&crate::media::unescape_url(&url)).ok()))
+    }
+}
+
+/// (De)serialize the colors of a subreddit, which reddit sends as an empty string if not set.
+///
+/// Invalid colors are parsed as `None`.
+mod asset_color {
+    use super::Color;
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+
+    pub fn serialize<S>(color: &Option<Color>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match color {
+            Some(color) => serializer.collect_str(color),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Color>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let color: Option<Box<str>> = Option::deserialize(deserializer)?;
+        Ok(color.and_then(|color| color.parse().ok()))
+    }
+}
+
+/// An award given to a post or comment
+///
+/// Awards are retired, and reddit returns old ones inconsistently, so only the id, name and count are required.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Awarding {
+    /// the id of the award, e.g. "award_3cf96da4-79da-4127-90ac-84545e1833dc" or "gid_1"
+    pub id: Box<str>,
+
+    /// the name of the award, e.g. "Home Time"
+    pub name: Box<str>,
+
+    /// the description of the award. This is html escaped
+    #[serde(default)]
+    pub description: Box<str>,
+
+    /// the number of times the post or comment received this award
+    pub count: u64,
+
+    /// the price of the award in coins
+    #[serde(default)]
+    pub coin_price: u64,
+
+    /// the coins the receiver got
+    #[serde(default)]
+    pub coin_reward: u64,
+
+    /// the coins the giver got back
+    pub giver_coin_reward: Option<u64>,
+
+    /// the coins the subreddit got
+    #[serde(default)]
+    pub subreddit_coin_reward: u64,
+
+    /// the days of premium the receiver got
+    pub days_of_premium: Option<u64>,
+
+    /// the days of premium drip extension the receiver got
+    pub days_of_drip_extension: Option<u64>,
+
+    /// the price of the award in pennies, for awards bought with money
+    pub penny_price: Option<u64>,
+
+    /// the pennies donated when the award is given
+    pub penny_donate: Option<u64>,
+
+    /// the kind of award, like "global" or "community"
+    #[serde(default)]
+    pub award_type: Box<str>,
+
+    /// the sub type of the award, like "GLOBAL", "PREMIUM", or "GROUP"
+    #[serde(default)]
+    pub award_sub_type: Box<str>,
+
+    /// the fullname of the subreddit of a community award. null for global awards
+    pub subreddit_id: Option<Box<str>>,
+
+    /// true if the award can be given
+    #[serde(default)]
+    pub is_enabled: bool,
+
+    /// true if the award was recently added
+    #[serde(default)]
+    pub is_new: bool,
+
+    /// the time the award became available in UTC epoch-second format, if limited
+    pub start_date: Option<f64>,
+
+    /// the time the award stops being available in UTC epoch-second format, if limited
+    pub end_date: Option<f64>,
+
+    /// the url of the full size icon. This may be animated
+    #[serde(default)]
+    pub icon_url: Box<str>,
+
+    /// the width of the full size icon in pixels
+    #[serde(default)]
+    pub icon_width: u32,
+
+    /// the height of the full size icon in pixels
+    #[serde(default)]
+    pub icon_height: u32,
+
+    /// the format of the icon, like "PNG" or "APNG"
+    pub icon_format: Option<Box<str>>,
+
+    /// scaled down versions of the icon, smallest first
+    #[serde(default)]
+    pub resized_icons: Vec<AwardIcon>,
+
+    /// the url of the full size icon, without animation
+    #[serde(default)]
+    pub static_icon_url: Box<str>,
+
+    /// the width of the full size static icon in pixels
+    #[serde(default)]
+    pub static_icon_width: u32,
+
+    /// the height of the full size static icon in pixels
+    #[serde(default)]
+    pub static_icon_height: u32,
+
+    /// scaled down versions of the static icon, smallest first
+    #[serde(default)]
+    pub resized_static_icons: Vec<AwardIcon>,
+
+    // TODO: Finish type, this is always null in samples
+    pub awardings_required_to_grant_benefits: Option<serde_json::Value>,
+
+    // TODO: Finish type, this is always null in samples
+    pub tiers_by_required_awardings: Option<serde_json::Value>,
+}
+
+/// A scaled down icon of an award
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AwardIcon {
+    /// the url of the icon. This is html escaped unless raw_json is used
+    pub url: Box<str>,
+
+    /// the width in pixels
+    pub width: u32,
+
+    /// the height in pixels
+    pub height: u32,
+}
+
+impl Awarding {
+    /// Get the smallest resized icon that is at least size pixels wide, or the largest one if none are.
+    pub fn icon(&self, size: u32) -> Option<&AwardIcon> {
+        pick_icon(&self.resized_icons, size)
+    }
+
+    /// Get the smallest resized static icon that is at least size pixels wide, or the largest one if none are.
+    pub fn static_icon(&self, size: u32) -> Option<&AwardIcon> {
+        pick_icon(&self.resized_static_icons, size)
+    }
+}
+
+/// The number of each kind of gilding a post or comment received.
+///
+/// Reddit leaves out kinds that were not received.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Gildings {
+    /// the number of silver awards
+    pub gid_1: u64,
+
+    /// the number of gold awards
+    pub gid_2: u64,
+
+    /// the number of platinum awards
+    pub gid_3: u64,
+}
+
+impl Gildings {
+    /// Get the total number of gildings.
+    pub fn total(&self) -> u64 {
+        self.gid_1 + self.gid_2 + self.gid_3
+    }
+
+    /// Returns `true` if nothing was received.
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+/// Pick the smallest icon that is at least size pixels wide, or the largest one if none are.
+fn pick_icon(icons: &[AwardIcon], size: u32) -> Option<&AwardIcon> {
+    icons
+        .iter()
+        .filter(|icon| icon.width >= size)
+        .min_by_key(|icon| icon.width)
+        .or_else(|| icons.iter().max_by_key(|icon| icon.width))
+}
+
+/// A summary of a subreddit, embedded in links when `sr_detail` is requested
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SubredditDetail {
+    /// Fullname of the subreddit, e.g. "t5_2qh1o"
+    pub name: Box<str>,
+
+    /// the name of the subreddit excluding the /r/ prefix, e.g. "aww"
+    pub display_name: Box<str>,
+
+    /// the name of the subreddit including the /r/ prefix, e.g. "r/aww"
+    pub display_name_prefixed: Box<str>,
+
+    /// the title of the subreddit
+    pub title: Box<str>,
+
+    /// the short description shown in search results and sidebars
+    pub public_description: Box<str>,
+
+    /// the relative url of the subreddit, e.g. "/r/aww/"
+    pub url: Box<str>,
+
+    /// the number of subscribers
+    pub subscribers: Option<u64>,
+
+    /// the url of the icon of the subreddit. Empty if not set
+    pub icon_img: Option<Box<str>>,
+
+    /// the url of the newer community icon of the subreddit. Empty if not set. This is html escaped unless raw_json is used
+    pub community_icon: Option<Box<str>>,
+
+    /// the url of the banner image of the subreddit. Empty if not set
+    pub banner_img: Option<Box<str>>,
+
+    /// the url of the header image of the subreddit, if set
+    pub header_img: Option<Box<str>>,
+
+    /// the primary color of the subreddit as a hex string, like "#ff4500". Empty if not set
+    pub primary_color: Option<Box<str>>,
+
+    /// the key color of the subreddit as a hex string. Empty if not set
+    pub key_color: Option<Box<str>>,
+
+    /// true if the subreddit is marked as NSFW
+    pub over_18: bool,
+
+    /// true if the subreddit is quarantined
+    pub quarantine: Option<bool>,
+
+    /// the type of the subreddit, like "public", "restricted", or "private"
+    pub subreddit_type: Box<str>,
+
+    /// the time of creation in UTC epoch-second format
+    pub created_utc: Option<f64>,
+}
+
+/// The new reddit theme of a subreddit. Unset values are null
+/// See https://www.reddit.com/dev/api#GET_api_v1_structured_styles_{subreddit}
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StructuredStyles {
+    /// the url of the icon of the subreddit
+    pub community_icon: Option<Box<str>>,
+
+    /// the primary color of the subreddit as a hex string, like "#FF4500"
+    pub primary_color: Option<Box<str>>,
+
+    /// the color of highlighted text and buttons
+    pub highlight_color: Option<Box<str>>,
+
+    /// the primary color used by old reddit
+    pub legacy_primary_color: Option<Box<str>>,
+
+    /// the background color of the page
+    pub background_color: Option<Box<str>>,
+
+    /// the url of the background image of the page
+    pub background_image: Option<Box<str>>,
+
+    /// how the background image is placed, like "cover" or "tiled"
+    pub background_image_position: Option<Box<str>>,
+
+    /// the size of the banner, like "small", "medium", or "large"
+    pub banner_height: Option<Box<str>>,
+
+    /// the background color of the banner
+    pub banner_background_color: Option<Box<str>>,
+
+    /// the url of the background image of the banner
+    pub banner_background_image: Option<Box<str>>,
+
+    /// how the banner background image is placed, like "cover" or "tiled"
+    pub banner_background_image_position: Option<Box<str>>,
+
+    /// the url of the banner image used by old reddit
+    pub legacy_banner_background_image: Option<Box<str>>,
+
+    /// the url of the banner image shown on mobile
+    pub mobile_banner_image: Option<Box<str>>,
+
+    /// the url of an image shown on top of the banner background
+    pub banner_positioned_image: Option<Box<str>>,
+
+    /// where the positioned banner image is shown, like "left" or "centered"
+    pub banner_positioned_image_position: Option<Box<str>>,
+
+    /// the url of an image shown on the right of the banner
+    pub secondary_banner_positioned_image: Option<Box<str>>,
+
+    /// the background color of the menu under the banner
+    pub menu_background_color: Option<Box<str>>,
+
+    /// the background color of sub menus
+    pub submenu_background_color: Option<Box<str>>,
+
+    /// the color of the selected menu link
+    pub menu_link_color_active: Option<Box<str>>,
+
+    /// the color of the other menu links
+    pub menu_link_color_inactive: Option<Box<str>>,
+
+    /// the color of post titles
+    pub post_title_color: Option<Box<str>>,
+
+    /// the background color of the headers of sidebar widgets
+    pub sidebar_widget_header_color: Option<Box<str>>,
+
+    /// the background color of sidebar widgets
+    pub sidebar_widget_background_color: Option<Box<str>>,
+
+    /// the url of the image shown for posts without a thumbnail
+    pub post_placeholder_image: Option<Box<str>>,
+
+    /// the url of the icon of an upvote
+    pub post_upvote_icon_active: Option<Box<str>>,
+
+    /// the url of the icon of the upvote button
+    pub post_upvote_icon_inactive: Option<Box<str>>,
+
+    /// the url of the icon of a downvote
+    pub post_downvote_icon_active: Option<Box<str>>,
+
+    /// the url of the icon of the downvote button
+    pub post_downvote_icon_inactive: Option<Box<str>>,
+}
+
+impl StructuredStyles {
+    /// Get the url of the image of the banner, preferring the new reddit banner.
+    pub fn banner_image(&self) -> Option<&str> {
+        [
+            &self.banner_background_image,
+            &self.legacy_banner_background_image,
+            &self.mobile_banner_image,
+        ]
+        .into_iter()
+        .filter_map(|image| image.as_deref())
+        .find(|image| !image.is_empty())
+    }
+}
+
+/// The order and captions of the images of a gallery
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct GalleryData {
+    /// the items of the gallery, in order
+    pub items: Vec<GalleryItem>,
+}
+
+/// An item of a gallery
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct GalleryItem {
+    /// the id of the media of this item, a key of media_metadata
+    pub media_id: Box<str>,
+
+    /// the id of this item
+    pub id: u64,
+
+    /// the caption of this item, if any
+    pub caption: Option<Box<str>>,
+}
+
+/// (De)serialize the replies of a comment or message, which reddit sends as an empty string if there are none.
+mod replies {
+    use super::{
+        Listing,
+        Thing,
+        ThingData,
+    };
+    use serde::{
+        de::{
+            Error,
+            MapAccess,
+            Visitor,
+        },
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+
+    #[derive(Serialize)]
+    struct ListingThing<'a> {
+        kind: &'static str,
+        data: &'a Listing,
+    }
+
+    pub fn serialize<S>(replies: &Option<Box<Listing>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match replies {
+            Some(listing) => ListingThing {
+                kind: "Listing",
+                data: listing,
+            }
+            .serialize(serializer),
+            None => serializer.serialize_str(""),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Box<Listing>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(RepliesVisitor)
+    }
+
+    struct RepliesVisitor;
+
+    impl<'de> Visitor<'de> for RepliesVisitor {
+        type Value = Option<Box<Listing>>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a listing or an empty string")
+        }
+
+        fn visit_str<E>(self, _value: &str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_unit<E>(self) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_none<E>(self) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            Ok(None)
+        }
+
+        fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let thing = Thing::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+            match thing.data {
+                ThingData::Listing(listing) => Ok(Some(listing)),
+                _ => Err(A::Error::custom("expected a listing")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SUBREDDIT_SAMPLE_1: &str = include_str!("../test_data/subreddit_dankmemes.json");
+    const SUBREDDIT_SAMPLE_2: &str = include_str!("../test_data/subreddit_cromch.json");
+    const SUBREDDIT_SAMPLE_3: &str = include_str!("../test_data/subreddit_cuddleroll.json");
+    const SUBREDDIT_SAMPLE_4: &str = include_str!("../test_data/subreddit_cursed_images.json");
+
+    const COMMENT_SAMPLE_1: &str = include_str!("../test_data/comment_h966lq.json");
+    const COMMENT_SAMPLE_2: &str = include_str!("../test_data/comment_h8p0py.json");
+
+    const TROPHY_LIST_SAMPLE_1: &str = include_str!("../test_data/trophy_list.json");
+
+    const SUBREDDIT_RULES_SAMPLE_1: &str = include_str!("../test_data/subreddit_rules.json");
+
+    const KARMA_LIST_SAMPLE_1: &str = include_str!("../test_data/karma_list.json");
+
+    const MULTIREDDIT_SAMPLE_1: &str = include_str!("../test_data/multireddit.json");
+
+    const WIKI_PAGE_SAMPLE_1: &str = include_str!("../test_data/wiki_page.json");
+
+    const LIVE_THREAD_SAMPLE_1: &str = include_str!("../test_data/live_thread_about.json");
+    const LIVE_UPDATES_SAMPLE_1: &str = include_str!("../test_data/live_thread_updates.json");
+
+    #[test]
+    fn parse_subreddit_1() {
+        let res = serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_1).unwrap();
+        dbg!(res);
+    }
+
+    #[test]
+    fn parse_subreddit_2() {
+        let res = serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_2).unwrap();
+        dbg!(res);
     }
 
     #[test]
@@ -496,4 +3010,791 @@ mod test {
         let res = serde_json::from_str::<Vec<Thing>>(COMMENT_SAMPLE_2).unwrap();
         dbg!(res);
     }
+
+    #[test]
+    fn comment_fields() {
+        let things: Vec<Thing> = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let comments = things[1].data.as_listing().unwrap();
+        let comment = |name: &str| {
+            comments
+                .walk_comments()
+                .map(|(comment, _)| comment)
+                .find(|comment| &*comment.name == name)
+                .unwrap()
+        };
+
+        let stickied = comment("t1_fus2s8m");
+        assert!(stickied.stickied);
+        assert_eq!(stickied.depth, Some(0));
+        assert_eq!(
+            &*stickied.permalink,
+            "/r/dankvideos/comments/h8p0py/pp_removal_time/fus2s8m/"
+        );
+
+        let collapsed = comment("t1_fuszs3s");
+        assert!(collapsed.collapsed);
+        assert_eq!(
+            collapsed.collapsed_reason.as_deref(),
+            Some("comment score below threshold")
+        );
+
+        assert_eq!(comment("t1_futc8id").controversiality, 1);
+        assert!(comments
+            .walk_comments()
+            .any(|(comment, _)| comment.is_submitter));
+
+        // Some endpoints, like the inbox, omit these fields.
+        let mut value: serde_json::Value = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let mut child = value[1]["data"]["children"][0].take();
+        let data = child["data"].as_object_mut().unwrap();
+        for key in [
+            "permalink",
+            "is_submitter",
+            "stickied",
+            "collapsed",
+            "collapsed_reason",
+            "controversiality",
+        ] {
+            data.remove(key);
+        }
+        let thing: Thing = serde_json::from_value(child).unwrap();
+        let comment = thing.data.as_comment().unwrap();
+        assert!(comment.permalink.is_empty());
+        assert!(!comment.stickied);
+        assert_eq!(comment.controversiality, 0);
+    }
+
+    #[test]
+    fn author_status() {
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let links: Vec<_> = subreddit
+            .data
+            .as_listing()
+            .unwrap()
+            .children
+            .iter()
+            .filter_map(|thing| thing.data.as_link())
+            .collect();
+        assert_eq!(
+            links.iter().filter(|link| link.is_author_cakeday()).count(),
+            5
+        );
+        assert!(links.iter().any(|link| link.is_author_premium()));
+        assert!(!links.iter().any(|link| link.is_author_blocked()));
+
+        let things: Vec<Thing> = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let comments = things[1].data.as_listing().unwrap();
+        assert!(comments
+            .walk_comments()
+            .any(|(comment, _)| comment.is_author_premium()));
+    }
+
+    #[test]
+    fn vote_direction() {
+        for direction in [
+            VoteDirection::Up,
+            VoteDirection::Neutral,
+            VoteDirection::Down,
+        ] {
+            assert_eq!(VoteDirection::from(direction.to_likes()), direction);
+        }
+        assert_eq!(VoteDirection::Down.as_str(), "-1");
+        assert_eq!(VoteDirection::Up.as_score(), 1);
+
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let mut link = subreddit
+            .data
+            .into_listing()
+            .unwrap()
+            .children
+            .remove(0)
+            .data
+            .into_link()
+            .unwrap();
+        assert!(link.my_vote().is_neutral());
+        link.likes = Some(false);
+        assert!(link.my_vote().is_down());
+
+        // The vote of a logged in user is read from the likes field of the response.
+        let mut value: serde_json::Value = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let mut child = value["data"]["children"][0].take();
+        child["data"]["likes"] = serde_json::Value::from(true);
+        let thing: Thing = serde_json::from_value(child).unwrap();
+        assert!(thing.data.as_link().unwrap().my_vote().is_up());
+
+        let mut value: serde_json::Value = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let mut child = value[1]["data"]["children"][0].take();
+        child["data"]["likes"] = serde_json::Value::from(false);
+        let thing: Thing = serde_json::from_value(child).unwrap();
+        assert!(thing.data.as_comment().unwrap().my_vote().is_down());
+
+        let votable: Votable =
+            serde_json::from_str(r#"{"ups": 1, "downs": 0, "likes": true}"#).unwrap();
+        assert!(votable.my_vote().is_up());
+    }
+
+    #[test]
+    fn estimated_votes() {
+        assert_eq!(estimate_votes(2, 0.625), Some((5, 3)));
+        assert_eq!(estimate_votes(1, 1.0), Some((1, 0)));
+        assert_eq!(estimate_votes(-3, 0.2), Some((1, 4)));
+        assert_eq!(estimate_votes(0, 0.5), None);
+        assert_eq!(estimate_votes(10, 0.5), None);
+        assert_eq!(estimate_votes(10, 0.2), None);
+
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        for thing in subreddit.data.as_listing().unwrap().children.iter() {
+            let link = thing.data.as_link().unwrap();
+            assert!((0.0..=1.0).contains(&link.upvote_ratio.unwrap()));
+            if let (Some(upvotes), Some(downvotes)) =
+                (link.estimated_upvotes(), link.estimated_downvotes())
+            {
+                assert!((upvotes as i64 - downvotes as i64 - link.score).abs() <= 1);
+            }
+        }
+
+        // Crosspost parents and some search results omit the ratio.
+        let mut value: serde_json::Value = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let mut child = value["data"]["children"][0].take();
+        child["data"]["score"] = serde_json::Value::from(-5);
+        child["data"]
+            .as_object_mut()
+            .unwrap()
+            .remove("upvote_ratio");
+        let thing: Thing = serde_json::from_value(child).unwrap();
+        let link = thing.data.as_link().unwrap();
+        assert_eq!(link.upvote_ratio, None);
+        assert_eq!(link.estimated_upvotes(), None);
+        assert_eq!(link.estimated_downvotes(), None);
+    }
+
+    #[test]
+    fn parse_reports() {
+        let reports: Vec<UserReport> =
+            serde_json::from_str(r#"[["Spam", 2, false, false], [null, 1]]"#).unwrap();
+        assert_eq!(
+            reports,
+            [
+                UserReport {
+                    reason: Some("Spam".into()),
+                    count: 2
+                },
+                UserReport {
+                    reason: None,
+                    count: 1
+                },
+            ]
+        );
+        assert_eq!(serde_json::to_string(&reports[0]).unwrap(), r#"["Spam",2]"#);
+
+        let report: ModReport = serde_json::from_str(r#"["Rule 1", "a_mod"]"#).unwrap();
+        assert_eq!(&*report.moderator, "a_mod");
+        assert!(serde_json::from_str::<ModReport>(r#"["Rule 1"]"#).is_err());
+
+        let things: Vec<Thing> = serde_json::from_str(COMMENT_SAMPLE_2).unwrap();
+        let link = things[0].data.as_listing().unwrap().children[0]
+            .data
+            .as_link()
+            .unwrap();
+        assert!(link.user_reports.is_empty());
+        assert!(link.report_reasons.is_none());
+    }
+
+    #[test]
+    fn moderation_fields() {
+        let mut subreddit: serde_json::Value = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let data = &mut subreddit["data"]["children"][0]["data"];
+        data["banned_by"] = "a_mod".into();
+        data["banned_at_utc"] = 1648000000.0.into();
+        data["mod_reason_title"] = "Rule 1".into();
+        data["mod_note"] = "repost".into();
+        let link: Link = serde_json::from_value(data.clone()).unwrap();
+        assert_eq!(link.banned_at_utc, Some(1648000000.0));
+        assert_eq!(link.mod_reason_title.as_deref(), Some("Rule 1"));
+        assert_eq!(link.mod_note.as_deref(), Some("repost"));
+        assert!(link.approved_at_utc.is_none());
+        assert!(link.is_removed());
+    }
+
+    #[test]
+    fn parse_moderated_subreddits() {
+        let subreddits: Vec<ModeratedSubreddit> = serde_json::from_str(
+            r#"[{
+                "banner_img": "",
+                "banner_size": null,
+                "community_icon": "https://styles.redditmedia.com/t5_2qh1o/styles/communityIcon_abc.png?width=256&amp;s=1",
+                "display_name": "aww",
+                "display_name_prefixed": "r/aww",
+                "icon_img": "",
+                "icon_size": null,
+                "key_color": "",
+                "mod_permissions": ["posts", "wiki"],
+                "name": "t5_2qh1o",
+                "over_18": false,
+                "primary_color": "",
+                "sr": "aww",
+                "sr_display_name_prefixed": "r/aww",
+                "subreddit_type": "public",
+                "subscribers": 100,
+                "title": "A subreddit for cute and cuddly pictures",
+                "url": "/r/aww/",
+                "user_is_subscriber": true,
+                "created": 1201242956.0,
+                "created_utc": 1201242956.0
+            }]"#,
+        )
+        .unwrap();
+        let subreddit = &subreddits[0];
+        assert_eq!(&*subreddit.display_name, "aww");
+        assert_eq!(&*subreddit.display_name_prefixed, "r/aww");
+        assert!(subreddit.has_permission("wiki"));
+        assert!(!subreddit.has_permission("access"));
+        assert!(!subreddit.has_full_permissions());
+        assert_eq!(
+            subreddit.icon().map(|url| url.as_str()),
+            Some("https://styles.redditmedia.com/t5_2qh1o/styles/communityIcon_abc.png?width=256&s=1")
+        );
+    }
+
+    #[test]
+    fn parse_gildings() {
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_2).unwrap();
+        let gildings: Vec<_> = subreddit
+            .data
+            .as_listing()
+            .unwrap()
+            .children
+            .iter()
+            .map(|thing| thing.data.as_link().unwrap().gildings)
+            .filter(|gildings| !gildings.is_empty())
+            .collect();
+        assert!(gildings.contains(&Gildings {
+            gid_2: 1,
+            ..Gildings::default()
+        }));
+
+        let things: Vec<Thing> = serde_json::from_str(COMMENT_SAMPLE_1).unwrap();
+        let link = things[0].data.as_listing().unwrap().children[0]
+            .data
+            .as_link()
+            .unwrap();
+        assert_eq!(link.gildings.gid_1, 1);
+        assert_eq!(link.gildings.total(), 1);
+    }
+
+    #[test]
+    fn merge_listings() {
+        let page = |names: &[&str], after: Option<&str>| -> Listing {
+            let children: Vec<_> = names
+                .iter()
+                .map(|name| {
+                    serde_json::json!({
+                        "kind": "more",
+                        "name": name,
+                        "data": {"children": []},
+                    })
+                })
+                .collect();
+            serde_json::from_value(serde_json::json!({
+                "after": after,
+                "before": null,
+                "modhash": "",
+                "children": children,
+            }))
+            .unwrap()
+        };
+
+        let mut listing = page(&["t1_a", "t1_b"], Some("t1_b"));
+        listing.merge(page(&["t1_b", "t1_c"], Some("t1_c")));
+        listing.merge(page(&["t1_d"], None));
+        let names: Vec<_> = listing
+            .children
+            .iter()
+            .filter_map(|thing| thing.fullname())
+            .collect();
+        assert_eq!(names, ["t1_a", "t1_b", "t1_c", "t1_d"]);
+        assert!(listing.after.is_none());
+    }
+
+    #[test]
+    fn listing_into_iter() {
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let listing = subreddit.data.into_listing().unwrap();
+        let names: Vec<_> = (&*listing)
+            .into_iter()
+            .filter_map(|thing| thing.fullname().map(Box::<str>::from))
+            .collect();
+        assert_eq!(names.len(), listing.children.len());
+
+        let links: Vec<Box<Link>> = listing
+            .into_iter()
+            .filter_map(|thing| thing.data.into_link())
+            .collect();
+        assert!(links
+            .iter()
+            .map(|link| &*link.name)
+            .eq(names.iter().map(|name| &**name)));
+
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_2).unwrap();
+        let listing = subreddit.data.into_listing().unwrap();
+        let count = listing.children.len();
+        assert_eq!(listing.into_children().len(), count);
+    }
+
+    #[test]
+    fn award_icons() {
+        let subreddit: Thing = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let listing = subreddit.data.as_listing().unwrap();
+        let award = listing
+            .children
+            .iter()
+            .filter_map(|thing| thing.data.as_link())
+            .flat_map(|link| link.all_awardings.iter())
+            .find(|award| &*award.name == "Home Time")
+            .unwrap();
+
+        assert_eq!(award.icon(40).map(|icon| icon.width), Some(48));
+        assert_eq!(award.icon(48).map(|icon| icon.width), Some(48));
+        assert_eq!(award.icon(0).map(|icon| icon.width), Some(16));
+        assert_eq!(award.static_icon(1000).map(|icon| icon.width), Some(128));
+        assert!(award.icon(32).unwrap().url.contains("width=32"));
+        assert!(pick_icon(&[], 32).is_none());
+    }
+
+    #[test]
+    fn minimal_award() {
+        let award: Awarding =
+            serde_json::from_str(r#"{"id": "gid_1", "name": "Silver", "count": 2}"#).unwrap();
+        assert_eq!(&*award.id, "gid_1");
+        assert_eq!(award.count, 2);
+        assert!(award.icon_url.is_empty());
+        assert!(award.icon(32).is_none());
+    }
+
+    #[test]
+    fn parse_unknown_kind() {
+        let json = r#"{
+            "kind": "Listing",
+            "data": {
+                "before": null,
+                "after": null,
+                "modhash": "",
+                "children": [
+                    {"kind": "modaction", "data": {"action": "removelink", "mod": "spez"}},
+                    {"data": {"children": ["abc"]}, "kind": "more"}
+                ]
+            }
+        }"#;
+        let thing: Thing = serde_json::from_str(json).unwrap();
+        let listing = thing.data.as_listing().unwrap();
+
+        let ThingData::Unknown { kind, data } = &listing.children[0].data else {
+            panic!("expected an unknown kind");
+        };
+        assert_eq!(&**kind, "modaction");
+        assert_eq!(data["action"], "removelink");
+        assert!(
+            matches!(&listing.children[1].data, ThingData::More(more) if more.children.len() == 1)
+        );
+
+        let value = serde_json::to_value(&thing).unwrap();
+        assert_eq!(value["data"]["children"][0]["kind"], "modaction");
+        assert_eq!(value["data"]["children"][0]["data"]["mod"], "spez");
+        assert_eq!(value["data"]["children"][1]["kind"], "more");
+    }
+
+    #[test]
+    fn parse_collection() {
+        let mut subreddit: serde_json::Value = serde_json::from_str(SUBREDDIT_SAMPLE_1).unwrap();
+        let link = subreddit["data"]["children"][0].take();
+        let link_id = link["data"]["name"].clone();
+
+        let json = serde_json::json!({
+            "collection_id": "0c5d5d5a-1234-4c1b-9d89-0123456789ab",
+            "subreddit_id": "t5_2zmfe",
+            "title": "Part 1",
+            "description": "",
+            "author_name": "spez",
+            "author_id": "t2_1w72",
+            "permalink": "https://www.reddit.com/r/dankmemes/collection/0c5d5d5a-1234-4c1b-9d89-0123456789ab",
+            "link_ids": [link_id],
+            "sorted_links": {
+                "kind": "Listing",
+                "data": {"before": null, "after": null, "modhash": "", "children": [link]}
+            },
+            "display_layout": "GALLERY",
+            "primary_link_id": link_id,
+            "created_at_utc": 1650000000.0,
+            "last_update_utc": 1650000100.0
+        });
+        let collection: Collection = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            collection.display_layout,
+            Some(CollectionDisplayLayout::Gallery)
+        );
+        let links: Vec<_> = collection.links().map(|link| &*link.name).collect();
+        assert_eq!(links, [&*collection.link_ids[0]]);
+    }
+
+    #[test]
+    fn parse_structured_styles() {
+        let styles: StructuredStyles = serde_json::from_str(
+            r##"{
+                "primaryColor": "#FF4500",
+                "communityIcon": "https://styles.redditmedia.com/t5_2qh1o/styles/communityIcon.png",
+                "bannerBackgroundImage": "",
+                "legacyBannerBackgroundImage": "https://styles.redditmedia.com/t5_2qh1o/styles/bannerBackgroundImage.png",
+                "mobileBannerImage": null,
+                "bannerHeight": "medium",
+                "postTitleColor": null
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(styles.primary_color.as_deref(), Some("#FF4500"));
+        assert_eq!(
+            styles.banner_image(),
+            Some("https://styles.redditmedia.com/t5_2qh1o/styles/bannerBackgroundImage.png")
+        );
+        assert!(styles.post_title_color.is_none());
+    }
+
+    #[test]
+    fn parse_partial_users() {
+        let users: std::collections::HashMap<Box<str>, PartialUser> = serde_json::from_str(
+            r##"{
+                "t2_1w72": {
+                    "comment_karma": 1000,
+                    "created_utc": 1118030400.0,
+                    "link_karma": 2000,
+                    "name": "spez",
+                    "profile_color": "",
+                    "profile_img": "https://www.redditstatic.com/avatars/avatar_default_01_FF4500.png",
+                    "profile_over_18": false
+                }
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(&*users["t2_1w72"].name, "spez");
+        assert_eq!(users["t2_1w72"].link_karma, 2000);
+    }
+
+    #[test]
+    fn subreddit_settings_form() {
+        let settings: SubredditSettings = serde_json::from_str(
+            r##"{
+                "subreddit_id": "t5_2qh1o",
+                "title": "A subreddit for cute and cuddly pictures",
+                "public_description": "Things that make you go AWW!",
+                "description": "# Rules",
+                "submit_text": "",
+                "subreddit_type": "public",
+                "content_options": "any",
+                "language": "en",
+                "over_18": false,
+                "allow_images": true,
+                "allow_videos": true,
+                "allow_galleries": false,
+                "allow_polls": false,
+                "spoilers_enabled": true,
+                "spam_links": "high",
+                "spam_selfposts": "low",
+                "spam_comments": "all",
+                "wikimode": "modonly",
+                "wiki_edit_age": 0,
+                "wiki_edit_karma": null,
+                "comment_score_hide_mins": 60,
+                "suggested_comment_sort": "qa",
+                "header_hover_text": "aww",
+                "key_color": "#ff4500",
+                "restrict_posting": true,
+                "related_subreddits": []
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(settings.wikimode, WikiMode::ModOnly);
+        assert_eq!(settings.spam_comments, SpamLevel::All);
+
+        let form = settings.site_admin_form();
+        let get = |name: &str| {
+            form.iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(get("sr"), Some("t5_2qh1o"));
+        assert_eq!(get("type"), Some("public"));
+        assert_eq!(get("link_type"), Some("any"));
+        assert_eq!(get("lang"), Some("en"));
+        assert_eq!(get("allow_galleries"), Some("false"));
+        assert_eq!(get("spam_links"), Some("high"));
+        assert_eq!(get("wikimode"), Some("modonly"));
+        assert_eq!(get("wiki_edit_karma"), None);
+        assert_eq!(get("comment_score_hide_mins"), Some("60"));
+        assert_eq!(get("suggested_comment_sort"), Some("qa"));
+        assert_eq!(get("header_hover_text"), Some("aww"));
+        assert_eq!(get("restrict_posting"), Some("true"));
+        assert_eq!(get("related_subreddits"), None);
+    }
+
+    #[test]
+    fn parse_subreddit_assets() {
+        let thing: Thing = serde_json::from_str(
+            r##"{
+                "kind": "t5",
+                "data": {
+                    "id": "2qh1o",
+                    "name": "t5_2qh1o",
+                    "display_name": "aww",
+                    "display_name_prefixed": "r/aww",
+                    "title": "A subreddit for cute and cuddly pictures",
+                    "subscribers": 37000000,
+                    "over18": false,
+                    "created_utc": 1201230879.0,
+                    "icon_img": "",
+                    "icon_size": null,
+                    "community_icon": "https://styles.redditmedia.com/t5_2qh1o/styles/communityIcon_abc.png?width=256&amp;s=123",
+                    "banner_background_image": "",
+                    "banner_img": "https://b.thumbs.redditmedia.com/banner.png",
+                    "header_img": null,
+                    "header_size": [120, 40],
+                    "primary_color": "#0079D3",
+                    "key_color": "",
+                    "banner_background_color": "not a color"
+                }
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(thing.fullname(), Some("t5_2qh1o"));
+        assert_eq!(thing.created_utc(), Some(1201230879.0));
+
+        let subreddit = thing.data.as_subreddit().unwrap();
+        assert_eq!(
+            subreddit.icon().map(url::Url::as_str),
+            Some("https://styles.redditmedia.com/t5_2qh1o/styles/communityIcon_abc.png?width=256&s=123")
+        );
+        assert_eq!(
+            subreddit.banner().map(url::Url::as_str),
+            Some("https://b.thumbs.redditmedia.com/banner.png")
+        );
+        assert!(subreddit.header_img.is_none());
+        assert_eq!(subreddit.header_size, Some([120, 40]));
+        assert_eq!(
+            subreddit.color(),
+            Some(Color {
+                red: 0x00,
+                green: 0x79,
+                blue: 0xd3
+            })
+        );
+        assert!(subreddit.key_color.is_none());
+        assert!(subreddit.banner_background_color.is_none());
+
+        assert_eq!("#f40".parse::<Color>().unwrap().to_string(), "#ff4400");
+        assert!("ff4500".parse::<Color>().is_err());
+
+        let json = serde_json::to_value(&thing).unwrap();
+        assert_eq!(json["kind"], "t5");
+        assert_eq!(json["data"]["primary_color"], "#0079d3");
+        assert_eq!(json["data"]["icon_img"], "");
+    }
+
+    #[test]
+    fn parse_sr_detail() {
+        let sr_detail: SubredditDetail = serde_json::from_str(
+            r##"{
+                "name": "t5_2qh1o",
+                "display_name": "aww",
+                "display_name_prefixed": "r/aww",
+                "title": "A subreddit for cute and cuddly pictures",
+                "public_description": "Things that make you go AWW!",
+                "url": "/r/aww/",
+                "subscribers": 37000000,
+                "icon_img": "",
+                "community_icon": "https://styles.redditmedia.com/t5_2qh1o/styles/communityIcon.png",
+                "banner_img": "",
+                "primary_color": "#ff4500",
+                "key_color": "",
+                "over_18": false,
+                "quarantine": false,
+                "subreddit_type": "public",
+                "created_utc": 1201230879.0,
+                "user_is_banned": null
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(&*sr_detail.display_name, "aww");
+        assert_eq!(sr_detail.subscribers, Some(37000000));
+    }
+
+    #[test]
+    fn removed_and_deleted() {
+        let thing = serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_1).unwrap();
+        let listing = thing.data.into_listing().unwrap();
+        for child in listing.children {
+            let link = child.data.into_link().unwrap();
+            assert!(!link.is_removed());
+            assert!(!link.is_deleted());
+        }
+
+        let link = Link {
+            selftext: "[removed]".into(),
+            ..Link::default()
+        };
+        assert!(link.is_removed());
+        let link = Link {
+            author: "[deleted]".into(),
+            removed_by_category: Some("moderator".into()),
+            ..Link::default()
+        };
+        assert!(link.is_removed());
+        assert!(!link.is_deleted());
+        let link = Link {
+            author: "[deleted]".into(),
+            selftext: "[deleted]".into(),
+            removed_by_category: Some("deleted".into()),
+            ..Link::default()
+        };
+        assert!(!link.is_removed());
+        assert!(link.is_deleted());
+
+        let comment = Comment {
+            author: "[deleted]".into(),
+            body: "[removed]".into(),
+            ..Comment::default()
+        };
+        assert!(comment.is_removed());
+        assert!(!comment.is_deleted());
+        let comment = Comment {
+            body: "still visible to mods".into(),
+            banned_by: Some("AutoModerator".into()),
+            ..Comment::default()
+        };
+        assert!(comment.is_removed());
+        assert!(!comment.is_deleted());
+    }
+
+    #[test]
+    fn removed_and_deleted_samples() {
+        let thing = serde_json::from_str::<Thing>(SUBREDDIT_SAMPLE_4).unwrap();
+        let listing = thing.data.into_listing().unwrap();
+        let crossposts: Vec<_> = listing
+            .children
+            .iter()
+            .filter_map(|child| child.data.as_link())
+            .flat_map(|link| link.crosspost_parent_list.iter().flatten())
+            .collect();
+        let removed: Vec<_> = crossposts
+            .iter()
+            .filter(|link| link.is_removed())
+            .map(|link| &*link.name)
+            .collect();
+        let deleted: Vec<_> = crossposts
+            .iter()
+            .filter(|link| link.is_deleted())
+            .map(|link| &*link.name)
+            .collect();
+        assert_eq!(
+            removed,
+            [
+                "t3_ut9gbd",
+                "t3_urmdat",
+                "t3_uqitw0",
+                "t3_uosnic",
+                "t3_uli2qp",
+                "t3_uk6s6c"
+            ]
+        );
+        assert_eq!(deleted, ["t3_ul55k9"]);
+
+        let things = serde_json::from_str::<Vec<Thing>>(COMMENT_SAMPLE_2).unwrap();
+        let comments = things[1].data.as_listing().unwrap();
+        let removed: Vec<_> = comments
+            .walk_comments()
+            .filter(|(comment, _)| comment.is_removed())
+            .map(|(comment, _)| &*comment.name)
+            .collect();
+        assert_eq!(
+            removed,
+            ["t1_fusytwa", "t1_futhcdx", "t1_fuu8f58", "t1_fuu8gs9"]
+        );
+        assert!(!comments
+            .walk_comments()
+            .any(|(comment, _)| comment.is_deleted()));
+    }
+
+    #[test]
+    fn parse_trophy_list_1() {
+        let res = serde_json::from_str::<Thing>(TROPHY_LIST_SAMPLE_1).unwrap();
+        dbg!(res);
+    }
+
+    #[test]
+    fn parse_subreddit_rules_1() {
+        #[derive(serde::Deserialize)]
+        struct SubredditRules {
+            rules: Vec<SubredditRule>,
+        }
+
+        let res = serde_json::from_str::<SubredditRules>(SUBREDDIT_RULES_SAMPLE_1).unwrap();
+        assert_eq!(res.rules.len(), 3);
+        assert_eq!(res.rules[0].kind, SubredditRuleKind::All);
+        assert_eq!(&*res.rules[1].short_name, "No reposts");
+        assert!(res.rules[1].description_html.is_none());
+        assert_eq!(res.rules[2].kind, SubredditRuleKind::Comment);
+        assert_eq!(res.rules[2].priority, 2);
+    }
+
+    #[test]
+    fn parse_karma_list_1() {
+        #[derive(serde::Deserialize)]
+        struct KarmaList {
+            data: Vec<SubredditKarma>,
+        }
+
+        let res = serde_json::from_str::<KarmaList>(KARMA_LIST_SAMPLE_1).unwrap();
+        assert_eq!(res.data.len(), 3);
+        assert_eq!(&*res.data[0].subreddit, "rust");
+        assert_eq!(res.data[0].comment_karma, 1204);
+        assert_eq!(res.data[0].link_karma, 356);
+        assert_eq!(res.data[2].comment_karma, -3);
+    }
+
+    #[test]
+    fn parse_multireddit_1() {
+        let res = serde_json::from_str::<Thing>(MULTIREDDIT_SAMPLE_1).unwrap();
+        dbg!(res);
+    }
+
+    #[test]
+    fn parse_wiki_page_1() {
+        let res = serde_json::from_str::<Thing>(WIKI_PAGE_SAMPLE_1).unwrap();
+        let wiki_page = match &res.data {
+            ThingData::WikiPage(wiki_page) => wiki_page,
+            _ => panic!("expected a wikipage"),
+        };
+        assert_eq!(wiki_page.revision_by_name(), Some("example_mod"));
+        dbg!(res);
+    }
+
+    #[test]
+    fn serialize_preferences_patch() {
+        let patch = Preferences {
+            over_18: Some(true),
+            lang: Some("en".into()),
+            ..Preferences::default()
+        };
+        let json = serde_json::to_value(&patch).unwrap();
+        assert_eq!(json, serde_json::json!({ "over_18": true, "lang": "en" }));
+    }
+
+    #[test]
+    fn parse_live_thread_1() {
+        let res = serde_json::from_str::<Thing>(LIVE_THREAD_SAMPLE_1).unwrap();
+        dbg!(res);
+    }
+
+    #[test]
+    fn parse_live_updates_1() {
+        let res = serde_json::from_str::<Thing>(LIVE_UPDATES_SAMPLE_1).unwrap();
+        dbg!(res);
+    }
 }
@@ -0,0 +1,117 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    ops::Deref,
+};
+
+#[cfg(not(feature = "compact-str"))]
+type Inner = Box<str>;
+
+#[cfg(feature = "compact-str")]
+type Inner = compact_str::CompactString;
+
+/// A string for short fields that repeat across many items, like subreddit names, ids, and flair types.
+///
+/// With the `compact-str` feature, short strings are stored inline,
+/// so parsing a page of a listing does not allocate each of these.
+/// The api of this type is the same either way.
+#[derive(
+    Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(transparent)]
+pub struct CompactStr(Inner);
+
+impl CompactStr {
+    /// Get this as a str.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for CompactStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for CompactStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for CompactStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for CompactStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for CompactStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl From<&str> for CompactStr {
+    fn from(value: &str) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<String> for CompactStr {
+    fn from(value: String) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<Box<str>> for CompactStr {
+    // Without the `compact-str` feature, this conversion does nothing.
+    #[allow(clippy::useless_conversion)]
+    fn from(value: Box<str>) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<CompactStr> for String {
+    fn from(value: CompactStr) -> Self {
+        value.0.into()
+    }
+}
+
+impl PartialEq<str> for CompactStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for CompactStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compact_str() {
+        let value = CompactStr::from("dankmemes");
+        assert_eq!(value, "dankmemes");
+        assert_eq!(value.len(), 9);
+        assert_eq!(format!("{value} {value:?}"), "dankmemes \"dankmemes\"");
+        assert_eq!(String::from(value.clone()), "dankmemes");
+
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "\"dankmemes\"");
+        assert_eq!(serde_json::from_str::<CompactStr>(&json).unwrap(), value);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// The order of the posts of a listing, like a subreddit or the front page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sort {
+    /// Popular posts, weighted by age
+    Hot,
+
+    /// Newest posts first
+    New,
+
+    /// Highest scoring posts first, within a [`TimeRange`]
+    Top,
+
+    /// Posts that are quickly gaining votes
+    Rising,
+
+    /// Posts with many upvotes and downvotes, within a [`TimeRange`]
+    Controversial,
+
+    /// Personalized ranking. Only supported by the front page
+    Best,
+}
+
+impl Sort {
+    /// All sorts
+    pub const ALL: &'static [Self] = &[
+        Self::Hot,
+        Self::New,
+        Self::Top,
+        Self::Rising,
+        Self::Controversial,
+        Self::Best,
+    ];
+
+    /// Get the value reddit expects for this sort.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hot => "hot",
+            Self::New => "new",
+            Self::Top => "top",
+            Self::Rising => "rising",
+            Self::Controversial => "controversial",
+            Self::Best => "best",
+        }
+    }
+
+    /// Returns `true` if this sort takes a [`TimeRange`].
+    pub fn has_time_range(self) -> bool {
+        matches!(self, Self::Top | Self::Controversial)
+    }
+}
+
+/// The order of the comments of a post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentSort {
+    /// Reddit's "best" sort. This also parses from "best"
+    #[serde(alias = "best")]
+    Confidence,
+
+    /// Highest scoring comments first
+    Top,
+
+    /// Newest comments first
+    New,
+
+    /// Comments with many upvotes and downvotes first
+    Controversial,
+
+    /// Oldest comments first
+    Old,
+
+    /// A random order
+    Random,
+
+    /// Comments by the author of the post, and the comments they replied to, first
+    Qa,
+
+    /// Newest comments first, updated live
+    Live,
+}
+
+impl CommentSort {
+    /// All comment sorts
+    pub const ALL: &'static [Self] = &[
+        Self::Confidence,
+        Self::Top,
+        Self::New,
+        Self::Controversial,
+        Self::Old,
+        Self::Random,
+        Self::Qa,
+        Self::Live,
+    ];
+
+    /// Get the value reddit expects for this sort.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Confidence => "confidence",
+            Self::Top => "top",
+            Self::New => "new",
+            Self::Controversial => "controversial",
+            Self::Old => "old",
+            Self::Random => "random",
+            Self::Qa => "qa",
+            Self::Live => "live",
+        }
+    }
+}
+
+/// The time range of a [`Sort::Top`] or [`Sort::Controversial`] listing, sent as `t`
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeRange {
+    /// The past hour
+    Hour,
+
+    /// The past day. This is reddit's default
+    #[default]
+    Day,
+
+    /// The past week
+    Week,
+
+    /// The past month
+    Month,
+
+    /// The past year
+    Year,
+
+    /// All time
+    All,
+}
+
+impl TimeRange {
+    /// All time ranges
+    pub const ALL: &'static [Self] = &[
+        Self::Hour,
+        Self::Day,
+        Self::Week,
+        Self::Month,
+        Self::Year,
+        Self::All,
+    ];
+
+    /// Get the value reddit expects for this time range.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+            Self::All => "all",
+        }
+    }
+}
+
+impl std::fmt::Display for Sort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Display for CommentSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Sort {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse(Self::ALL, Self::as_str, "sort", value)
+    }
+}
+
+impl FromStr for CommentSort {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.trim().eq_ignore_ascii_case("best") {
+            return Ok(Self::Confidence);
+        }
+
+        parse(Self::ALL, Self::as_str, "comment sort", value)
+    }
+}
+
+impl FromStr for TimeRange {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        parse(Self::ALL, Self::as_str, "time range", value)
+    }
+}
+
+/// Find the variant whose value matches a string, ignoring case.
+fn parse<T>(
+    variants: &[T],
+    as_str: fn(T) -> &'static str,
+    kind: &'static str,
+    value: &str,
+) -> Result<T, Error>
+where
+    T: Copy,
+{
+    let trimmed = value.trim();
+    variants
+        .iter()
+        .copied()
+        .find(|&variant| as_str(variant).eq_ignore_ascii_case(trimmed))
+        .ok_or_else(|| Error::InvalidParam {
+            kind,
+            value: value.into(),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for &sort in Sort::ALL {
+            assert_eq!(sort.to_string().parse::<Sort>().unwrap(), sort);
+        }
+        for &sort in CommentSort::ALL {
+            assert_eq!(sort.to_string().parse::<CommentSort>().unwrap(), sort);
+        }
+        for &time_range in TimeRange::ALL {
+            assert_eq!(
+                time_range.to_string().parse::<TimeRange>().unwrap(),
+                time_range
+            );
+        }
+
+        assert_eq!("Top".parse::<Sort>().unwrap(), Sort::Top);
+        assert_eq!(
+            "best".parse::<CommentSort>().unwrap(),
+            CommentSort::Confidence
+        );
+        assert_eq!(
+            serde_json::from_str::<CommentSort>("\"best\"").unwrap(),
+            CommentSort::Confidence
+        );
+        assert_eq!(serde_json::to_string(&TimeRange::Week).unwrap(), "\"week\"");
+
+        let error = "fortnight".parse::<TimeRange>().unwrap_err();
+        assert!(error.is_invalid_param());
+        assert_eq!(error.to_string(), "invalid time range \"fortnight\"");
+    }
+}
@@ -0,0 +1,159 @@
+/// A lease to upload a file to reddit's S3 bucket, returned by the asset upload endpoints.
+#[derive(Debug, serde::Deserialize)]
+#[serde(try_from = "RawS3UploadLease")]
+pub(crate) struct S3UploadLease {
+    /// the url to upload to, without a scheme, like "//reddit-uploaded-emoji.s3-accelerate.amazonaws.com"
+    action: Box<str>,
+
+    /// the form fields that must be sent with the file, in order
+    fields: Vec<S3UploadField>,
+
+    /// the value of the "key" field
+    key: Box<str>,
+}
+
+#[derive(serde::Deserialize)]
+struct RawS3UploadLease {
+    action: Box<str>,
+    fields: Vec<S3UploadField>,
+}
+
+impl TryFrom<RawS3UploadLease> for S3UploadLease {
+    type Error = &'static str;
+
+    fn try_from(lease: RawS3UploadLease) -> Result<Self, Self::Error> {
+        let key = lease
+            .fields
+            .iter()
+            .find(|field| &*field.name == "key")
+            .ok_or("the upload lease has no key field")?
+            .value
+            .clone();
+
+        Ok(Self {
+            action: lease.action,
+            fields: lease.fields,
+            key,
+        })
+    }
+}
+
+/// A form field of an [`S3UploadLease`].
+#[derive(Debug, serde::Deserialize)]
+struct S3UploadField {
+    name: Box<str>,
+    value: Box<str>,
+}
+
+impl S3UploadLease {
+    /// Get the url to upload the file to.
+    pub(crate) fn url(&self) -> String {
+        if self.action.starts_with("//") {
+            format!("https:{}", self.action)
+        } else {
+            self.action.to_string()
+        }
+    }
+
+    /// Get the key the file is stored under, which is used to refer to it after uploading.
+    pub(crate) fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Encode the fields of this lease and a file as a multipart form.
+    ///
+    /// Returns the content type, including the boundary, and the body.
+    pub(crate) fn multipart_body(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        file: &[u8],
+    ) -> (String, Vec<u8>) {
+        let boundary = multipart_boundary(file);
+
+        let mut body = Vec::with_capacity(file.len() + 1024);
+        for field in self.fields.iter() {
+            body.extend_from_slice(
+                format!(
+                    "--{boundary}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    field.name, field.value
+                )
+                .as_bytes(),
+            );
+        }
+        // S3 ignores fields after the file, so it must be last.
+        body.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {mime_type}\r\n\r\n",
+                file_name.replace('"', "")
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(file);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+        (format!("multipart/form-data; boundary={boundary}"), body)
+    }
+}
+
+/// Pick a multipart boundary that does not occur in a file.
+fn multipart_boundary(file: &[u8]) -> String {
+    let mut boundary = String::from("reddit-rs-boundary");
+    let mut suffix = 0_u64;
+    while file
+        .windows(boundary.len())
+        .any(|window| window == boundary.as_bytes())
+    {
+        suffix += 1;
+        boundary = format!("reddit-rs-boundary-{suffix}");
+    }
+
+    boundary
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn multipart_body() {
+        let lease: S3UploadLease = serde_json::from_str(
+            r#"{
+                "action": "//uploads.example.com",
+                "fields": [
+                    {"name": "key", "value": "t5_abc/emoji.png"},
+                    {"name": "Content-Type", "value": "image/png"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(lease.url(), "https://uploads.example.com");
+        assert_eq!(lease.key(), "t5_abc/emoji.png");
+
+        let (content_type, body) = lease.multipart_body("emoji.png", "image/png", b"png data");
+        assert_eq!(
+            content_type,
+            "multipart/form-data; boundary=reddit-rs-boundary"
+        );
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "--reddit-rs-boundary\r\n\
+            Content-Disposition: form-data; name=\"key\"\r\n\r\n\
+            t5_abc/emoji.png\r\n\
+            --reddit-rs-boundary\r\n\
+            Content-Disposition: form-data; name=\"Content-Type\"\r\n\r\n\
+            image/png\r\n\
+            --reddit-rs-boundary\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"emoji.png\"\r\n\
+            Content-Type: image/png\r\n\r\n\
+            png data\r\n\
+            --reddit-rs-boundary--\r\n"
+        );
+
+        assert!(serde_json::from_str::<S3UploadLease>(r#"{"action": "", "fields": []}"#).is_err());
+        assert_eq!(
+            multipart_boundary(b"xx reddit-rs-boundary xx"),
+            "reddit-rs-boundary-1"
+        );
+    }
+}
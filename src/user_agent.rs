@@ -0,0 +1,288 @@
+use crate::error::Error;
+
+// Guesses for good defaults for the user agent.
+
+// TODO: Extract from target
+const DEFAULT_PLATFORM: &str = "pc";
+
+const DEFAULT_APP_ID: &str = env!("CARGO_PKG_NAME");
+const DEFAULT_APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// TODO: Is there really a good default to choose here?
+const DEFAULT_REDDIT_USERNAME: &str = "deleted";
+
+/// Words that show up in browser user-agents.
+///
+/// Reddit asks that clients never pretend to be a browser.
+const BROWSER_WORDS: &[&str] = &["mozilla", "applewebkit", "chrome", "safari", "gecko"];
+
+/// A user-agent in the format reddit asks for, like `linux:my-app:v1.0.0 (by /u/spez)`.
+///
+/// See https://github.com/reddit-archive/reddit/wiki/API#rules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserAgent {
+    platform: Box<str>,
+    app_id: Box<str>,
+    version: Box<str>,
+    username: Box<str>,
+}
+
+impl UserAgent {
+    /// Create a new [`UserAgentBuilder`].
+    pub fn builder() -> UserAgentBuilder {
+        UserAgentBuilder::new()
+    }
+
+    /// Create a user-agent from components as they are, without validating or normalizing them.
+    pub(crate) fn from_parts_unchecked(
+        platform: &str,
+        app_id: &str,
+        version: &str,
+        username: &str,
+    ) -> Self {
+        Self {
+            platform: platform.into(),
+            app_id: app_id.into(),
+            version: version.into(),
+            username: username.into(),
+        }
+    }
+
+    /// The target platform, like "linux"
+    pub fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    /// The unique id of the app
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    /// The version of the app, without the v prefix
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The reddit username of the developer, without the /u/ prefix
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+}
+
+impl Default for UserAgent {
+    /// The user-agent used if no other is set.
+    ///
+    /// Reddit may rate limit this more harshly than a user-agent that identifies the app.
+    fn default() -> Self {
+        Self {
+            platform: DEFAULT_PLATFORM.into(),
+            app_id: DEFAULT_APP_ID.into(),
+            version: DEFAULT_APP_VERSION.into(),
+            username: DEFAULT_REDDIT_USERNAME.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for UserAgent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:v{} (by /u/{})",
+            self.platform, self.app_id, self.version, self.username
+        )
+    }
+}
+
+impl std::str::FromStr for UserAgent {
+    type Err = Error;
+
+    /// Parse a user-agent in the format reddit asks for, like `linux:my-app:v1.0.0 (by /u/spez)`.
+    fn from_str(user_agent: &str) -> Result<Self, Self::Err> {
+        let invalid_format = || Error::InvalidUserAgent {
+            reason: "invalid format",
+        };
+
+        let (components, username) = user_agent
+            .trim()
+            .strip_suffix(')')
+            .and_then(|user_agent| user_agent.split_once(" (by "))
+            .ok_or_else(invalid_format)?;
+        let mut components = components.split(':');
+        let (Some(platform), Some(app_id), Some(version), None) = (
+            components.next(),
+            components.next(),
+            components.next(),
+            components.next(),
+        ) else {
+            return Err(invalid_format());
+        };
+
+        UserAgent::builder()
+            .platform(platform)
+            .app_id(app_id)
+            .version(version)
+            .username(username)
+            .build()
+    }
+}
+
+/// A builder for a [`UserAgent`].
+#[derive(Debug, Clone, Default)]
+pub struct UserAgentBuilder {
+    platform: Option<Box<str>>,
+    app_id: Option<Box<str>>,
+    version: Option<Box<str>>,
+    username: Option<Box<str>>,
+}
+
+impl UserAgentBuilder {
+    /// Create a new [`UserAgentBuilder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the target platform, like "linux" or "android".
+    ///
+    /// This defaults to "pc".
+    pub fn platform(mut self, platform: &str) -> Self {
+        self.platform = Some(platform.trim().into());
+        self
+    }
+
+    /// Set the unique id of the app, like "my-app".
+    pub fn app_id(mut self, app_id: &str) -> Self {
+        self.app_id = Some(app_id.trim().into());
+        self
+    }
+
+    /// Set the version of the app, like "1.0.0". A v prefix is removed.
+    pub fn version(mut self, version: &str) -> Self {
+        let version = version.trim();
+        let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the reddit username of the developer. A /u/ prefix is removed.
+    pub fn username(mut self, username: &str) -> Self {
+        let username = username.trim();
+        let username = username
+            .strip_prefix("/u/")
+            .or_else(|| username.strip_prefix("u/"))
+            .unwrap_or(username);
+        self.username = Some(username.into());
+        self
+    }
+
+    /// Build the [`UserAgent`].
+    ///
+    /// This fails with [`Error::InvalidUserAgent`] if a component is missing or does not follow reddit's rules.
+    pub fn build(self) -> Result<UserAgent, Error> {
+        let platform = self.platform.unwrap_or_else(|| DEFAULT_PLATFORM.into());
+        let app_id = self.app_id.ok_or(Error::InvalidUserAgent {
+            reason: "missing app id",
+        })?;
+        let version = self.version.ok_or(Error::InvalidUserAgent {
+            reason: "missing version",
+        })?;
+        let username = self.username.ok_or(Error::InvalidUserAgent {
+            reason: "missing username",
+        })?;
+
+        validate_component(&platform, "invalid platform")?;
+        validate_component(&app_id, "invalid app id")?;
+        validate_component(&version, "invalid version")?;
+
+        // Reddit usernames are 3 to 20 letters, numbers, underscores, or dashes.
+        let is_valid_username = (3..=20).contains(&username.len())
+            && username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !is_valid_username {
+            return Err(Error::InvalidUserAgent {
+                reason: "invalid username",
+            });
+        }
+
+        Ok(UserAgent {
+            platform,
+            app_id,
+            version,
+            username,
+        })
+    }
+}
+
+/// Validate a component of a user-agent that is separated by colons.
+fn validate_component(component: &str, reason: &'static str) -> Result<(), Error> {
+    let is_valid = !component.is_empty()
+        && component
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !matches!(c, ':' | '(' | ')'));
+    if !is_valid {
+        return Err(Error::InvalidUserAgent { reason });
+    }
+
+    let lower = component.to_ascii_lowercase();
+    if BROWSER_WORDS.iter().any(|word| lower.contains(word)) {
+        return Err(Error::InvalidUserAgent {
+            reason: "user-agents must not pretend to be a browser",
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn build_user_agent() {
+        let user_agent = UserAgent::builder()
+            .platform("linux")
+            .app_id("my-app")
+            .version("v1.0.0")
+            .username("/u/spez")
+            .build()
+            .unwrap();
+        assert_eq!(user_agent.to_string(), "linux:my-app:v1.0.0 (by /u/spez)");
+        assert_eq!(
+            UserAgent::default().to_string(),
+            format!("pc:reddit:v{DEFAULT_APP_VERSION} (by /u/deleted)")
+        );
+
+        let builder = UserAgent::builder().version("1.0.0").username("spez");
+        for app_id in ["", "my:app", "my app", "Mozilla/5.0"] {
+            let error = builder.clone().app_id(app_id).build().unwrap_err();
+            assert!(error.is_invalid_user_agent(), "app_id = {app_id}");
+        }
+
+        let error = builder
+            .clone()
+            .app_id("my-app")
+            .username("a")
+            .build()
+            .unwrap_err();
+        assert!(error.is_invalid_user_agent());
+        assert!(builder.build().unwrap_err().is_invalid_user_agent());
+    }
+
+    #[test]
+    fn parse_user_agent() {
+        let user_agent: UserAgent = "linux:my-app:v1.0.0 (by /u/spez)".parse().unwrap();
+        assert_eq!(user_agent.platform(), "linux");
+        assert_eq!(user_agent.app_id(), "my-app");
+        assert_eq!(user_agent.version(), "1.0.0");
+        assert_eq!(user_agent.username(), "spez");
+
+        for user_agent in [
+            "my-app/1.0",
+            "linux:my-app (by /u/spez)",
+            "a:b:c:d (by /u/spez)",
+        ] {
+            let error = user_agent.parse::<UserAgent>().unwrap_err();
+            assert!(error.is_invalid_user_agent(), "user_agent = {user_agent}");
+        }
+    }
+}
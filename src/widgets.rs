@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+
+/// The widgets of a subreddit, shown in the sidebar and topbar of new reddit
+/// See https://www.reddit.com/dev/api#GET_api_widgets
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct Widgets {
+    /// the widgets, keyed by id
+    pub items: HashMap<Box<str>, Widget>,
+
+    /// where the widgets are shown
+    pub layout: WidgetLayout,
+}
+
+impl Widgets {
+    /// Get a widget by id.
+    pub fn get(&self, id: &str) -> Option<&Widget> {
+        self.items.get(id)
+    }
+
+    /// Get the widgets of the sidebar, in the order they are shown.
+    ///
+    /// This does not include the id card and moderators widgets, which are always shown first.
+    pub fn sidebar(&self) -> impl Iterator<Item = &Widget> + '_ {
+        self.layout
+            .sidebar
+            .order
+            .iter()
+            .filter_map(|id| self.get(id))
+    }
+
+    /// Get the widgets of the topbar, in the order they are shown.
+    pub fn topbar(&self) -> impl Iterator<Item = &Widget> + '_ {
+        self.layout
+            .topbar
+            .order
+            .iter()
+            .filter_map(|id| self.get(id))
+    }
+}
+
+/// The placement of the widgets of a subreddit
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetLayout {
+    /// the id of the widget describing the subreddit, shown at the top of the sidebar
+    pub id_card_widget: Box<str>,
+
+    /// the id of the widget listing the moderators of the subreddit
+    pub moderator_widget: Box<str>,
+
+    /// the widgets of the topbar
+    pub topbar: WidgetOrder,
+
+    /// the widgets of the sidebar
+    pub sidebar: WidgetOrder,
+}
+
+/// An ordered list of widgets
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct WidgetOrder {
+    /// the ids of the widgets, in the order they are shown
+    pub order: Vec<Box<str>>,
+}
+
+/// A widget of a subreddit
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Widget {
+    /// A block of markdown text
+    Textarea(TextareaWidget),
+
+    /// A list of buttons linking to other pages
+    Button(ButtonWidget),
+
+    /// Upcoming events of a google calendar
+    Calendar(CalendarWidget),
+
+    /// A list of related subreddits
+    CommunityList(CommunityListWidget),
+
+    /// A widget that is not modeled yet, like the id card, moderators, rules, or menu widgets
+    #[serde(other)]
+    Other,
+}
+
+impl Widget {
+    /// Get the id of this widget.
+    ///
+    /// Returns `None` for [`Widget::Other`].
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::Textarea(widget) => Some(&widget.id),
+            Self::Button(widget) => Some(&widget.id),
+            Self::Calendar(widget) => Some(&widget.id),
+            Self::CommunityList(widget) => Some(&widget.id),
+            Self::Other => None,
+        }
+    }
+
+    /// Get the title of this widget.
+    ///
+    /// Returns `None` for [`Widget::Other`].
+    pub fn short_name(&self) -> Option<&str> {
+        match self {
+            Self::Textarea(widget) => Some(&widget.short_name),
+            Self::Button(widget) => Some(&widget.short_name),
+            Self::Calendar(widget) => Some(&widget.short_name),
+            Self::CommunityList(widget) => Some(&widget.short_name),
+            Self::Other => None,
+        }
+    }
+}
+
+/// A widget with a block of markdown text
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextareaWidget {
+    /// the id of the widget, like "widget_13fq5dfq3mhb0"
+    pub id: Box<str>,
+
+    /// the title of the widget
+    pub short_name: Box<str>,
+
+    /// the raw markdown text of the widget
+    pub text: Box<str>,
+
+    /// the formatted escaped HTML text of the widget
+    pub text_html: Box<str>,
+
+    /// the colors of the widget
+    #[serde(default)]
+    pub styles: WidgetStyles,
+}
+
+/// A widget with a list of buttons
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ButtonWidget {
+    /// the id of the widget
+    pub id: Box<str>,
+
+    /// the title of the widget
+    pub short_name: Box<str>,
+
+    /// the raw markdown text shown above the buttons. May be empty
+    #[serde(default)]
+    pub description: Box<str>,
+
+    /// the formatted escaped HTML text shown above the buttons
+    pub description_html: Option<Box<str>>,
+
+    /// the buttons
+    pub buttons: Vec<WidgetButton>,
+
+    /// the colors of the widget
+    #[serde(default)]
+    pub styles: WidgetStyles,
+}
+
+/// A button of a [`ButtonWidget`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum WidgetButton {
+    /// A button with text
+    #[serde(rename_all = "camelCase")]
+    Text {
+        /// the text of the button
+        text: Box<str>,
+
+        /// the url the button links to
+        url: Box<str>,
+
+        /// the border color of the button, like "#FF4500"
+        color: Option<Box<str>>,
+
+        /// the text color of the button
+        text_color: Option<Box<str>>,
+
+        /// the background color of the button
+        fill_color: Option<Box<str>>,
+    },
+
+    /// A button with an image
+    #[serde(rename_all = "camelCase")]
+    Image {
+        /// the alt text of the button
+        text: Box<str>,
+
+        /// the url the button links to
+        link_url: Box<str>,
+
+        /// the url of the image
+        url: Box<str>,
+
+        /// the width of the image in pixels
+        width: Option<u32>,
+
+        /// the height of the image in pixels
+        height: Option<u32>,
+    },
+}
+
+impl WidgetButton {
+    /// Get the url this button links to.
+    pub fn link_url(&self) -> &str {
+        match self {
+            Self::Text { url, .. } => url,
+            Self::Image { link_url, .. } => link_url,
+        }
+    }
+}
+
+/// A widget with the upcoming events of a google calendar
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarWidget {
+    /// the id of the widget
+    pub id: Box<str>,
+
+    /// the title of the widget
+    pub short_name: Box<str>,
+
+    /// the id of the google calendar, which is usually an email address
+    pub google_calendar_id: Box<str>,
+
+    /// true if the calendar has not been synced with google yet
+    #[serde(default)]
+    pub requires_sync: bool,
+
+    /// which parts of the events are shown
+    pub configuration: CalendarConfiguration,
+
+    /// the upcoming events
+    #[serde(default)]
+    pub data: Vec<CalendarEvent>,
+
+    /// the colors of the widget
+    #[serde(default)]
+    pub styles: WidgetStyles,
+}
+
+/// The display options of a [`CalendarWidget`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarConfiguration {
+    /// the maximum number of events shown
+    pub num_events: u32,
+
+    /// true if the dates of events are shown
+    pub show_date: bool,
+
+    /// true if the descriptions of events are shown
+    pub show_description: bool,
+
+    /// true if the locations of events are shown
+    pub show_location: bool,
+
+    /// true if the times of events are shown
+    pub show_time: bool,
+
+    /// true if the titles of events are shown
+    pub show_title: bool,
+}
+
+/// An event of a [`CalendarWidget`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    /// the title of the event
+    pub title: Box<str>,
+
+    /// the formatted escaped HTML title of the event
+    pub title_html: Option<Box<str>>,
+
+    /// the description of the event
+    pub description: Option<Box<str>>,
+
+    /// the formatted escaped HTML description of the event
+    pub description_html: Option<Box<str>>,
+
+    /// the location of the event
+    pub location: Option<Box<str>>,
+
+    /// the formatted escaped HTML location of the event
+    pub location_html: Option<Box<str>>,
+
+    /// the time the event starts, in UTC epoch-second format
+    pub start_time: f64,
+
+    /// the time the event ends, in UTC epoch-second format
+    pub end_time: f64,
+
+    /// true if the event lasts all day
+    #[serde(default)]
+    pub all_day: bool,
+}
+
+/// A widget with a list of related subreddits
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunityListWidget {
+    /// the id of the widget
+    pub id: Box<str>,
+
+    /// the title of the widget
+    pub short_name: Box<str>,
+
+    /// the subreddits
+    pub data: Vec<WidgetCommunity>,
+
+    /// the colors of the widget
+    #[serde(default)]
+    pub styles: WidgetStyles,
+}
+
+/// A subreddit of a [`CommunityListWidget`]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetCommunity {
+    /// the name of the subreddit, excluding the /r/ prefix
+    pub name: Box<str>,
+
+    /// the number of subscribers of the subreddit
+    pub subscribers: u64,
+
+    /// the url of the icon of the subreddit. May be empty
+    #[serde(default)]
+    pub icon_url: Box<str>,
+
+    /// the url of the new reddit icon of the subreddit. May be empty
+    #[serde(default)]
+    pub community_icon: Box<str>,
+
+    /// the primary color of the subreddit, like "#0079D3". May be empty
+    #[serde(default)]
+    pub primary_color: Box<str>,
+
+    /// true if the current user subscribes to the subreddit
+    #[serde(default)]
+    pub is_subscribed: bool,
+
+    /// true if the subreddit is nsfw
+    #[serde(rename = "isNSFW", default)]
+    pub is_nsfw: bool,
+}
+
+/// The colors of a widget. Empty colors use the colors of the subreddit
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetStyles {
+    /// the color of the title of the widget, like "#FF4500"
+    pub header_color: Option<Box<str>>,
+
+    /// the background color of the widget
+    pub background_color: Option<Box<str>>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_widgets() {
+        let widgets: Widgets = serde_json::from_str(
+            r##"{
+                "items": {
+                    "widget_id-card-2qh1i": {
+                        "kind": "id-card",
+                        "id": "widget_id-card-2qh1i",
+                        "shortName": "Community Details"
+                    },
+                    "widget_1": {
+                        "kind": "textarea",
+                        "id": "widget_1",
+                        "shortName": "Rules",
+                        "text": "Be **nice**",
+                        "textHtml": "&lt;p&gt;Be &lt;strong&gt;nice&lt;/strong&gt;&lt;/p&gt;",
+                        "styles": {"headerColor": "#FF4500", "backgroundColor": ""}
+                    },
+                    "widget_2": {
+                        "kind": "button",
+                        "id": "widget_2",
+                        "shortName": "Links",
+                        "description": "",
+                        "buttons": [
+                            {"kind": "text", "text": "Wiki", "url": "https://www.reddit.com/r/rust/wiki", "color": "#000000"},
+                            {"kind": "image", "text": "Logo", "linkUrl": "https://www.rust-lang.org", "url": "https://example.com/logo.png", "width": 80, "height": 40}
+                        ]
+                    },
+                    "widget_3": {
+                        "kind": "community-list",
+                        "id": "widget_3",
+                        "shortName": "Related",
+                        "data": [{"name": "learnrust", "subscribers": 12, "isNSFW": false, "type": "subreddit"}]
+                    }
+                },
+                "layout": {
+                    "idCardWidget": "widget_id-card-2qh1i",
+                    "moderatorWidget": "widget_moderators-2qh1i",
+                    "topbar": {"order": []},
+                    "sidebar": {"order": ["widget_2", "widget_missing", "widget_1", "widget_3"]}
+                }
+            }"##,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            widgets.get(&widgets.layout.id_card_widget),
+            Some(Widget::Other)
+        ));
+        assert_eq!(
+            widgets.sidebar().filter_map(Widget::id).collect::<Vec<_>>(),
+            ["widget_2", "widget_1", "widget_3"]
+        );
+        assert_eq!(widgets.topbar().count(), 0);
+
+        let Some(Widget::Button(buttons)) = widgets.get("widget_2") else {
+            panic!("expected a button widget");
+        };
+        assert_eq!(
+            buttons
+                .buttons
+                .iter()
+                .map(WidgetButton::link_url)
+                .collect::<Vec<_>>(),
+            [
+                "https://www.reddit.com/r/rust/wiki",
+                "https://www.rust-lang.org"
+            ]
+        );
+
+        let Some(Widget::Textarea(textarea)) = widgets.get("widget_1") else {
+            panic!("expected a textarea widget");
+        };
+        assert_eq!(textarea.styles.header_color.as_deref(), Some("#FF4500"));
+    }
+}